@@ -1,6 +1,9 @@
-use anchor_lang::AccountDeserialize;
+use anchor_lang::{AccountDeserialize, AccountSerialize, Owner};
 use litesvm::LiteSVM;
+use litesvm_utils::StateSurgery;
 use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::error::Error as StdError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,7 +23,8 @@ pub enum AccountError {
 /// This function:
 /// 1. Retrieves the account data from LiteSVM
 /// 2. Deserializes it using Anchor's AccountDeserialize trait
-/// 3. Handles the 8-byte discriminator that Anchor prepends to account data
+/// 3. Handles the discriminator that Anchor prepends to account data (8 bytes by
+///    default, or the custom one declared by the account type in Anchor 0.31+)
 pub fn get_anchor_account<T>(
     svm: &LiteSVM,
     address: &Pubkey,
@@ -34,7 +38,7 @@ where
         .ok_or(AccountError::AccountNotFound(*address))?;
 
     // Deserialize using Anchor's method
-    // Note: Anchor accounts have an 8-byte discriminator at the beginning
+    // Note: the discriminator check uses T::DISCRIMINATOR, whatever its length
     let mut data_slice: &[u8] = &account.data;
     T::try_deserialize(&mut data_slice)
         .map_err(|e| AccountError::DeserializationError(e.to_string()))
@@ -66,6 +70,129 @@ where
         .map_err(|e| AccountError::DeserializationError(e.to_string()))
 }
 
+/// Anchor account writes for [`StateSurgery`]
+///
+/// # Example
+/// ```ignore
+/// let mut vault: Vault = ctx.get_account(&vault_pda)?;
+/// vault.locked = false;
+/// ctx.svm.unsafe_state().set_anchor_account(&vault_pda, &vault)?;
+/// ```
+pub trait AnchorStateSurgery {
+    /// Serialize `account` (with its discriminator) into `address`, owned by `T::owner()`
+    ///
+    /// An existing account keeps its lamports; a new one is made rent-exempt. The
+    /// write is recorded in the surgery report as `set_anchor_account`.
+    fn set_anchor_account<T>(
+        &mut self,
+        address: &Pubkey,
+        account: &T,
+    ) -> Result<(), Box<dyn StdError>>
+    where
+        T: AccountSerialize + Owner;
+}
+
+impl AnchorStateSurgery for StateSurgery<'_> {
+    #[track_caller]
+    fn set_anchor_account<T>(
+        &mut self,
+        address: &Pubkey,
+        account: &T,
+    ) -> Result<(), Box<dyn StdError>>
+    where
+        T: AccountSerialize + Owner,
+    {
+        let mut data = Vec::new();
+        account
+            .try_serialize(&mut data)
+            .map_err(|e| format!("Failed to serialize account {}: {}", address, e))?;
+        let lamports = match self.svm().get_account(address) {
+            Some(existing) => existing.lamports,
+            None => self.svm().minimum_balance_for_rent_exemption(data.len()),
+        };
+        self.write(
+            "set_anchor_account",
+            address,
+            Account {
+                lamports,
+                data,
+                owner: T::owner(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+    }
+}
+
+/// Assert one named field of a deserialized Anchor account
+///
+/// Expands to a read through `AnchorContext::get_account` and an equality check
+/// whose failure message names the type and field, e.g.
+/// `Vault.amount mismatch for account 7xKX.... Expected: 1000, Actual: 900`.
+///
+/// ```ignore
+/// assert_account_field!(ctx, &vault_pda, Vault, amount, 1_000);
+/// assert_account_field!(ctx, &vault_pda, Vault, owner, user.pubkey());
+/// ```
+#[macro_export]
+macro_rules! assert_account_field {
+    ($ctx:expr, $address:expr, $ty:ty, $field:ident, $expected:expr) => {{
+        let address = $address;
+        let account: $ty = $ctx
+            .get_account(address)
+            .unwrap_or_else(|e| panic!("Failed to read account {}: {}", address, e));
+        let (actual, expected) = (&account.$field, &$expected);
+        assert!(
+            actual == expected,
+            "{}.{} mismatch for account {}. Expected: {:?}, Actual: {:?}",
+            stringify!($ty),
+            stringify!($field),
+            address,
+            expected,
+            actual
+        );
+    }};
+}
+
+/// Line diff of two pretty-printed (`{:#?}`) values
+///
+/// Lines only in `expected` are prefixed with `- `, lines only in `actual` with
+/// `+ ` and shared lines with two spaces, so a mismatch shows exactly which fields
+/// differ.
+pub(crate) fn debug_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push(format!("  {}", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("- {}", expected[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", actual[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +246,191 @@ mod tests {
         }
     }
 
+    impl anchor_lang::AccountSerialize for TestAccount {
+        fn try_serialize<W: std::io::Write>(
+            &self,
+            writer: &mut W,
+        ) -> Result<(), anchor_lang::error::Error> {
+            writer
+                .write_all(Self::DISCRIMINATOR)
+                .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize)?;
+            BorshSerialize::serialize(self, writer)
+                .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize.into())
+        }
+    }
+
+    impl anchor_lang::Owner for TestAccount {
+        fn owner() -> Pubkey {
+            Pubkey::new_from_array([9; 32])
+        }
+    }
+
+    impl anchor_lang::AnchorSerialize for TestAccount {
+        fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+            BorshSerialize::serialize(self, writer)
+        }
+    }
+
+    #[test]
+    fn test_set_anchor_account_is_recorded() {
+        use litesvm_utils::TestHelpers;
+
+        let mut svm = LiteSVM::new();
+        let addr = Pubkey::new_unique();
+        let written = TestAccount {
+            value: 7,
+            owner: Pubkey::new_unique(),
+        };
+        svm.unsafe_state().set_anchor_account(&addr, &written).unwrap();
+
+        let read: TestAccount = get_anchor_account(&svm, &addr).unwrap();
+        assert_eq!(read.value, 7);
+        assert_eq!(read.owner, written.owner);
+        assert_eq!(svm.get_account(&addr).unwrap().owner, TestAccount::owner());
+
+        let report = litesvm_utils::surgery_report(&svm);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].operation, "set_anchor_account");
+        assert_eq!(report[0].pubkey, addr);
+    }
+
+    #[test]
+    fn test_context_set_anchor_account() {
+        let program_id = Pubkey::new_unique();
+        let mut ctx = crate::AnchorContext::new(LiteSVM::new(), program_id);
+        let addr = Pubkey::new_unique();
+        let written = TestAccount {
+            value: 42,
+            owner: Pubkey::new_unique(),
+        };
+        ctx.set_anchor_account(&addr, &written).unwrap();
+
+        let read: TestAccount = ctx.get_account(&addr).unwrap();
+        assert_eq!(read, written);
+        let account = ctx.svm.get_account(&addr).unwrap();
+        assert_eq!(account.owner, program_id);
+        assert_eq!(&account.data[..8], TestAccount::DISCRIMINATOR);
+        assert_eq!(
+            account.lamports,
+            ctx.svm.minimum_balance_for_rent_exemption(account.data.len())
+        );
+        assert_eq!(litesvm_utils::surgery_report(&ctx.svm)[0].location.file(), file!());
+    }
+
+    #[test]
+    fn test_get_all_accounts() {
+        use litesvm_utils::TestHelpers;
+
+        let mut ctx = crate::AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let owner = Pubkey::new_unique();
+        let mut addresses: Vec<Pubkey> = (0..3u64)
+            .map(|value| {
+                let addr = Pubkey::new_unique();
+                ctx.set_anchor_account(&addr, &TestAccount { value, owner })
+                    .unwrap();
+                addr
+            })
+            .collect();
+        addresses.sort();
+
+        // Same program, different discriminator
+        let other = Pubkey::new_unique();
+        let program_id = ctx.program_id;
+        ctx.svm
+            .account_builder(other)
+            .owner(&program_id)
+            .data(vec![0; 48])
+            .create()
+            .unwrap();
+
+        let accounts: Vec<(Pubkey, TestAccount)> = ctx.get_all_accounts().unwrap();
+        let found: Vec<Pubkey> = accounts.iter().map(|(addr, _)| *addr).collect();
+        assert_eq!(found, addresses);
+        assert!(accounts.iter().all(|(_, account)| account.owner == owner));
+    }
+
+    #[test]
+    fn test_context_modify_anchor_account() {
+        let mut ctx = crate::AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let addr = Pubkey::new_unique();
+        let written = TestAccount {
+            value: 1,
+            owner: Pubkey::new_unique(),
+        };
+        ctx.set_anchor_account(&addr, &written).unwrap();
+        let lamports = ctx.svm.get_balance(&addr).unwrap();
+
+        ctx.modify_anchor_account(&addr, |account: &mut TestAccount| account.value = u64::MAX)
+            .unwrap();
+        let read: TestAccount = ctx.get_account(&addr).unwrap();
+        assert_eq!(read.value, u64::MAX);
+        assert_eq!(read.owner, written.owner);
+        assert_eq!(ctx.svm.get_balance(&addr), Some(lamports));
+
+        let missing = Pubkey::new_unique();
+        assert!(ctx
+            .modify_anchor_account(&missing, |account: &mut TestAccount| account.value = 0)
+            .is_err());
+        let operations: Vec<String> = litesvm_utils::surgery_report(&ctx.svm)
+            .into_iter()
+            .map(|record| record.operation)
+            .collect();
+        assert_eq!(operations, ["set_anchor_account", "modify_anchor_account"]);
+    }
+
+    #[test]
+    fn test_assert_account_eq() {
+        let mut ctx = crate::AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let addr = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        ctx.set_anchor_account(&addr, &TestAccount { value: 5, owner }).unwrap();
+        ctx.assert_account_eq(&addr, &TestAccount { value: 5, owner });
+
+        let message = std::panic::catch_unwind(|| {
+            ctx.assert_account_eq(&addr, &TestAccount { value: 6, owner })
+        })
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+        assert!(message.contains("-     value: 6,\n+     value: 5,"));
+        assert!(message.contains(&format!("      owner: {:?},", owner)));
+    }
+
+    #[test]
+    fn test_assert_field() {
+        let mut ctx = crate::AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let addr = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        ctx.set_anchor_account(&addr, &TestAccount { value: 5, owner }).unwrap();
+
+        ctx.assert_field(&addr, |account: &TestAccount| account.value, 5);
+        crate::assert_account_field!(ctx, &addr, TestAccount, owner, owner);
+
+        let message = std::panic::catch_unwind(|| {
+            crate::assert_account_field!(ctx, &addr, TestAccount, value, 6);
+        })
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+        assert_eq!(
+            *message,
+            format!("TestAccount.value mismatch for account {}. Expected: 6, Actual: 5", addr)
+        );
+        let message = std::panic::catch_unwind(|| {
+            ctx.assert_field(&addr, |account: &TestAccount| account.value, 6);
+        })
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+        assert!(message.starts_with("Field mismatch for "));
+    }
+
+    #[test]
+    fn test_debug_diff() {
+        assert_eq!(debug_diff("a\nb\nc", "a\nx\nc"), "  a\n- b\n+ x\n  c");
+        assert_eq!(debug_diff("a", "a\nb"), "  a\n+ b");
+    }
+
     #[test]
     fn test_get_anchor_account_with_discriminator() {
         let mut svm = LiteSVM::new();
@@ -3,9 +3,11 @@
 //! This module provides builders specifically designed for Anchor programs,
 //! extending the base LiteSVM builder functionality.
 
+use crate::idl::{discover_idls, idl_program_id, Idl};
 use crate::AnchorContext;
-use litesvm_utils::LiteSVMBuilder;
+use litesvm_utils::{fund_account, new_keypair, LiteSVMBuilder};
 use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
 use solana_sdk::signature::{Keypair, Signer};
 
 /// Builder for creating an AnchorContext with programs pre-deployed
@@ -36,8 +38,10 @@ use solana_sdk::signature::{Keypair, Signer};
 /// ```
 pub struct AnchorLiteSVM {
     svm_builder: LiteSVMBuilder,
-    primary_program_id: Option<Pubkey>,
+    program_ids: Vec<Pubkey>,
     payer: Option<Keypair>,
+    idls: Vec<Idl>,
+    idl_autoload: bool,
 }
 
 impl AnchorLiteSVM {
@@ -45,8 +49,10 @@ impl AnchorLiteSVM {
     pub fn new() -> Self {
         Self {
             svm_builder: LiteSVMBuilder::new(),
-            primary_program_id: None,
+            program_ids: Vec::new(),
             payer: None,
+            idls: Vec::new(),
+            idl_autoload: true,
         }
     }
 
@@ -58,6 +64,188 @@ impl AnchorLiteSVM {
         self
     }
 
+    /// Register an IDL with the context
+    ///
+    /// Explicitly registered IDLs take precedence over auto-loaded ones.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut ctx = AnchorLiteSVM::new()
+    ///     .deploy_program(program_id, program_bytes)
+    ///     .with_idl(load_idl("target/idl/my_program.json")?)
+    ///     .build();
+    /// ```
+    pub fn with_idl(mut self, idl: Idl) -> Self {
+        self.idls.push(idl);
+        self
+    }
+
+    /// Enable or disable IDL auto-loading (enabled by default)
+    ///
+    /// When enabled, `build()` loads the IDLs found in `target/idl/` and `idl/`
+    /// of the Anchor workspace (see [`crate::idl::discover_idls`]) for every
+    /// deployed program.
+    pub fn with_idl_autoload(mut self, autoload: bool) -> Self {
+        self.idl_autoload = autoload;
+        self
+    }
+
+    /// Enable or disable transaction signature verification
+    ///
+    /// See [`LiteSVMBuilder::with_sigverify`].
+    pub fn with_sigverify(mut self, sigverify: bool) -> Self {
+        self.svm_builder = self.svm_builder.with_sigverify(sigverify);
+        self
+    }
+
+    /// Enable or disable the recent blockhash check
+    ///
+    /// See [`LiteSVMBuilder::with_blockhash_check`].
+    pub fn with_blockhash_check(mut self, check: bool) -> Self {
+        self.svm_builder = self.svm_builder.with_blockhash_check(check);
+        self
+    }
+
+    /// Set the rent rate in lamports per byte-year
+    ///
+    /// See [`LiteSVMBuilder::with_lamports_per_byte_year`].
+    pub fn with_lamports_per_byte_year(mut self, lamports_per_byte_year: u64) -> Self {
+        self.svm_builder = self
+            .svm_builder
+            .with_lamports_per_byte_year(lamports_per_byte_year);
+        self
+    }
+
+    /// Replace the Rent sysvar
+    ///
+    /// See [`LiteSVMBuilder::with_rent`].
+    pub fn with_rent(mut self, rent: Rent) -> Self {
+        self.svm_builder = self.svm_builder.with_rent(rent);
+        self
+    }
+
+    /// Make every account rent-exempt, whatever its balance
+    ///
+    /// See [`LiteSVMBuilder::rent_free`].
+    pub fn rent_free(mut self) -> Self {
+        self.svm_builder = self.svm_builder.rent_free();
+        self
+    }
+
+    /// Limit how many bytes of program logs are collected per transaction
+    ///
+    /// See [`LiteSVMBuilder::with_log_bytes_limit`].
+    pub fn with_log_bytes_limit(mut self, limit: Option<usize>) -> Self {
+        self.svm_builder = self.svm_builder.with_log_bytes_limit(limit);
+        self
+    }
+
+    /// Load the SPL program binaries bundled with LiteSVM
+    ///
+    /// See [`LiteSVMBuilder::with_spl_programs`].
+    pub fn with_spl_programs(mut self) -> Self {
+        self.svm_builder = self.svm_builder.with_spl_programs();
+        self
+    }
+
+    /// Copy an account from a live cluster at build time
+    ///
+    /// See [`LiteSVMBuilder::clone_account_from`].
+    #[cfg(feature = "cluster")]
+    pub fn clone_account_from(mut self, rpc_url: &str, pubkey: Pubkey) -> Self {
+        self.svm_builder = self.svm_builder.clone_account_from(rpc_url, pubkey);
+        self
+    }
+
+    /// Copy an executable program from a live cluster at build time
+    ///
+    /// See [`LiteSVMBuilder::clone_program_from`].
+    #[cfg(feature = "cluster")]
+    pub fn clone_program_from(mut self, rpc_url: &str, program_id: Pubkey) -> Self {
+        self.svm_builder = self.svm_builder.clone_program_from(rpc_url, program_id);
+        self
+    }
+
+    /// Load every account fixture in `dir` at build time
+    ///
+    /// See [`LiteSVMBuilder::with_fixtures`].
+    pub fn with_fixtures(mut self, dir: impl AsRef<std::path::Path>) -> Self {
+        self.svm_builder = self.svm_builder.with_fixtures(dir);
+        self
+    }
+
+    /// Load an account from a `solana account --output json` dump at build time
+    ///
+    /// See [`LiteSVMBuilder::add_account_from_file`].
+    pub fn add_account_from_file(mut self, path: impl AsRef<std::path::Path>) -> Self {
+        self.svm_builder = self.svm_builder.add_account_from_file(path);
+        self
+    }
+
+    /// Load an account from a JSON dump at build time, writing it at `address`
+    ///
+    /// See [`LiteSVMBuilder::add_account_from_file_at`].
+    pub fn add_account_from_file_at(
+        mut self,
+        address: Pubkey,
+        path: impl AsRef<std::path::Path>,
+    ) -> Self {
+        self.svm_builder = self.svm_builder.add_account_from_file_at(address, path);
+        self
+    }
+
+    /// Record account diffs for every executed transaction
+    ///
+    /// See [`LiteSVMBuilder::with_account_diffs`].
+    pub fn with_account_diffs(mut self) -> Self {
+        self.svm_builder = self.svm_builder.with_account_diffs();
+        self
+    }
+
+    /// Prepend a compute budget to every executed transaction
+    ///
+    /// See [`LiteSVMBuilder::with_default_compute_budget`].
+    /// [`AnchorContext::set_compute_budget`] overrides it for one context.
+    pub fn with_default_compute_budget(mut self, limit: u32, price: u64) -> Self {
+        self.svm_builder = self.svm_builder.with_default_compute_budget(limit, price);
+        self
+    }
+
+    /// Activate a runtime feature
+    ///
+    /// See [`LiteSVMBuilder::with_feature_enabled`].
+    pub fn with_feature_enabled(mut self, feature_id: Pubkey) -> Self {
+        self.svm_builder = self.svm_builder.with_feature_enabled(feature_id);
+        self
+    }
+
+    /// Deactivate a runtime feature
+    ///
+    /// See [`LiteSVMBuilder::with_feature_disabled`].
+    pub fn with_feature_disabled(mut self, feature_id: Pubkey) -> Self {
+        self.svm_builder = self.svm_builder.with_feature_disabled(feature_id);
+        self
+    }
+
+    /// Create helper keypairs, including the default payer, from a seeded sequence
+    ///
+    /// See [`LiteSVMBuilder::with_keypair_seed`]. The same test then produces the
+    /// same pubkeys on every run.
+    pub fn with_keypair_seed(mut self, seed: u64) -> Self {
+        self.svm_builder = self.svm_builder.with_keypair_seed(seed);
+        self
+    }
+
+    /// Pay all helper airdrops from a named, capped faucet
+    ///
+    /// See [`LiteSVMBuilder::with_faucet`]. The default payer is funded from the
+    /// faucet too.
+    pub fn with_faucet(mut self, name: &str, faucet: &Keypair, cap: u64) -> Self {
+        self.svm_builder = self.svm_builder.with_faucet(name, faucet, cap);
+        self
+    }
+
     /// Add a program to be deployed
     ///
     /// The first program added becomes the primary program for the AnchorContext.
@@ -73,11 +261,8 @@ impl AnchorLiteSVM {
     /// builder.deploy_program(program_id, program_bytes)
     /// ```
     pub fn deploy_program(mut self, program_id: Pubkey, program_bytes: &[u8]) -> Self {
-        // Set the first program as primary if not already set
-        if self.primary_program_id.is_none() {
-            self.primary_program_id = Some(program_id);
-        }
-
+        // The first program added becomes the primary program
+        self.program_ids.push(program_id);
         self.svm_builder = self.svm_builder.deploy_program(program_id, program_bytes);
         self
     }
@@ -90,7 +275,7 @@ impl AnchorLiteSVM {
     ///
     /// # Panics
     ///
-    /// Panics if no programs were added
+    /// Panics if no programs were added or a registered IDL has an invalid address
     ///
     /// # Example
     ///
@@ -98,20 +283,37 @@ impl AnchorLiteSVM {
     /// let mut ctx = builder.build();
     /// ```
     pub fn build(self) -> AnchorContext {
-        let program_id = self.primary_program_id
+        let program_id = *self.program_ids
+            .first()
             .expect("No programs added. Call deploy_program() at least once.");
 
         let mut svm = self.svm_builder.build();
 
         // Create or use provided payer
         let payer = self.payer.unwrap_or_else(|| {
-            let payer = Keypair::new();
+            let payer = new_keypair(&mut svm);
             // Fund the payer account
-            svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+            fund_account(&mut svm, &payer.pubkey(), 10_000_000_000).unwrap();
             payer
         });
 
-        AnchorContext::new_with_payer(svm, program_id, payer)
+        let mut ctx = AnchorContext::new_with_payer(svm, program_id, payer);
+
+        if self.idl_autoload {
+            for idl in discover_idls() {
+                let deployed = idl_program_id(&idl)
+                    .map(|id| self.program_ids.contains(&id))
+                    .unwrap_or(false);
+                if deployed {
+                    ctx.add_idl(idl).expect("IDL address was validated above");
+                }
+            }
+        }
+        for idl in self.idls {
+            ctx.add_idl(idl).expect("Invalid program address in IDL");
+        }
+
+        ctx
     }
 
     /// Convenience method to quickly set up a single Anchor program
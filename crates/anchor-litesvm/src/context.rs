@@ -1,13 +1,28 @@
-use crate::account::AccountError;
+use crate::account::{debug_diff, AccountError};
+use crate::idl::{idl_program_id, instruction_discriminator, Idl, IdlError};
+use crate::idl_instruction::IdlInstructionBuilder;
+use crate::instruction::{build_anchor_instruction_with_discriminator, calculate_anchor_discriminator};
 use crate::program::Program;
-use anchor_lang::AccountDeserialize;
+use crate::return_data::ReturnDataHelpers;
+use anchor_lang::{
+    AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize, Discriminator,
+};
 use litesvm::LiteSVM;
+use solana_program::instruction::{AccountMeta, Instruction};
 use solana_program::pubkey::Pubkey;
 use solana_sdk::{
+    account::Account,
     signature::{Keypair, Signature, Signer},
     transaction::Transaction,
 };
-use litesvm_utils::TransactionResult;
+use litesvm_utils::{
+    default_compute_budget, fund_account, new_keypair, track_accounts, with_compute_budget,
+    AccountFilter, ActorBuilder, ActorHelpers, ComputeBudget, LogFilter, PayerPool,
+    PersonaBuilder, SvmSnapshot, TestHelpers, TransactionHelpers, TransactionResult,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::collections::HashMap;
 
 /// Production-compatible testing context for Anchor programs.
 ///
@@ -22,6 +37,41 @@ pub struct AnchorContext {
     payer: Keypair,
     /// The program instance for instruction building
     program: Program,
+    /// Loaded IDLs keyed by program ID
+    idls: HashMap<Pubkey, Idl>,
+    /// Optional rotating fee payers for executed transactions
+    payer_pool: Option<PayerPool>,
+    /// Whether to warn about writable accounts left untouched by a transaction
+    warn_unused_accounts: bool,
+    /// Writable accounts the last executed transaction never modified
+    untouched_accounts: Vec<Pubkey>,
+    /// Whether to create missing associated token accounts before executing
+    auto_create_atas: bool,
+    /// Transactions waiting for their slot, in scheduling order
+    scheduled: Vec<ScheduledTransaction>,
+    /// How much of each executed transaction's logs to print
+    log_filter: LogFilter,
+    /// Every transaction executed through the context, oldest first
+    history: Vec<TransactionResult>,
+    /// Compute budget instructions prepended to every executed transaction
+    compute_budget: Option<ComputeBudget>,
+}
+
+/// A transaction queued with [`AnchorContext::schedule_at_slot`]
+struct ScheduledTransaction {
+    slot: u64,
+    instructions: Vec<Instruction>,
+    signers: Vec<Keypair>,
+}
+
+impl Clone for ScheduledTransaction {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot,
+            instructions: self.instructions.clone(),
+            signers: self.signers.iter().map(|s| s.insecure_clone()).collect(),
+        }
+    }
 }
 
 impl AnchorContext {
@@ -42,8 +92,8 @@ impl AnchorContext {
     /// ```
     pub fn new(mut svm: LiteSVM, program_id: Pubkey) -> Self {
         // Create a default payer and fund it
-        let payer = Keypair::new();
-        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+        let payer = new_keypair(&mut svm);
+        fund_account(&mut svm, &payer.pubkey(), 10_000_000_000).unwrap();
 
         let program = Program::new(program_id);
 
@@ -52,6 +102,15 @@ impl AnchorContext {
             program_id,
             payer,
             program,
+            idls: HashMap::new(),
+            payer_pool: None,
+            warn_unused_accounts: false,
+            untouched_accounts: Vec::new(),
+            auto_create_atas: false,
+            scheduled: Vec::new(),
+            log_filter: LogFilter::default(),
+            history: Vec::new(),
+            compute_budget: None,
         }
     }
 
@@ -68,6 +127,15 @@ impl AnchorContext {
             program_id,
             payer,
             program,
+            idls: HashMap::new(),
+            payer_pool: None,
+            warn_unused_accounts: false,
+            untouched_accounts: Vec::new(),
+            auto_create_atas: false,
+            scheduled: Vec::new(),
+            log_filter: LogFilter::default(),
+            history: Vec::new(),
+            compute_budget: None,
         }
     }
 
@@ -91,6 +159,250 @@ impl AnchorContext {
         &self.payer
     }
 
+    /// Rotate fee payers across executed transactions
+    ///
+    /// Once set, `execute_instruction(s)` pays every transaction with the next payer
+    /// of the pool instead of the first signer, so tests can't silently depend on a
+    /// single implicit payer. Pass `None` to restore the default behavior.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let pool = PayerPool::create(&mut ctx.svm, 3, 10_000_000_000)?;
+    /// ctx.set_payer_pool(Some(pool));
+    /// ```
+    pub fn set_payer_pool(&mut self, pool: Option<PayerPool>) {
+        self.payer_pool = pool;
+    }
+
+    /// Get the payer pool, if one is configured
+    pub fn payer_pool(&self) -> Option<&PayerPool> {
+        self.payer_pool.as_ref()
+    }
+
+    /// Warn about writable accounts that executed transactions never modify
+    ///
+    /// When enabled, every successful `execute_instruction(s)` compares the writable,
+    /// non-signer accounts it passed before and after execution and prints a warning
+    /// for each one left unchanged. Such accounts are often leftovers from a
+    /// copy-pasted account list. Reads can't be observed, so read-only accounts are
+    /// not checked.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.warn_unused_accounts(true);
+    /// ctx.execute_instruction(ix, &[&user])?.assert_success();
+    /// assert!(ctx.untouched_accounts().is_empty());
+    /// ```
+    pub fn warn_unused_accounts(&mut self, enabled: bool) {
+        self.warn_unused_accounts = enabled;
+    }
+
+    /// Get the writable accounts the last executed transaction left unchanged
+    ///
+    /// Only populated while [`warn_unused_accounts`](Self::warn_unused_accounts) is enabled.
+    pub fn untouched_accounts(&self) -> &[Pubkey] {
+        &self.untouched_accounts
+    }
+
+    /// Create missing associated token accounts before executing instructions
+    ///
+    /// When enabled, `execute_instruction(s)` looks for accounts that don't exist yet
+    /// and are the ATA of another account and a mint passed to the same instruction,
+    /// and prepends idempotent create instructions paid by the fee payer. This mirrors
+    /// what production clients usually do before sending a transaction.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.auto_create_atas(true);
+    /// // `recipient_ata` is created on the fly
+    /// ctx.execute_instruction(transfer_ix, &[&sender])?.assert_success();
+    /// ```
+    pub fn auto_create_atas(&mut self, enabled: bool) {
+        self.auto_create_atas = enabled;
+    }
+
+    /// Control how much of each executed transaction's logs is printed
+    ///
+    /// Defaults to [`LogFilter::Quiet`]. Use [`LogFilter::ErrorsOnly`] in large scenario
+    /// tests to see only the transactions that failed, or [`LogFilter::Full`] while
+    /// debugging. Setting `LITESVM_LOG_FILTER=full` overrides this for a single run.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.set_log_filter(LogFilter::ErrorsOnly);
+    /// ```
+    pub fn set_log_filter(&mut self, filter: LogFilter) {
+        self.log_filter = filter;
+    }
+
+    /// Get the current log filter
+    pub fn log_filter(&self) -> LogFilter {
+        self.log_filter
+    }
+
+    /// Prepend compute budget instructions to every executed or simulated transaction
+    ///
+    /// Use it when the program under test needs more than the default 200k compute
+    /// units per instruction. Overrides the builder's
+    /// [`with_default_compute_budget`](crate::AnchorLiteSVM::with_default_compute_budget);
+    /// `None` falls back to that default again. Transactions that contain their own
+    /// ComputeBudget instruction are sent as they are.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.set_compute_budget(Some(ComputeBudget::new().with_compute_unit_limit(600_000)));
+    /// ```
+    pub fn set_compute_budget(&mut self, budget: Option<ComputeBudget>) {
+        self.compute_budget = budget;
+    }
+
+    /// Get the compute budget applied to executed transactions, if any
+    pub fn compute_budget(&self) -> Option<&ComputeBudget> {
+        self.compute_budget.as_ref()
+    }
+
+    /// The context's compute budget, falling back to the VM's default
+    fn effective_compute_budget(&self) -> Option<ComputeBudget> {
+        self.compute_budget.or_else(|| default_compute_budget(&self.svm))
+    }
+
+    /// Start building a funded actor with a token portfolio
+    ///
+    /// # Example
+    /// ```ignore
+    /// let alice = ctx.create_actor()
+    ///     .with_sol(10)
+    ///     .with_tokens(&usdc, 1_000_000)
+    ///     .build()?;
+    /// ```
+    pub fn create_actor(&mut self) -> ActorBuilder<'_> {
+        self.svm.create_actor()
+    }
+
+    /// Start building a funded wallet with ATAs and starting token balances
+    ///
+    /// Same as [`create_actor`](Self::create_actor), with the shorter
+    /// [`sol`](ActorBuilder::sol) and [`token`](ActorBuilder::token) setters.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let trader = ctx.persona().sol(10).token(&mint_a, 1_000).token(&mint_b, 0).build()?;
+    /// ```
+    pub fn persona(&mut self) -> PersonaBuilder<'_> {
+        self.svm.persona()
+    }
+
+    /// Register an IDL with this context
+    ///
+    /// The IDL is keyed by the program address it declares, replacing any
+    /// IDL previously registered for that program.
+    pub fn add_idl(&mut self, idl: Idl) -> Result<(), IdlError> {
+        let program_id = idl_program_id(&idl)?;
+        self.idls.insert(program_id, idl);
+        Ok(())
+    }
+
+    /// Get the discriminator of an instruction of the primary program
+    ///
+    /// Reads the discriminator from the loaded IDL so programs using custom
+    /// discriminators work, and falls back to Anchor's default
+    /// `sha256("global:<name>")[..8]` when no IDL (or no matching instruction) is loaded.
+    pub fn instruction_discriminator(&self, instruction_name: &str) -> Vec<u8> {
+        self.idl()
+            .and_then(|idl| instruction_discriminator(idl, instruction_name))
+            .map(<[u8]>::to_vec)
+            .unwrap_or_else(|| calculate_anchor_discriminator(instruction_name).to_vec())
+    }
+
+    /// Build an instruction for the primary program by name
+    ///
+    /// The discriminator is resolved with [`AnchorContext::instruction_discriminator`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let ix = ctx.build_instruction("make", accounts, (seed, amount))?;
+    /// ```
+    pub fn build_instruction<T>(
+        &self,
+        instruction_name: &str,
+        accounts: Vec<AccountMeta>,
+        args: T,
+    ) -> Result<Instruction, Box<dyn std::error::Error>>
+    where
+        T: AnchorSerialize,
+    {
+        build_anchor_instruction_with_discriminator(
+            &self.program_id,
+            &self.instruction_discriminator(instruction_name),
+            accounts,
+            args,
+        )
+    }
+
+    /// Call a read-only instruction of the primary program and decode its return value
+    ///
+    /// Builds the instruction like [`build_instruction`](Self::build_instruction),
+    /// simulates it paid for by the context's payer, and deserializes the return
+    /// data as `T`, the way clients call Anchor view functions. Nothing is
+    /// committed, so the instruction doesn't need to be read-only to be called.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the instruction can't be built, the simulation fails, or the
+    /// return data is missing or isn't a `T`
+    ///
+    /// # Example
+    /// ```ignore
+    /// // pub fn get_price(ctx: Context<GetPrice>, amount_in: u64) -> Result<u64>
+    /// let price: u64 = ctx.view("get_price", accounts, 1_000u64)?;
+    /// ```
+    pub fn view<T, A>(
+        &self,
+        instruction_name: &str,
+        accounts: Vec<AccountMeta>,
+        args: A,
+    ) -> Result<T, Box<dyn std::error::Error>>
+    where
+        T: AnchorDeserialize,
+        A: AnchorSerialize,
+    {
+        let instruction = self.build_instruction(instruction_name, accounts, args)?;
+        let result = self.simulate_transaction(&[instruction], &[], instruction_name.to_string())?;
+        if let Some(error) = result.error() {
+            return Err(format!("View {} failed: {}", instruction_name, error).into());
+        }
+        Ok(result.return_data_as()?)
+    }
+
+    /// Get the IDL of the primary program, if one is loaded
+    pub fn idl(&self) -> Option<&Idl> {
+        self.idls.get(&self.program_id)
+    }
+
+    /// Get the IDL of a specific program, if one is loaded
+    pub fn idl_for(&self, program_id: &Pubkey) -> Option<&Idl> {
+        self.idls.get(program_id)
+    }
+
+    /// Build an instruction of the primary program from its IDL
+    ///
+    /// Argument types and the discriminator come from the loaded IDL, so no
+    /// generated client types are needed, and PDAs and `has_one` accounts are
+    /// derived from the IDL against the context's current state. Fails at
+    /// `.instruction()` if no IDL is loaded.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let ix = ctx
+    ///     .idl_instruction("make")
+    ///     .arg("seed", 42u64)
+    ///     .account("maker", maker.pubkey())
+    ///     .instruction()?;
+    /// ```
+    pub fn idl_instruction(&self, name: &str) -> IdlInstructionBuilder<'_> {
+        IdlInstructionBuilder::for_program(self.idl(), self.program_id, name).with_svm(&self.svm)
+    }
+
     /// Execute a single instruction using LiteSVM
     ///
     /// This is a convenience method for executing instructions.
@@ -110,81 +422,364 @@ impl AnchorContext {
         instruction: solana_program::instruction::Instruction,
         signers: &[&Keypair],
     ) -> Result<TransactionResult, Box<dyn std::error::Error>> {
-        // Determine the payer - use the first signer if provided, otherwise use the context's payer
-        let payer_pubkey = if !signers.is_empty() {
-            signers[0].pubkey()
-        } else {
-            self.payer.pubkey()
-        };
+        let name = format!("instruction to {}", instruction.program_id);
+        self.execute(std::slice::from_ref(&instruction), signers, name)
+    }
 
-        // Build and sign the transaction
+    /// Execute multiple instructions in a single transaction
+    pub fn execute_instructions(
+        &mut self,
+        instructions: Vec<solana_program::instruction::Instruction>,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, Box<dyn std::error::Error>> {
+        self.execute(&instructions, signers, "batch transaction".to_string())
+    }
+
+    /// Execute an instruction against the current state without committing anything
+    ///
+    /// The fee payer is the first signer, or the context's payer if there are no
+    /// signers; the payer pool isn't rotated. See
+    /// [`TransactionHelpers::simulate_instruction`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let preview = ctx.simulate(withdraw_ix.clone(), &[&user])?;
+    /// preview.assert_error("InsufficientFunds");
+    /// ```
+    pub fn simulate(
+        &self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, Box<dyn std::error::Error>> {
+        let name = format!("instruction to {}", instruction.program_id);
+        self.simulate_transaction(std::slice::from_ref(&instruction), signers, name)
+    }
+
+    /// Build and simulate a transaction, paid for like [`simulate`](Self::simulate)
+    fn simulate_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        name: String,
+    ) -> Result<TransactionResult, Box<dyn std::error::Error>> {
+        let mut all_signers = signers.to_vec();
+        if all_signers.is_empty() {
+            all_signers.push(&self.payer);
+        }
+        let all_instructions = with_compute_budget(self.effective_compute_budget(), instructions);
         let tx = Transaction::new_signed_with_payer(
-            &[instruction.clone()],
-            Some(&payer_pubkey),
-            signers,
+            &all_instructions,
+            Some(&all_signers[0].pubkey()),
+            &all_signers,
             self.svm.latest_blockhash(),
         );
+        let result = self
+            .svm
+            .simulate_transaction_result(tx)?
+            .with_instruction_name(name)
+            .with_prepended_instructions(all_instructions.len() - instructions.len());
+        Ok(self.with_idl_error_names(result))
+    }
 
-        // Execute the transaction
-        match self.svm.send_transaction(tx) {
-            Ok(result) => Ok(TransactionResult::new(
-                result,
-                Some(format!("instruction to {}", instruction.program_id)),
-            )),
-            Err(failed) => Ok(TransactionResult::new_failed(
-                format!("{:?}", failed.err),
-                failed.meta,
-                Some(format!("instruction to {}", instruction.program_id)),
-            )),
+    /// Name the custom error codes in `result` after the errors of the loaded IDLs
+    fn with_idl_error_names(&self, result: TransactionResult) -> TransactionResult {
+        self.idls.iter().fold(result, |result, (program_id, idl)| {
+            result.with_error_names(
+                *program_id,
+                idl.errors.iter().map(|error| (error.code, error.name.clone())),
+            )
+        })
+    }
+
+    /// Queue an instruction to execute once the chain reaches `slot`
+    ///
+    /// Nothing runs until [`run_until_slot`](Self::run_until_slot) is called. The
+    /// signers are copied, so the queue doesn't borrow them.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.schedule_at_slot(100, bid_ix, &[&alice]);
+    /// ctx.schedule_at_slot(250, settle_ix, &[&auctioneer]);
+    /// let results = ctx.run_until_slot(300)?;
+    /// results[1].1.assert_success();
+    /// ```
+    pub fn schedule_at_slot(&mut self, slot: u64, instruction: Instruction, signers: &[&Keypair]) {
+        self.scheduled.push(ScheduledTransaction {
+            slot,
+            instructions: vec![instruction],
+            signers: signers.iter().map(|s| s.insecure_clone()).collect(),
+        });
+    }
+
+    /// Advance to `slot`, executing queued transactions at their scheduled slots
+    ///
+    /// Transactions run in slot order; those scheduled for the same slot run in the
+    /// order they were queued. Transactions scheduled after `slot` stay queued.
+    /// Returns each executed transaction's slot and result.
+    pub fn run_until_slot(
+        &mut self,
+        slot: u64,
+    ) -> Result<Vec<(u64, TransactionResult)>, Box<dyn std::error::Error>> {
+        // Stable sort keeps the queueing order within a slot
+        self.scheduled.sort_by_key(|tx| tx.slot);
+        let split = self.scheduled.partition_point(|tx| tx.slot <= slot);
+        let due: Vec<ScheduledTransaction> = self.scheduled.drain(..split).collect();
+
+        let mut results = Vec::with_capacity(due.len());
+        for tx in due {
+            self.warp_forward_to(tx.slot);
+            let signers: Vec<&Keypair> = tx.signers.iter().collect();
+            let result = self.execute(
+                &tx.instructions,
+                &signers,
+                format!("scheduled instruction at slot {}", tx.slot),
+            )?;
+            results.push((tx.slot, result));
         }
+        self.warp_forward_to(slot);
+
+        Ok(results)
     }
 
-    /// Execute multiple instructions in a single transaction
-    pub fn execute_instructions(
+    /// Run `attempt` until it returns a successful result, at most `attempts` times
+    ///
+    /// A fresh blockhash is issued before each retry, so a retried transaction isn't
+    /// rejected as a duplicate of the one that failed. If no attempt succeeds, the
+    /// error lists every attempt's failure along with its logs.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let result = ctx.retry(3, |ctx| ctx.execute_instruction(crank_ix.clone(), &[&keeper]))?;
+    /// ```
+    pub fn retry<F>(
         &mut self,
-        instructions: Vec<solana_program::instruction::Instruction>,
+        attempts: usize,
+        mut attempt: F,
+    ) -> Result<TransactionResult, Box<dyn std::error::Error>>
+    where
+        F: FnMut(&mut Self) -> Result<TransactionResult, Box<dyn std::error::Error>>,
+    {
+        if attempts == 0 {
+            return Err("retry needs at least one attempt".into());
+        }
+
+        let mut failures = Vec::with_capacity(attempts);
+        for n in 1..=attempts {
+            if n > 1 {
+                self.svm.expire_blockhash();
+            }
+            match attempt(self) {
+                Ok(result) if result.is_success() => return Ok(result),
+                Ok(result) => failures.push(format!(
+                    "attempt {}: {}\n{}",
+                    n,
+                    result.error().map_or("failed", |e| e.as_str()),
+                    result.logs().join("\n")
+                )),
+                Err(e) => failures.push(format!("attempt {}: {}", n, e)),
+            }
+        }
+
+        Err(format!("All {} attempts failed:\n{}", attempts, failures.join("\n")).into())
+    }
+
+    /// Warp to `slot` unless the chain is already there or past it
+    ///
+    /// A fresh blockhash is issued too, so identical transactions scheduled at
+    /// different slots don't collide as duplicates.
+    fn warp_forward_to(&mut self, slot: u64) {
+        let current = self.svm.get_sysvar::<solana_program::clock::Clock>().slot;
+        if slot > current {
+            self.svm.warp_to_slot(slot);
+            self.svm.expire_blockhash();
+        }
+    }
+
+    /// Build, sign and send a transaction, wrapping the outcome in a `TransactionResult`
+    fn execute(
+        &mut self,
+        instructions: &[Instruction],
         signers: &[&Keypair],
+        name: String,
     ) -> Result<TransactionResult, Box<dyn std::error::Error>> {
-        // Determine the payer
-        let payer_pubkey = if !signers.is_empty() {
-            signers[0].pubkey()
-        } else {
-            self.payer.pubkey()
+        let budget = self.effective_compute_budget();
+
+        // Determine the payer - the next pooled payer if a pool is configured, otherwise
+        // the first signer if provided, otherwise the context's payer
+        let pool_payer = self.payer_pool.as_mut().map(|pool| pool.next_payer());
+        let (payer_pubkey, all_signers) = match pool_payer {
+            Some(payer) => {
+                let mut all_signers = vec![payer];
+                all_signers.extend(signers.iter().filter(|s| s.pubkey() != payer.pubkey()));
+                (payer.pubkey(), all_signers)
+            }
+            None if !signers.is_empty() => (signers[0].pubkey(), signers.to_vec()),
+            None => (self.payer.pubkey(), signers.to_vec()),
         };
 
+        let mut all_instructions = Vec::new();
+        if self.auto_create_atas {
+            all_instructions.extend(Self::missing_ata_instructions(&self.svm, instructions, &payer_pubkey));
+        }
+        all_instructions.extend_from_slice(instructions);
+        let all_instructions = with_compute_budget(budget, &all_instructions);
+
         // Build and sign the transaction
         let tx = Transaction::new_signed_with_payer(
-            &instructions,
+            &all_instructions,
             Some(&payer_pubkey),
-            signers,
+            &all_signers,
             self.svm.latest_blockhash(),
         );
 
+        // Snapshot writable accounts to detect the ones the transaction never modifies
+        let watched: Vec<(Pubkey, Option<Account>)> = if self.warn_unused_accounts {
+            let signer_keys: Vec<Pubkey> = all_signers.iter().map(|s| s.pubkey()).collect();
+            let mut keys: Vec<Pubkey> = instructions
+                .iter()
+                .flat_map(|ix| ix.accounts.iter())
+                .filter(|meta| meta.is_writable && !signer_keys.contains(&meta.pubkey))
+                .map(|meta| meta.pubkey)
+                .collect();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter()
+                .map(|key| (key, Self::existing_account(&self.svm, &key)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         // Execute the transaction
-        match self.svm.send_transaction(tx) {
-            Ok(result) => Ok(TransactionResult::new(
-                result,
-                Some("batch transaction".to_string()),
-            )),
-            Err(failed) => Ok(TransactionResult::new_failed(
-                format!("{:?}", failed.err),
-                failed.meta,
-                Some("batch transaction".to_string()),
-            )),
+        let result = self
+            .svm
+            .send_transaction_result(tx)?
+            .with_instruction_name(name)
+            .with_prepended_instructions(all_instructions.len() - instructions.len());
+        let result = self.with_idl_error_names(result);
+        self.log_filter.print(&result);
+        self.history.push(result.clone());
+
+        self.untouched_accounts.clear();
+        if result.is_success() {
+            for (key, before) in watched {
+                if Self::existing_account(&self.svm, &key) == before {
+                    eprintln!(
+                        "Warning: writable account {} was never modified by {}",
+                        key,
+                        result.instruction_name().unwrap_or("transaction")
+                    );
+                    self.untouched_accounts.push(key);
+                }
+            }
         }
+        Ok(result)
+    }
+
+    /// Build idempotent create instructions for the missing ATAs among `instructions`' accounts
+    ///
+    /// A missing account counts as an ATA when it derives from another account of the
+    /// same instruction as owner and a token-program-owned account as mint.
+    fn missing_ata_instructions(
+        svm: &LiteSVM,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+    ) -> Vec<Instruction> {
+        let mut created = Vec::new();
+        let mut create_ixs = Vec::new();
+
+        for instruction in instructions {
+            let keys: Vec<Pubkey> = instruction.accounts.iter().map(|meta| meta.pubkey).collect();
+            let mints: Vec<(Pubkey, Pubkey)> = keys
+                .iter()
+                .filter_map(|key| {
+                    let owner = svm.get_account(key)?.owner;
+                    let is_token_program = owner == spl_token::id() || owner == spl_token_2022::id();
+                    is_token_program.then_some((*key, owner))
+                })
+                .collect();
+            if mints.is_empty() {
+                continue;
+            }
+
+            for ata in &keys {
+                if created.contains(ata) || Self::existing_account(svm, ata).is_some() {
+                    continue;
+                }
+                let found = keys.iter().find_map(|owner| {
+                    mints
+                        .iter()
+                        .find(|(mint, program)| {
+                            get_associated_token_address_with_program_id(owner, mint, program) == *ata
+                        })
+                        .map(|(mint, program)| (owner, mint, program))
+                });
+                if let Some((owner, mint, token_program)) = found {
+                    create_ixs.push(create_associated_token_account_idempotent(
+                        payer,
+                        owner,
+                        mint,
+                        token_program,
+                    ));
+                    created.push(*ata);
+                }
+            }
+        }
+        create_ixs
+    }
+
+    /// Get an account, treating empty zero-lamport accounts as nonexistent
+    fn existing_account(svm: &LiteSVM, address: &Pubkey) -> Option<Account> {
+        svm.get_account(address)
+            .filter(|account| account.lamports > 0 || !account.data.is_empty())
     }
 
     /// Send and confirm a transaction (convenience method)
+    ///
+    /// The transaction is recorded like those of
+    /// [`execute_instruction`](Self::execute_instruction): its accounts are tracked
+    /// and it joins the [`transaction_history`](Self::transaction_history).
     pub fn send_and_confirm_transaction(
         &mut self,
         transaction: &Transaction,
     ) -> Result<Signature, Box<dyn std::error::Error>> {
-        match self.svm.send_transaction(transaction.clone()) {
-            Ok(_) => Ok(transaction.signatures[0]),
-            Err(e) => Err(format!("Transaction failed: {:?}", e).into()),
+        let result = self.svm.send_transaction_result(transaction.clone())?;
+        let result = self.with_idl_error_names(result);
+        self.log_filter.print(&result);
+        self.history.push(result.clone());
+        match result.error() {
+            None => Ok(result.signature()),
+            Some(error) => Err(format!("Transaction failed: {}", error).into()),
         }
     }
 
+    /// Every transaction executed through this context so far, oldest first
+    ///
+    /// Failed transactions are included. Each result carries its
+    /// [`signature`](TransactionResult::signature) and instruction name, which makes
+    /// the history handy for end-of-test reports.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for result in ctx.transaction_history() {
+    ///     println!(
+    ///         "{} {:?}: {} CU, {}",
+    ///         result.signature(),
+    ///         result.instruction_name(),
+    ///         result.compute_units(),
+    ///         if result.is_success() { "ok" } else { "failed" }
+    ///     );
+    /// }
+    /// ```
+    pub fn transaction_history(&self) -> &[TransactionResult] {
+        &self.history
+    }
+
+    /// Forget the transactions recorded so far
+    pub fn clear_transaction_history(&mut self) {
+        self.history.clear();
+    }
+
     /// Get an Anchor account from the blockchain
     ///
     /// This fetches and deserializes an Anchor account from the current state.
@@ -244,19 +839,189 @@ impl AnchorContext {
             .map_err(|e| AccountError::DeserializationError(e.to_string()))
     }
 
+    /// Get every account of type `T` owned by this context's program
+    ///
+    /// Scans the program's accounts with [`TestHelpers::get_program_accounts_with_filters`],
+    /// keeps those whose data starts with `T::DISCRIMINATOR` and deserializes them.
+    /// Results are in ascending address order. Only accounts known to the helpers
+    /// are scanned, which includes every account used by an executed transaction.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let positions: Vec<(Pubkey, Position)> = ctx.get_all_accounts()?;
+    /// assert_eq!(positions.iter().filter(|(_, p)| p.owner == alice).count(), 3);
+    /// ```
+    pub fn get_all_accounts<T>(&self) -> Result<Vec<(Pubkey, T)>, AccountError>
+    where
+        T: AccountDeserialize + Discriminator,
+    {
+        let filters = [AccountFilter::memcmp(0, T::DISCRIMINATOR)];
+        self.svm
+            .get_program_accounts_with_filters(&self.program_id, &filters)
+            .into_iter()
+            .map(|(address, account)| {
+                T::try_deserialize(&mut account.data.as_slice())
+                    .map(|value| (address, value))
+                    .map_err(|e| AccountError::DeserializationError(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Write a typed account owned by this context's program, without executing anything
+    ///
+    /// The account data is `T::DISCRIMINATOR` followed by the Borsh-serialized
+    /// `value`, and the balance is the rent-exempt minimum for that data. Use it to
+    /// seed program state instead of running initialize instructions. The write is
+    /// recorded in the [`surgery_report`](litesvm_utils::surgery_report).
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.set_anchor_account(&config_pda, &Config { admin, fee_bps: 30 })?;
+    /// ```
+    #[track_caller]
+    pub fn set_anchor_account<T>(
+        &mut self,
+        address: &Pubkey,
+        value: &T,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        T: AnchorSerialize + Discriminator,
+    {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        value
+            .serialize(&mut data)
+            .map_err(|e| format!("Failed to serialize account {}: {}", address, e))?;
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: self.program_id,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm
+            .unsafe_state()
+            .write("set_anchor_account", address, account)
+    }
+
+    /// Read an Anchor account, change it in `modify` and write it back
+    ///
+    /// Lamports and owner are kept. The re-serialized account overwrites the start of
+    /// the existing data, which only grows if the new encoding is longer, matching
+    /// how Anchor writes accounts on exit. Use it to corrupt or fast-forward state
+    /// for edge cases; the write is recorded in the
+    /// [`surgery_report`](litesvm_utils::surgery_report).
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.modify_anchor_account(&vault_pda, |vault: &mut Vault| {
+    ///     vault.unlock_at = 0;
+    /// })?;
+    /// ```
+    #[track_caller]
+    pub fn modify_anchor_account<T, F>(
+        &mut self,
+        address: &Pubkey,
+        modify: F,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        T: AccountDeserialize + AccountSerialize,
+        F: FnOnce(&mut T),
+    {
+        let mut account = self
+            .svm
+            .get_account(address)
+            .ok_or(AccountError::AccountNotFound(*address))?;
+        let mut value = T::try_deserialize(&mut account.data.as_slice())
+            .map_err(|e| AccountError::DeserializationError(e.to_string()))?;
+        modify(&mut value);
+
+        let mut data = Vec::new();
+        value
+            .try_serialize(&mut data)
+            .map_err(|e| format!("Failed to serialize account {}: {}", address, e))?;
+        if data.len() > account.data.len() {
+            account.data.resize(data.len(), 0);
+        }
+        account.data[..data.len()].copy_from_slice(&data);
+        self.svm
+            .unsafe_state()
+            .write("modify_anchor_account", address, account)
+    }
+
+    /// Assert that the Anchor account at `address` equals `expected`
+    ///
+    /// On mismatch the panic message is a line diff of the two values'
+    /// pretty-printed `Debug` output, with `-` marking expected and `+` actual
+    /// lines, so the differing fields stand out without field-by-field asserts.
+    /// Also panics if the account is missing or doesn't deserialize as `T`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.assert_account_eq(&vault_pda, &Vault { owner, amount: 500, bump });
+    /// ```
+    #[track_caller]
+    pub fn assert_account_eq<T>(&self, address: &Pubkey, expected: &T)
+    where
+        T: AccountDeserialize + PartialEq + std::fmt::Debug,
+    {
+        let actual: T = self
+            .get_account(address)
+            .unwrap_or_else(|e| panic!("Failed to read account {}: {}", address, e));
+        if &actual != expected {
+            panic!(
+                "Account {} does not match the expected {} (- expected, + actual):\n{}",
+                address,
+                std::any::type_name::<T>(),
+                debug_diff(&format!("{:#?}", expected), &format!("{:#?}", actual))
+            );
+        }
+    }
+
+    /// Assert one field of the Anchor account at `address`
+    ///
+    /// `field` picks the value to compare out of the deserialized account. Panics
+    /// with the account type and both values on mismatch, or if the account is
+    /// missing or doesn't deserialize as `T`. Use
+    /// [`assert_account_field!`](crate::assert_account_field) to have the field
+    /// named in the message too.
+    ///
+    /// # Example
+    /// ```ignore
+    /// ctx.assert_field(&vault_pda, |vault: &Vault| vault.amount, 1_000);
+    /// ```
+    #[track_caller]
+    pub fn assert_field<T, V>(&self, address: &Pubkey, field: impl FnOnce(&T) -> V, expected: V)
+    where
+        T: AccountDeserialize,
+        V: PartialEq + std::fmt::Debug,
+    {
+        let account: T = self
+            .get_account(address)
+            .unwrap_or_else(|e| panic!("Failed to read account {}: {}", address, e));
+        let actual = field(&account);
+        assert!(
+            actual == expected,
+            "Field mismatch for {} account {}. Expected: {:?}, Actual: {:?}",
+            std::any::type_name::<T>(),
+            address,
+            expected,
+            actual
+        );
+    }
+
     /// Create a funded account (convenience method)
+    #[track_caller]
     pub fn create_funded_account(&mut self, lamports: u64) -> Result<Keypair, Box<dyn std::error::Error>> {
-        let account = Keypair::new();
-        self.svm.airdrop(&account.pubkey(), lamports)
-            .map_err(|e| format!("Airdrop failed: {:?}", e))?;
+        let account = new_keypair(&mut self.svm);
+        fund_account(&mut self.svm, &account.pubkey(), lamports)?;
+        track_accounts(&mut self.svm, [account.pubkey()]);
         Ok(account)
     }
 
     /// Airdrop lamports to an account (convenience method)
+    #[track_caller]
     pub fn airdrop(&mut self, pubkey: &Pubkey, lamports: u64) -> Result<(), Box<dyn std::error::Error>> {
-        self.svm.airdrop(pubkey, lamports)
-            .map_err(|e| format!("Airdrop failed: {:?}", e))?;
-        Ok(())
+        fund_account(&mut self.svm, pubkey, lamports)
     }
 
     /// Get the latest blockhash
@@ -268,4 +1033,56 @@ impl AnchorContext {
     pub fn account_exists(&self, pubkey: &Pubkey) -> bool {
         self.svm.get_account(pubkey).is_some()
     }
+
+    /// Save the full VM state: accounts, programs, sysvars and the VM's record of
+    /// processed transactions
+    ///
+    /// See [`TestHelpers::snapshot`]. Only the VM is saved: the context's
+    /// [`transaction_history`](Self::transaction_history) and transactions queued with
+    /// [`schedule_at_slot`](Self::schedule_at_slot) are not part of the snapshot and
+    /// are left as they are by [`restore`](Self::restore).
+    pub fn snapshot(&self) -> SvmSnapshot {
+        self.svm.snapshot()
+    }
+
+    /// Replace the VM state with one saved by [`snapshot`](Self::snapshot)
+    ///
+    /// The context's [`transaction_history`](Self::transaction_history) keeps the
+    /// transactions executed since the snapshot; call
+    /// [`clear_transaction_history`](Self::clear_transaction_history) to drop them.
+    pub fn restore(&mut self, snapshot: &SvmSnapshot) {
+        self.svm.restore(snapshot);
+    }
+
+    /// Branch this context into an independent copy
+    ///
+    /// The fork has the same VM state, payer, IDLs, settings and scheduled
+    /// transactions, so one setup can be driven down several alternative paths in a
+    /// single test. Nothing executed on one branch is visible on the other, and each
+    /// branch keeps its own copy of the helpers' per-VM state, such as the
+    /// [`surgery_report`](litesvm_utils::surgery_report) and tracked accounts.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut refund = ctx.fork();
+    /// ctx.execute_instruction(take_ix, &[&taker])?.assert_success();
+    /// refund.execute_instruction(refund_ix, &[&maker])?.assert_success();
+    /// ```
+    pub fn fork(&self) -> Self {
+        Self {
+            svm: self.svm.clone(),
+            program_id: self.program_id,
+            payer: self.payer.insecure_clone(),
+            program: self.program,
+            idls: self.idls.clone(),
+            payer_pool: self.payer_pool.clone(),
+            warn_unused_accounts: self.warn_unused_accounts,
+            untouched_accounts: self.untouched_accounts.clone(),
+            auto_create_atas: self.auto_create_atas,
+            scheduled: self.scheduled.clone(),
+            log_filter: self.log_filter,
+            history: self.history.clone(),
+            compute_budget: self.compute_budget,
+        }
+    }
 }
\ No newline at end of file
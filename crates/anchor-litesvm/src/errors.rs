@@ -0,0 +1,127 @@
+//! Typed assertions on Anchor program errors
+//!
+//! [`ErrorAssertions::assert_error_of`] matches a failed transaction against a
+//! variant of the program's `#[error_code]` enum by its numeric code, instead of
+//! searching the logs for its name.
+//!
+//! ```ignore
+//! let result = ctx.execute_instruction(withdraw_ix, &[&user])?;
+//! result.assert_error_of(my_program::errors::MyError::InvalidAmount);
+//! ```
+
+use litesvm_utils::TransactionResult;
+use std::fmt::Debug;
+
+/// Extension trait for TransactionResult to assert typed program errors
+pub trait ErrorAssertions {
+    /// Get the custom error code the transaction failed with, if any
+    ///
+    /// Read from the program's failure log line, or from the error message when the
+    /// logs don't have one.
+    fn custom_error_code(&self) -> Option<u32>;
+
+    /// Assert that the transaction failed with `error`
+    ///
+    /// `error` is any error type that converts to its code, such as an Anchor
+    /// `#[error_code]` enum or [`anchor_lang::error::ErrorCode`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transaction succeeded or failed with a different error
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_error_of(MyError::InvalidAmount);
+    /// result.assert_error_of(anchor_lang::error::ErrorCode::ConstraintSeeds);
+    /// ```
+    fn assert_error_of<E>(&self, error: E) -> &Self
+    where
+        E: Into<u32> + Debug;
+}
+
+impl ErrorAssertions for TransactionResult {
+    fn custom_error_code(&self) -> Option<u32> {
+        if let Some((_, code)) = self.custom_error() {
+            return Some(code);
+        }
+        let (_, rest) = self.error()?.split_once("Custom(")?;
+        rest.split(')').next()?.parse().ok()
+    }
+
+    fn assert_error_of<E>(&self, error: E) -> &Self
+    where
+        E: Into<u32> + Debug,
+    {
+        self.assert_failure();
+
+        let name = format!("{:?}", error);
+        let expected = error.into();
+        let actual = self.custom_error_code();
+        assert!(
+            actual == Some(expected),
+            "Expected error {} ({}), but the transaction failed with {}.\nLogs:\n{}",
+            name,
+            expected,
+            match (actual, self.custom_error_name()) {
+                (Some(code), Some(actual_name)) => format!("{} ({})", actual_name, code),
+                (Some(code), None) => format!("error code {}", code),
+                (None, _) => self.error().cloned().unwrap_or_default(),
+            },
+            self.logs().join("\n")
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use litesvm::LiteSVM;
+    use litesvm_utils::{TestHelpers, TransactionHelpers};
+    use solana_program::pubkey::Pubkey;
+    use solana_program::system_instruction;
+    use solana_sdk::signature::Signer;
+
+    /// Codes of the system program's errors, converted the way `#[error_code]` does
+    #[derive(Debug, Clone, Copy)]
+    enum SystemError {
+        AccountAlreadyInUse,
+        ResultWithNegativeLamports,
+    }
+
+    impl From<SystemError> for u32 {
+        fn from(error: SystemError) -> u32 {
+            error as u32
+        }
+    }
+
+    fn failed_transfer() -> TransactionResult {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let ix = system_instruction::transfer(&from.pubkey(), &to, 10_000_000_000);
+        svm.send_instruction(ix, &[&from]).unwrap()
+    }
+
+    #[test]
+    fn test_assert_error_of() {
+        let result = failed_transfer();
+        assert_eq!(result.custom_error_code(), Some(1));
+        result.assert_error_of(SystemError::ResultWithNegativeLamports);
+
+        // Anchor's built-in errors convert to their codes the same way
+        assert_eq!(
+            u32::from(anchor_lang::error::ErrorCode::ConstraintSeeds),
+            2006
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "AccountAlreadyInUse (0), but the transaction failed with error code 1"
+    )]
+    fn test_assert_error_of_wrong_error() {
+        failed_transfer().assert_error_of(SystemError::AccountAlreadyInUse);
+    }
+}
@@ -107,7 +107,7 @@ impl EventHelpers for TransactionResult {
         let mut events = Vec::new();
 
         // Anchor events are logged with the format: "Program data: <base64_encoded_data>"
-        // The discriminator for events is the first 8 bytes
+        // The data starts with the event discriminator (8 bytes unless customized)
         for log in self.logs() {
             if let Some(event_data) = log.strip_prefix("Program data: ") {
                 // Decode base64
@@ -115,15 +115,10 @@ impl EventHelpers for TransactionResult {
                     .decode(event_data)
                     .map_err(EventError::Base64Error)?;
 
-                // Check if this matches the event discriminator
-                if decoded.len() < 8 {
-                    continue;
-                }
-
-                let discriminator = &decoded[0..8];
-                if discriminator == T::DISCRIMINATOR {
+                // Check if this matches the event discriminator. Anchor 0.31 allows
+                // custom discriminators, so its length comes from the event type.
+                if let Some(mut event_data_slice) = decoded.strip_prefix(T::DISCRIMINATOR) {
                     // Deserialize the event (skip discriminator)
-                    let mut event_data_slice = &decoded[8..];
                     match T::deserialize(&mut event_data_slice) {
                         Ok(event) => events.push(event),
                         Err(e) => {
@@ -228,23 +223,18 @@ where
         .map_err(EventError::Base64Error)?;
 
     // Check discriminator
-    if decoded.len() < 8 {
-        return Err(EventError::InvalidFormat);
-    }
-
-    let discriminator = &decoded[0..8];
-    if discriminator != T::DISCRIMINATOR {
-        return Err(EventError::InvalidFormat);
-    }
+    let mut event_data_slice = decoded
+        .strip_prefix(T::DISCRIMINATOR)
+        .ok_or(EventError::InvalidFormat)?;
 
     // Deserialize
-    let mut event_data_slice = &decoded[8..];
     T::deserialize(&mut event_data_slice).map_err(|e| EventError::AnchorError(e.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::prelude::{borsh, AnchorSerialize};
 
     #[test]
     fn test_event_error_display() {
@@ -254,4 +244,34 @@ mod tests {
         let err = EventError::ParseError("test error".to_string());
         assert_eq!(err.to_string(), "Failed to parse event data: test error");
     }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+    struct ShortDiscriminatorEvent {
+        amount: u64,
+    }
+
+    impl Discriminator for ShortDiscriminatorEvent {
+        const DISCRIMINATOR: &'static [u8] = &[42];
+    }
+
+    impl Event for ShortDiscriminatorEvent {
+        fn data(&self) -> Vec<u8> {
+            let mut data = Self::DISCRIMINATOR.to_vec();
+            self.serialize(&mut data).unwrap();
+            data
+        }
+    }
+
+    #[test]
+    fn test_parse_event_data_custom_discriminator() {
+        let event = ShortDiscriminatorEvent { amount: 7 };
+        let encoded = general_purpose::STANDARD.encode(event.data());
+
+        let parsed: ShortDiscriminatorEvent = parse_event_data(&encoded).unwrap();
+        assert_eq!(parsed, event);
+
+        let wrong = general_purpose::STANDARD.encode([1u8, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let result: Result<ShortDiscriminatorEvent, _> = parse_event_data(&wrong);
+        assert!(matches!(result, Err(EventError::InvalidFormat)));
+    }
 }
@@ -0,0 +1,293 @@
+//! IDL loading and discovery for Anchor programs
+//!
+//! Anchor writes the IDL of every program in a workspace to `target/idl/<name>.json`.
+//! This module loads those files so IDL-driven features (validation, decoding,
+//! PDA resolution) work without any explicit configuration.
+
+use anchor_lang_idl_spec::{IdlInstructionAccount, IdlInstructionAccountItem};
+use solana_program::pubkey::Pubkey;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+pub use anchor_lang_idl_spec::Idl;
+
+/// Directories, relative to a workspace root, that are searched for IDL files
+const IDL_DIRS: [&str; 2] = ["target/idl", "idl"];
+
+#[derive(Error, Debug)]
+pub enum IdlError {
+    #[error("Failed to read IDL file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Failed to parse IDL: {0}")]
+    ParseError(String),
+
+    #[error("Invalid program address in IDL: {0}")]
+    InvalidAddress(String),
+}
+
+/// Parse an IDL from its JSON representation
+///
+/// Only the Anchor 0.30+ IDL format (the one with an `address` field) is supported.
+pub fn parse_idl(json: &str) -> Result<Idl, IdlError> {
+    serde_json::from_str(json).map_err(|e| IdlError::ParseError(e.to_string()))
+}
+
+/// Load an IDL from a JSON file
+///
+/// # Example
+///
+/// ```ignore
+/// let idl = load_idl("target/idl/escrow.json")?;
+/// ```
+pub fn load_idl(path: impl AsRef<Path>) -> Result<Idl, IdlError> {
+    let path = path.as_ref();
+    let json =
+        std::fs::read_to_string(path).map_err(|e| IdlError::Io(path.to_path_buf(), e))?;
+    parse_idl(&json)
+}
+
+/// Get the program ID declared in an IDL
+pub fn idl_program_id(idl: &Idl) -> Result<Pubkey, IdlError> {
+    Pubkey::from_str(&idl.address).map_err(|_| IdlError::InvalidAddress(idl.address.clone()))
+}
+
+/// Get the discriminator of an instruction declared in an IDL
+///
+/// Anchor 0.31 programs may declare custom discriminators of any length, so
+/// the IDL is the source of truth rather than `sha256("global:<name>")[..8]`.
+pub fn instruction_discriminator<'a>(idl: &'a Idl, name: &str) -> Option<&'a [u8]> {
+    idl.instructions
+        .iter()
+        .find(|ix| ix.name == name)
+        .map(|ix| ix.discriminator.as_slice())
+}
+
+/// Get the discriminator of an account type declared in an IDL
+pub fn account_discriminator<'a>(idl: &'a Idl, name: &str) -> Option<&'a [u8]> {
+    idl.accounts
+        .iter()
+        .find(|account| account.name == name)
+        .map(|account| account.discriminator.as_slice())
+}
+
+/// Get the discriminator of an event declared in an IDL
+pub fn event_discriminator<'a>(idl: &'a Idl, name: &str) -> Option<&'a [u8]> {
+    idl.events
+        .iter()
+        .find(|event| event.name == name)
+        .map(|event| event.discriminator.as_slice())
+}
+
+/// Get the address of a program or sysvar account from its conventional name
+///
+/// Anchor programs name these accounts consistently, so instruction builders can
+/// fill them in when a test leaves them out.
+pub(crate) fn well_known_account(name: &str) -> Option<Pubkey> {
+    match name {
+        "system_program" => Some(solana_program::system_program::id()),
+        "token_program" => Some(spl_token::id()),
+        "token_program_2022" | "token_2022_program" => Some(spl_token_2022::id()),
+        "associated_token_program" => Some(spl_associated_token_account::id()),
+        "rent" => Some(solana_program::sysvar::rent::id()),
+        _ => None,
+    }
+}
+
+/// The single accounts of an IDL instruction, flattening composite groups
+pub(crate) fn leaf_accounts(items: &[IdlInstructionAccountItem]) -> Vec<&IdlInstructionAccount> {
+    items
+        .iter()
+        .flat_map(|item| match item {
+            IdlInstructionAccountItem::Single(account) => vec![account],
+            IdlInstructionAccountItem::Composite(group) => leaf_accounts(&group.accounts),
+        })
+        .collect()
+}
+
+/// Discover the IDLs of the Anchor workspace containing the current directory
+///
+/// See [`discover_idls_from`] for the search rules.
+pub fn discover_idls() -> Vec<Idl> {
+    std::env::current_dir()
+        .map(|dir| discover_idls_from(&dir))
+        .unwrap_or_default()
+}
+
+/// Discover IDLs starting from `start`
+///
+/// Looks for `*.json` files in the `target/idl` and `idl` directories of `start`
+/// and of the nearest ancestor containing an `Anchor.toml`. Files that are not
+/// valid IDLs (e.g. the legacy pre-0.30 format) are skipped.
+pub fn discover_idls_from(start: &Path) -> Vec<Idl> {
+    let mut idls = Vec::new();
+    for dir in idl_search_dirs(start) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            if let Ok(idl) = load_idl(&path) {
+                if !idls.iter().any(|known: &Idl| known.address == idl.address) {
+                    idls.push(idl);
+                }
+            }
+        }
+    }
+    idls
+}
+
+fn idl_search_dirs(start: &Path) -> Vec<PathBuf> {
+    let mut roots = vec![start];
+    if let Some(workspace_root) = start
+        .ancestors()
+        .find(|dir| dir.join("Anchor.toml").is_file())
+    {
+        if workspace_root != start {
+            roots.push(workspace_root);
+        }
+    }
+
+    roots
+        .into_iter()
+        .flat_map(|root| IDL_DIRS.iter().map(move |dir| root.join(dir)))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const TEST_IDL: &str = r#"{
+        "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+        "metadata": { "name": "escrow", "version": "0.1.0", "spec": "0.1.0" },
+        "instructions": [
+            {
+                "name": "make",
+                "discriminator": [138, 227, 232, 77, 223, 166, 96, 197],
+                "accounts": [
+                    { "name": "maker", "writable": true, "signer": true },
+                    { "name": "system_program", "address": "11111111111111111111111111111111" }
+                ],
+                "args": [{ "name": "seed", "type": "u64" }]
+            }
+        ]
+    }"#;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "anchor-litesvm-{}-{}",
+            name,
+            Pubkey::new_unique()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_idl() {
+        let idl = parse_idl(TEST_IDL).unwrap();
+        assert_eq!(idl.metadata.name, "escrow");
+        assert_eq!(idl.instructions.len(), 1);
+        assert_eq!(
+            idl_program_id(&idl).unwrap().to_string(),
+            "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+        );
+    }
+
+    #[test]
+    fn test_idl_discriminators() {
+        let mut idl = parse_idl(TEST_IDL).unwrap();
+        idl.accounts.push(anchor_lang_idl_spec::IdlAccount {
+            name: "Escrow".to_string(),
+            discriminator: vec![7],
+        });
+
+        assert_eq!(
+            instruction_discriminator(&idl, "make"),
+            Some(&[138, 227, 232, 77, 223, 166, 96, 197][..])
+        );
+        assert_eq!(account_discriminator(&idl, "Escrow"), Some(&[7][..]));
+        assert_eq!(event_discriminator(&idl, "Escrow"), None);
+        assert_eq!(instruction_discriminator(&idl, "take"), None);
+    }
+
+    #[test]
+    fn test_parse_idl_invalid() {
+        assert!(matches!(parse_idl("{}"), Err(IdlError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_load_idl_missing_file() {
+        let result = load_idl("does/not/exist.json");
+        assert!(matches!(result, Err(IdlError::Io(_, _))));
+    }
+
+    #[test]
+    fn test_context_add_idl() {
+        let idl = parse_idl(TEST_IDL).unwrap();
+        let program_id = idl_program_id(&idl).unwrap();
+        let mut ctx = crate::AnchorContext::new(litesvm::LiteSVM::new(), program_id);
+        assert!(ctx.idl().is_none());
+
+        ctx.add_idl(idl).unwrap();
+        assert_eq!(ctx.idl().unwrap().metadata.name, "escrow");
+        assert!(ctx.idl_for(&Pubkey::new_unique()).is_none());
+    }
+
+    #[test]
+    fn test_context_names_idl_errors() {
+        use litesvm_utils::TestHelpers;
+        use solana_sdk::signature::Signer;
+
+        // An IDL for the system program, so a real custom error can be named
+        let idl = parse_idl(
+            r#"{
+                "address": "11111111111111111111111111111111",
+                "metadata": { "name": "system", "version": "0.1.0", "spec": "0.1.0" },
+                "instructions": [],
+                "errors": [{ "code": 1, "name": "ResultWithNegativeLamports" }]
+            }"#,
+        )
+        .unwrap();
+        let mut ctx = crate::AnchorContext::new(litesvm::LiteSVM::new(), Pubkey::new_unique());
+        ctx.add_idl(idl).unwrap();
+
+        let from = ctx.svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = solana_program::system_instruction::transfer(
+            &from.pubkey(),
+            &Pubkey::new_unique(),
+            10_000_000_000,
+        );
+        let result = ctx.execute_instruction(ix, &[&from]).unwrap();
+        assert_eq!(result.custom_error_name(), Some("ResultWithNegativeLamports"));
+        result.assert_anchor_error("ResultWithNegativeLamports");
+    }
+
+    #[test]
+    fn test_discover_idls_from_workspace_root() {
+        let root = temp_dir("discover");
+        std::fs::write(root.join("Anchor.toml"), "").unwrap();
+        std::fs::create_dir_all(root.join("target/idl")).unwrap();
+        std::fs::write(root.join("target/idl/escrow.json"), TEST_IDL).unwrap();
+        std::fs::write(root.join("target/idl/legacy.json"), "{\"version\": \"0.1.0\"}").unwrap();
+
+        // Tests of a program run from its own directory inside the workspace
+        let program_dir = root.join("programs/escrow");
+        std::fs::create_dir_all(&program_dir).unwrap();
+
+        let idls = discover_idls_from(&program_dir);
+        assert_eq!(idls.len(), 1);
+        assert_eq!(idls[0].metadata.name, "escrow");
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,1119 @@
+//! Instructions built from an IDL and JSON-like arguments
+//!
+//! [`IdlInstructionBuilder`] encodes an instruction using only the program's IDL:
+//! the discriminator, the Borsh layout of each argument and the account order all
+//! come from it, so tests don't need generated client types or `declare_program!`.
+//! Accounts the IDL can derive, such as PDAs and their token vaults, are filled in.
+//!
+//! ```ignore
+//! let ix = ctx
+//!     .idl_instruction("make")
+//!     .arg("seed", 42u64)
+//!     .arg("receive", json!({ "amount": 500, "mint": mint_b.to_string() }))
+//!     .account("maker", maker.pubkey())
+//!     .account("mint_a", mint_a)
+//!     .instruction()?; // escrow, vault and programs are derived
+//! ```
+//!
+//! Arguments are [`serde_json::Value`]s, matched to the IDL types like this:
+//!
+//! - integers from JSON numbers, or strings for values outside `u64`/`i64`
+//! - public keys from base58 strings or 32-element byte arrays
+//! - `bytes`, `vec` and arrays from JSON arrays, options from `null` or the value
+//! - structs from objects keyed by field name (arrays for tuple structs)
+//! - enums from the variant name, or `{ "Variant": fields }` for variants with data
+
+use crate::idl::{idl_program_id, leaf_accounts, well_known_account, Idl};
+use crate::program::BuildError;
+use anchor_lang_idl_spec::{
+    IdlArrayLen, IdlDefinedFields, IdlField, IdlInstruction, IdlInstructionAccount, IdlPda,
+    IdlSeed, IdlSerialization, IdlType, IdlTypeDef, IdlTypeDefTy,
+};
+use litesvm::LiteSVM;
+use serde_json::Value;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Builds an instruction by name from a program's IDL
+///
+/// Created by [`AnchorContext::idl_instruction`](crate::AnchorContext::idl_instruction).
+/// Only the "free" accounts (signers, mints and the like) need to be set; the rest
+/// are resolved like Anchor's TypeScript client does:
+///
+/// - accounts with a fixed address in the IDL, and program and sysvar accounts
+///   with their conventional names (`system_program`, `token_program`,
+///   `associated_token_program`, `rent`)
+/// - PDAs, derived from their IDL seeds: constants, arguments and other accounts,
+///   including fields of their on-chain data when the builder can read accounts
+/// - `has_one` targets, read from the account that holds the relation
+///
+/// Unset optional accounts are passed as the program ID.
+pub struct IdlInstructionBuilder<'a> {
+    idl: Option<&'a Idl>,
+    svm: Option<&'a LiteSVM>,
+    program_id: Pubkey,
+    name: String,
+    args: HashMap<String, Value>,
+    accounts: HashMap<String, Pubkey>,
+    remaining_accounts: Vec<AccountMeta>,
+}
+
+impl<'a> IdlInstructionBuilder<'a> {
+    /// Start building instruction `name` of the program `idl` describes
+    pub fn new(idl: &'a Idl, name: &str) -> Self {
+        let program_id = idl_program_id(idl).unwrap_or_default();
+        Self::for_program(Some(idl), program_id, name)
+    }
+
+    pub(crate) fn for_program(idl: Option<&'a Idl>, program_id: Pubkey, name: &str) -> Self {
+        Self {
+            idl,
+            svm: None,
+            program_id,
+            name: name.to_string(),
+            args: HashMap::new(),
+            accounts: HashMap::new(),
+            remaining_accounts: Vec::new(),
+        }
+    }
+
+    /// Set argument `name`
+    pub fn arg(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.args.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Set account `name`
+    ///
+    /// Accounts nested in a composite group are named by their own IDL name.
+    pub fn account(mut self, name: &str, pubkey: Pubkey) -> Self {
+        self.accounts.insert(name.to_string(), pubkey);
+        self
+    }
+
+    /// Set several accounts by name, e.g. from a `HashMap<&str, Pubkey>`
+    ///
+    /// The accounts are placed in IDL order whatever the map's order, so fixtures
+    /// and configs can supply accounts without positional calls. Names the IDL
+    /// doesn't declare fail at [`instruction`](Self::instruction).
+    pub fn accounts_map<K: AsRef<str>>(
+        mut self,
+        accounts: impl IntoIterator<Item = (K, Pubkey)>,
+    ) -> Self {
+        self.accounts.extend(
+            accounts
+                .into_iter()
+                .map(|(name, pubkey)| (name.as_ref().to_string(), pubkey)),
+        );
+        self
+    }
+
+    /// Read account data from `svm` when resolving seeds and relations
+    ///
+    /// Builders from [`AnchorContext::idl_instruction`](crate::AnchorContext::idl_instruction)
+    /// read the context's VM already.
+    pub fn with_svm(mut self, svm: &'a LiteSVM) -> Self {
+        self.svm = Some(svm);
+        self
+    }
+
+    /// Append accounts after those the IDL declares
+    pub fn remaining_accounts(mut self, accounts: Vec<AccountMeta>) -> Self {
+        self.remaining_accounts.extend(accounts);
+        self
+    }
+
+    /// Encode the instruction
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no IDL or no instruction of that name, if an argument or
+    /// a required account is missing or unknown, or if a value doesn't fit its type
+    pub fn instruction(self) -> Result<Instruction, BuildError> {
+        let idl = self.idl.ok_or(BuildError::MissingIdl(self.program_id))?;
+        let instruction = idl
+            .instructions
+            .iter()
+            .find(|ix| ix.name == self.name)
+            .ok_or_else(|| BuildError::UnknownInstruction(self.name.clone()))?;
+
+        if let Some(unknown) = self
+            .args
+            .keys()
+            .find(|name| !instruction.args.iter().any(|arg| &arg.name == *name))
+        {
+            return Err(BuildError::IdlMismatch(format!(
+                "`{}` has no argument `{}`",
+                instruction.name, unknown
+            )));
+        }
+
+        let mut data = instruction.discriminator.clone();
+        for arg in &instruction.args {
+            let value = self
+                .args
+                .get(&arg.name)
+                .ok_or_else(|| BuildError::MissingArg(arg.name.clone()))?;
+            encode_value(idl, &arg.ty, value, &mut data)
+                .map_err(|message| BuildError::InvalidArg(arg.name.clone(), message))?;
+        }
+
+        let known = leaf_accounts(&instruction.accounts);
+        if let Some(unknown) = self
+            .accounts
+            .keys()
+            .find(|name| !known.iter().any(|account| &account.name == *name))
+        {
+            return Err(BuildError::IdlMismatch(format!(
+                "`{}` has no account `{}`",
+                instruction.name, unknown
+            )));
+        }
+        let mut accounts = self.resolve_accounts(idl, instruction)?;
+        accounts.extend(self.remaining_accounts);
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Resolve every account of `instruction`, in IDL order
+    ///
+    /// Seeds and relations can refer to accounts that are themselves derived, so
+    /// derivation repeats until no more accounts resolve.
+    fn resolve_accounts(
+        &self,
+        idl: &Idl,
+        instruction: &IdlInstruction,
+    ) -> Result<Vec<AccountMeta>, BuildError> {
+        let accounts = leaf_accounts(&instruction.accounts);
+        let mut resolved: HashMap<&str, Pubkey> = HashMap::new();
+        for account in &accounts {
+            if let Some(pubkey) = self.accounts.get(&account.name) {
+                resolved.insert(&account.name, *pubkey);
+            } else if let Some(address) = &account.address {
+                let pubkey = Pubkey::from_str(address).map_err(|_| {
+                    BuildError::IdlMismatch(format!(
+                        "account `{}` of `{}` has an invalid address {}",
+                        account.name, instruction.name, address
+                    ))
+                })?;
+                resolved.insert(&account.name, pubkey);
+            } else if let Some(pubkey) = well_known_account(&account.name) {
+                resolved.insert(&account.name, pubkey);
+            }
+        }
+
+        loop {
+            let mut progress = false;
+            for account in &accounts {
+                if account.optional || resolved.contains_key(account.name.as_str()) {
+                    continue;
+                }
+                let derived = self
+                    .derive_account(idl, instruction, account, &resolved)
+                    .map_err(|message| {
+                        BuildError::IdlMismatch(format!(
+                            "cannot derive account `{}` of `{}`: {}",
+                            account.name, instruction.name, message
+                        ))
+                    })?;
+                if let Some(pubkey) = derived {
+                    resolved.insert(&account.name, pubkey);
+                    progress = true;
+                }
+            }
+            if !progress {
+                break;
+            }
+        }
+
+        accounts
+            .iter()
+            .map(|account| {
+                let pubkey = match resolved.get(account.name.as_str()) {
+                    Some(pubkey) => *pubkey,
+                    // Anchor reads an unset optional account as the program ID
+                    None if account.optional => {
+                        return Ok(AccountMeta::new_readonly(self.program_id, false))
+                    }
+                    None => return Err(BuildError::MissingAccount(account.name.clone())),
+                };
+                Ok(if account.writable {
+                    AccountMeta::new(pubkey, account.signer)
+                } else {
+                    AccountMeta::new_readonly(pubkey, account.signer)
+                })
+            })
+            .collect()
+    }
+
+    /// Derive an account from its PDA seeds or `has_one` relations
+    ///
+    /// Returns `None` while something it depends on is unresolved.
+    fn derive_account(
+        &self,
+        idl: &Idl,
+        instruction: &IdlInstruction,
+        account: &IdlInstructionAccount,
+        resolved: &HashMap<&str, Pubkey>,
+    ) -> Result<Option<Pubkey>, String> {
+        if let Some(pda) = &account.pda {
+            if let Some(pubkey) = self.derive_pda(idl, instruction, pda, resolved)? {
+                return Ok(Some(pubkey));
+            }
+        }
+        for relation in &account.relations {
+            let Some(holder) = resolved.get(relation.as_str()) else {
+                continue;
+            };
+            if let Some(bytes) = self.read_account_field(idl, holder, None, &[&account.name])? {
+                return Pubkey::try_from(bytes.as_slice())
+                    .map(Some)
+                    .map_err(|_| format!("`{}.{}` is not a public key", relation, account.name));
+            }
+        }
+        Ok(None)
+    }
+
+    fn derive_pda(
+        &self,
+        idl: &Idl,
+        instruction: &IdlInstruction,
+        pda: &IdlPda,
+        resolved: &HashMap<&str, Pubkey>,
+    ) -> Result<Option<Pubkey>, String> {
+        let mut seeds = Vec::with_capacity(pda.seeds.len());
+        for seed in &pda.seeds {
+            match self.seed_bytes(idl, instruction, seed, resolved)? {
+                Some(bytes) => seeds.push(bytes),
+                None => return Ok(None),
+            }
+        }
+        let program_id = match &pda.program {
+            Some(seed) => match self.seed_bytes(idl, instruction, seed, resolved)? {
+                Some(bytes) => Pubkey::try_from(bytes.as_slice())
+                    .map_err(|_| "the PDA program is not a public key".to_string())?,
+                None => return Ok(None),
+            },
+            None => self.program_id,
+        };
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        Ok(Some(Pubkey::find_program_address(&seeds, &program_id).0))
+    }
+
+    /// The bytes of one seed, or `None` while the value it refers to is unset
+    fn seed_bytes(
+        &self,
+        idl: &Idl,
+        instruction: &IdlInstruction,
+        seed: &IdlSeed,
+        resolved: &HashMap<&str, Pubkey>,
+    ) -> Result<Option<Vec<u8>>, String> {
+        match seed {
+            IdlSeed::Const(seed) => Ok(Some(seed.value.clone())),
+            IdlSeed::Arg(seed) => {
+                let mut path = seed.path.split('.');
+                let name = path.next().unwrap_or_default();
+                let arg = instruction
+                    .args
+                    .iter()
+                    .find(|arg| arg.name == name)
+                    .ok_or_else(|| format!("there is no argument `{}`", name))?;
+                let Some(mut value) = self.args.get(name) else {
+                    return Ok(None);
+                };
+                let mut ty = &arg.ty;
+                for field in path {
+                    ty = &named_fields(idl, ty)?
+                        .iter()
+                        .find(|f| f.name == field)
+                        .ok_or_else(|| format!("argument `{}` has no field `{}`", name, field))?
+                        .ty;
+                    value = value
+                        .get(field)
+                        .ok_or_else(|| format!("argument `{}` has no field `{}`", name, field))?;
+                }
+                let mut bytes = Vec::new();
+                encode_value(idl, ty, value, &mut bytes)?;
+                Ok(Some(seed_from_borsh(ty, bytes)))
+            }
+            IdlSeed::Account(seed) => {
+                let mut path = seed.path.split('.');
+                let name = path.next().unwrap_or_default();
+                let Some(pubkey) = resolved.get(name) else {
+                    return Ok(None);
+                };
+                let fields: Vec<&str> = path.collect();
+                if fields.is_empty() {
+                    return Ok(Some(pubkey.to_bytes().to_vec()));
+                }
+                self.read_account_field(idl, pubkey, seed.account.as_deref(), &fields)
+            }
+        }
+    }
+
+    /// Read field `path` of the account at `pubkey`, as seed bytes
+    ///
+    /// The account type is `type_name`, or found by discriminator. Returns `None` if
+    /// the builder can't read accounts or the account doesn't exist.
+    fn read_account_field(
+        &self,
+        idl: &Idl,
+        pubkey: &Pubkey,
+        type_name: Option<&str>,
+        path: &[&str],
+    ) -> Result<Option<Vec<u8>>, String> {
+        let Some(account) = self.svm.and_then(|svm| svm.get_account(pubkey)) else {
+            return Ok(None);
+        };
+        let data = account.data.as_slice();
+        let idl_account = idl
+            .accounts
+            .iter()
+            .find(|idl_account| match type_name {
+                Some(name) => idl_account.name == name,
+                None => data.starts_with(&idl_account.discriminator),
+            })
+            .ok_or_else(|| format!("account {} has no type in the IDL", pubkey))?;
+
+        let mut ty = IdlType::Defined {
+            name: idl_account.name.clone(),
+            generics: Vec::new(),
+        };
+        let mut offset = idl_account.discriminator.len();
+        for field in path {
+            let mut found = None;
+            for candidate in named_fields(idl, &ty)? {
+                if candidate.name == *field {
+                    found = Some(candidate.ty.clone());
+                    break;
+                }
+                offset += value_len(idl, &candidate.ty, data.get(offset..).unwrap_or_default())?;
+            }
+            ty = found.ok_or_else(|| format!("account {} has no field `{}`", pubkey, field))?;
+        }
+        let field_data = data.get(offset..).unwrap_or_default();
+        let len = value_len(idl, &ty, field_data)?;
+        Ok(Some(seed_from_borsh(&ty, field_data[..len].to_vec())))
+    }
+}
+
+/// Turn the Borsh encoding of a seed value into the bytes Anchor seeds with
+///
+/// Strings and byte vectors seed with their contents, without the length prefix.
+fn seed_from_borsh(ty: &IdlType, mut bytes: Vec<u8>) -> Vec<u8> {
+    if matches!(ty, IdlType::String | IdlType::Bytes) {
+        bytes.drain(..4);
+    }
+    bytes
+}
+
+/// Look up the type named `name`, which must be a plain Borsh type
+fn borsh_type_def<'i>(idl: &'i Idl, name: &str) -> Result<&'i IdlTypeDef, String> {
+    let type_def = idl
+        .types
+        .iter()
+        .find(|type_def| type_def.name == name)
+        .ok_or_else(|| format!("type `{}` is not defined in the IDL", name))?;
+    if type_def.serialization != IdlSerialization::Borsh || !type_def.generics.is_empty() {
+        return Err(format!("type `{}` is not a plain Borsh type", name));
+    }
+    Ok(type_def)
+}
+
+/// The named fields of struct type `ty`
+fn named_fields<'i>(idl: &'i Idl, ty: &IdlType) -> Result<&'i [IdlField], String> {
+    if let IdlType::Defined { name, .. } = ty {
+        if let IdlTypeDefTy::Struct {
+            fields: Some(IdlDefinedFields::Named(fields)),
+        } = &borsh_type_def(idl, name)?.ty
+        {
+            return Ok(fields);
+        }
+    }
+    Err(format!("type {:?} is not a struct with named fields", ty))
+}
+
+/// Length of the Borsh-encoded `ty` at the start of `data`
+fn value_len(idl: &Idl, ty: &IdlType, data: &[u8]) -> Result<usize, String> {
+    let len = match ty {
+        IdlType::Bool | IdlType::U8 | IdlType::I8 => 1,
+        IdlType::U16 | IdlType::I16 => 2,
+        IdlType::U32 | IdlType::I32 | IdlType::F32 => 4,
+        IdlType::U64 | IdlType::I64 | IdlType::F64 => 8,
+        IdlType::U128 | IdlType::I128 => 16,
+        IdlType::Pubkey => 32,
+        IdlType::String | IdlType::Bytes => 4 + read_len(data)?,
+        IdlType::Option(inner) => match data.first() {
+            Some(0) => 1,
+            Some(1) => 1 + value_len(idl, inner, &data[1..])?,
+            _ => return Err("invalid option tag".to_string()),
+        },
+        IdlType::Vec(inner) => {
+            let count = read_len(data)?;
+            4 + items_len(idl, inner, count, &data[4..])?
+        }
+        IdlType::Array(inner, IdlArrayLen::Value(count)) => items_len(idl, inner, *count, data)?,
+        IdlType::Defined { name, generics } if generics.is_empty() => {
+            match &borsh_type_def(idl, name)?.ty {
+                IdlTypeDefTy::Struct { fields } => fields_len(idl, fields.as_ref(), data)?,
+                IdlTypeDefTy::Enum { variants } => {
+                    let variant = data
+                        .first()
+                        .and_then(|index| variants.get(*index as usize))
+                        .ok_or_else(|| format!("invalid variant of `{}`", name))?;
+                    1 + fields_len(idl, variant.fields.as_ref(), &data[1..])?
+                }
+                IdlTypeDefTy::Type { alias } => value_len(idl, alias, data)?,
+            }
+        }
+        other => return Err(format!("type {:?} is not supported", other)),
+    };
+    if len > data.len() {
+        return Err("account data is too short".to_string());
+    }
+    Ok(len)
+}
+
+/// Length of `count` consecutive Borsh-encoded `ty`s at the start of `data`
+fn items_len(idl: &Idl, ty: &IdlType, count: usize, data: &[u8]) -> Result<usize, String> {
+    let mut len = 0;
+    for _ in 0..count {
+        len += value_len(idl, ty, data.get(len..).unwrap_or_default())?;
+    }
+    Ok(len)
+}
+
+/// Length of the Borsh-encoded fields of a struct or enum variant
+fn fields_len(idl: &Idl, fields: Option<&IdlDefinedFields>, data: &[u8]) -> Result<usize, String> {
+    let types: Vec<&IdlType> = match fields {
+        None => Vec::new(),
+        Some(IdlDefinedFields::Named(fields)) => fields.iter().map(|field| &field.ty).collect(),
+        Some(IdlDefinedFields::Tuple(types)) => types.iter().collect(),
+    };
+    let mut len = 0;
+    for ty in types {
+        len += value_len(idl, ty, data.get(len..).unwrap_or_default())?;
+    }
+    Ok(len)
+}
+
+/// Read a Borsh `u32` length prefix
+fn read_len(data: &[u8]) -> Result<usize, String> {
+    let prefix = data.get(..4).ok_or("account data is too short")?;
+    Ok(u32::from_le_bytes(prefix.try_into().expect("4 bytes")) as usize)
+}
+
+/// Borsh-encode `value` as IDL type `ty`
+fn encode_value(idl: &Idl, ty: &IdlType, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    match ty {
+        IdlType::Bool => out.push(value.as_bool().ok_or("expected a bool")? as u8),
+        IdlType::U8 => out.extend(unsigned::<u8>(value)?.to_le_bytes()),
+        IdlType::I8 => out.extend(signed::<i8>(value)?.to_le_bytes()),
+        IdlType::U16 => out.extend(unsigned::<u16>(value)?.to_le_bytes()),
+        IdlType::I16 => out.extend(signed::<i16>(value)?.to_le_bytes()),
+        IdlType::U32 => out.extend(unsigned::<u32>(value)?.to_le_bytes()),
+        IdlType::I32 => out.extend(signed::<i32>(value)?.to_le_bytes()),
+        IdlType::U64 => out.extend(unsigned::<u64>(value)?.to_le_bytes()),
+        IdlType::I64 => out.extend(signed::<i64>(value)?.to_le_bytes()),
+        IdlType::U128 => out.extend(unsigned::<u128>(value)?.to_le_bytes()),
+        IdlType::I128 => out.extend(signed::<i128>(value)?.to_le_bytes()),
+        IdlType::F32 => {
+            out.extend((value.as_f64().ok_or("expected a number")? as f32).to_le_bytes())
+        }
+        IdlType::F64 => out.extend(value.as_f64().ok_or("expected a number")?.to_le_bytes()),
+        IdlType::String => {
+            let string = value.as_str().ok_or("expected a string")?;
+            out.extend((string.len() as u32).to_le_bytes());
+            out.extend(string.as_bytes());
+        }
+        IdlType::Pubkey => out.extend(pubkey(value)?.to_bytes()),
+        IdlType::Bytes => {
+            let items = value.as_array().ok_or("expected an array of bytes")?;
+            out.extend((items.len() as u32).to_le_bytes());
+            for item in items {
+                out.push(unsigned::<u8>(item)?);
+            }
+        }
+        IdlType::Option(inner) => {
+            if value.is_null() {
+                out.push(0);
+            } else {
+                out.push(1);
+                encode_value(idl, inner, value, out)?;
+            }
+        }
+        IdlType::Vec(inner) => {
+            let items = value.as_array().ok_or("expected an array")?;
+            out.extend((items.len() as u32).to_le_bytes());
+            for item in items {
+                encode_value(idl, inner, item, out)?;
+            }
+        }
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => {
+            let items = value.as_array().ok_or("expected an array")?;
+            if items.len() != *len {
+                return Err(format!("expected {} elements, got {}", len, items.len()));
+            }
+            for item in items {
+                encode_value(idl, inner, item, out)?;
+            }
+        }
+        IdlType::Defined { name, generics } if generics.is_empty() => {
+            encode_defined(idl, name, value, out)?
+        }
+        other => return Err(format!("type {:?} is not supported", other)),
+    }
+    Ok(())
+}
+
+/// Borsh-encode `value` as the type named `name` in the IDL's `types`
+fn encode_defined(idl: &Idl, name: &str, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    match &borsh_type_def(idl, name)?.ty {
+        IdlTypeDefTy::Struct { fields } => {
+            encode_fields(idl, fields.as_ref(), value, out).map_err(|e| format!("{}: {}", name, e))
+        }
+        IdlTypeDefTy::Enum { variants } => {
+            let (variant_name, fields_value) = match value {
+                Value::String(variant) => (variant.as_str(), &Value::Null),
+                Value::Object(object) if object.len() == 1 => {
+                    let (variant, fields) = object.iter().next().expect("one entry");
+                    (variant.as_str(), fields)
+                }
+                _ => return Err(format!("expected a variant of `{}`", name)),
+            };
+            let index = variants
+                .iter()
+                .position(|variant| variant.name == variant_name)
+                .ok_or_else(|| format!("`{}` has no variant `{}`", name, variant_name))?;
+            out.push(index as u8);
+            encode_fields(idl, variants[index].fields.as_ref(), fields_value, out)
+                .map_err(|e| format!("{}::{}: {}", name, variant_name, e))
+        }
+        IdlTypeDefTy::Type { alias } => encode_value(idl, alias, value, out),
+    }
+}
+
+/// Borsh-encode the fields of a struct or enum variant, in declaration order
+fn encode_fields(
+    idl: &Idl,
+    fields: Option<&IdlDefinedFields>,
+    value: &Value,
+    out: &mut Vec<u8>,
+) -> Result<(), String> {
+    match fields {
+        None => Ok(()),
+        Some(IdlDefinedFields::Named(fields)) => {
+            let object = value.as_object().ok_or("expected an object")?;
+            for field in fields {
+                let field_value = object
+                    .get(&field.name)
+                    .ok_or_else(|| format!("missing field `{}`", field.name))?;
+                encode_value(idl, &field.ty, field_value, out)
+                    .map_err(|e| format!("field `{}`: {}", field.name, e))?;
+            }
+            Ok(())
+        }
+        Some(IdlDefinedFields::Tuple(types)) => {
+            let items = value.as_array().ok_or("expected an array")?;
+            if items.len() != types.len() {
+                return Err(format!(
+                    "expected {} fields, got {}",
+                    types.len(),
+                    items.len()
+                ));
+            }
+            for (ty, item) in types.iter().zip(items) {
+                encode_value(idl, ty, item, out)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Read an unsigned integer from a JSON number or decimal string
+fn unsigned<T: TryFrom<u128>>(value: &Value) -> Result<T, String> {
+    let wide = match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(string) => string.parse::<u128>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("expected an unsigned integer, got {}", value))?;
+    T::try_from(wide).map_err(|_| format!("{} is out of range", wide))
+}
+
+/// Read a signed integer from a JSON number or decimal string
+fn signed<T: TryFrom<i128>>(value: &Value) -> Result<T, String> {
+    let wide = match value {
+        Value::Number(number) => number.as_i64().map(i128::from),
+        Value::String(string) => string.parse::<i128>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("expected an integer, got {}", value))?;
+    T::try_from(wide).map_err(|_| format!("{} is out of range", wide))
+}
+
+/// Read a public key from a base58 string or an array of 32 bytes
+fn pubkey(value: &Value) -> Result<Pubkey, String> {
+    match value {
+        Value::String(string) => {
+            Pubkey::from_str(string).map_err(|_| format!("invalid public key {}", string))
+        }
+        Value::Array(items) => {
+            let bytes = items
+                .iter()
+                .map(unsigned::<u8>)
+                .collect::<Result<Vec<u8>, _>>()?;
+            Pubkey::try_from(bytes.as_slice()).map_err(|_| "expected 32 bytes".to_string())
+        }
+        _ => Err(format!("expected a public key, got {}", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::idl::{parse_idl, tests::TEST_IDL};
+    use crate::AnchorContext;
+    use borsh::BorshSerialize;
+    use litesvm::LiteSVM;
+    use serde_json::json;
+
+    const AMM_IDL: &str = r#"{
+        "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+        "metadata": { "name": "amm", "version": "0.1.0", "spec": "0.1.0" },
+        "instructions": [
+            {
+                "name": "create_pool",
+                "discriminator": [1, 2],
+                "accounts": [
+                    { "name": "creator", "writable": true, "signer": true },
+                    {
+                        "name": "mints",
+                        "accounts": [
+                            { "name": "mint_a" },
+                            { "name": "mint_b" }
+                        ]
+                    },
+                    { "name": "oracle", "optional": true },
+                    { "name": "system_program", "address": "11111111111111111111111111111111" }
+                ],
+                "args": [
+                    { "name": "seed", "type": "u64" },
+                    { "name": "label", "type": "string" },
+                    { "name": "admin", "type": { "option": "pubkey" } },
+                    { "name": "fees", "type": { "defined": { "name": "Fees" } } },
+                    { "name": "curve", "type": { "defined": { "name": "Curve" } } },
+                    { "name": "weights", "type": { "array": ["u8", 2] } }
+                ]
+            }
+        ],
+        "types": [
+            {
+                "name": "Fees",
+                "type": {
+                    "kind": "struct",
+                    "fields": [
+                        { "name": "trade_bps", "type": "u16" },
+                        { "name": "max", "type": "u128" }
+                    ]
+                }
+            },
+            {
+                "name": "Curve",
+                "type": {
+                    "kind": "enum",
+                    "variants": [
+                        { "name": "ConstantProduct" },
+                        { "name": "Stable", "fields": [{ "name": "amp", "type": "u64" }] }
+                    ]
+                }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_idl_instruction_encodes_args_and_accounts() {
+        let idl = parse_idl(AMM_IDL).unwrap();
+        let (creator, mint_a, mint_b, admin) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = IdlInstructionBuilder::new(&idl, "create_pool")
+            .arg("seed", 42u64)
+            .arg("label", "SOL/USDC")
+            .arg("admin", admin.to_string())
+            .arg(
+                "fees",
+                json!({ "trade_bps": 30, "max": "340282366920938463463374607431768211455" }),
+            )
+            .arg("curve", json!({ "Stable": { "amp": 100 } }))
+            .arg("weights", json!([50, 50]))
+            .account("creator", creator)
+            .account("mint_a", mint_a)
+            .account("mint_b", mint_b)
+            .instruction()
+            .unwrap();
+
+        let mut expected = vec![1, 2];
+        42u64.serialize(&mut expected).unwrap();
+        "SOL/USDC".to_string().serialize(&mut expected).unwrap();
+        Some(admin.to_bytes()).serialize(&mut expected).unwrap();
+        (30u16, u128::MAX).serialize(&mut expected).unwrap();
+        (1u8, 100u64, [50u8, 50u8])
+            .serialize(&mut expected)
+            .unwrap();
+        assert_eq!(ix.data, expected);
+
+        let program_id = idl_program_id(&idl).unwrap();
+        assert_eq!(ix.program_id, program_id);
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(creator, true),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new_readonly(program_id, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_idl_instruction_errors() {
+        let idl = parse_idl(AMM_IDL).unwrap();
+        let build = |name: &str| {
+            IdlInstructionBuilder::new(&idl, name)
+                .arg("seed", 1u64)
+                .arg("label", "")
+                .arg("admin", Value::Null)
+                .arg("fees", json!({ "trade_bps": 30, "max": 0 }))
+                .arg("curve", "ConstantProduct")
+                .arg("weights", json!([1, 2]))
+                .account("creator", Pubkey::new_unique())
+                .account("mint_a", Pubkey::new_unique())
+                .account("mint_b", Pubkey::new_unique())
+        };
+        assert!(build("create_pool").instruction().is_ok());
+
+        assert_eq!(
+            build("close_pool").instruction().unwrap_err(),
+            BuildError::UnknownInstruction("close_pool".to_string())
+        );
+        assert!(matches!(
+            build("create_pool").arg("seed", -1).instruction(),
+            Err(BuildError::InvalidArg(name, _)) if name == "seed"
+        ));
+        assert!(matches!(
+            build("create_pool").arg("weights", json!([1])).instruction(),
+            Err(BuildError::InvalidArg(name, _)) if name == "weights"
+        ));
+        assert!(matches!(
+            build("create_pool").arg("curve", "Linear").instruction(),
+            Err(BuildError::InvalidArg(name, _)) if name == "curve"
+        ));
+        assert!(matches!(
+            build("create_pool").arg("amount", 1).instruction(),
+            Err(BuildError::IdlMismatch(_))
+        ));
+
+        let missing = IdlInstructionBuilder::new(&idl, "create_pool").instruction();
+        assert_eq!(
+            missing.unwrap_err(),
+            BuildError::MissingArg("seed".to_string())
+        );
+    }
+
+    /// An escrow IDL whose accounts are derived like `anchor build` emits them
+    fn escrow_idl() -> Idl {
+        let escrow_seed = json!({ "kind": "const", "value": b"escrow".to_vec() });
+        let vault_pda = json!({
+            "seeds": [
+                { "kind": "account", "path": "escrow" },
+                { "kind": "const", "value": spl_token::id().to_bytes().to_vec() },
+                { "kind": "account", "path": "mint_a" }
+            ],
+            "program": {
+                "kind": "const",
+                "value": spl_associated_token_account::id().to_bytes().to_vec()
+            }
+        });
+        let idl = json!({
+            "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+            "metadata": { "name": "escrow", "version": "0.1.0", "spec": "0.1.0" },
+            "instructions": [
+                {
+                    "name": "make",
+                    "discriminator": [1],
+                    "accounts": [
+                        { "name": "maker", "writable": true, "signer": true },
+                        { "name": "mint_a" },
+                        {
+                            "name": "escrow",
+                            "writable": true,
+                            "pda": { "seeds": [
+                                escrow_seed,
+                                { "kind": "account", "path": "maker" },
+                                { "kind": "arg", "path": "params.seed" }
+                            ] }
+                        },
+                        { "name": "vault", "writable": true, "pda": vault_pda },
+                        { "name": "token_program", "address": spl_token::id().to_string() }
+                    ],
+                    "args": [{ "name": "params", "type": { "defined": { "name": "MakeParams" } } }]
+                },
+                {
+                    "name": "take",
+                    "discriminator": [2],
+                    "accounts": [
+                        { "name": "taker", "writable": true, "signer": true },
+                        { "name": "maker", "writable": true, "relations": ["escrow"] },
+                        { "name": "mint_a", "relations": ["escrow"] },
+                        { "name": "escrow", "writable": true },
+                        { "name": "vault", "writable": true, "pda": vault_pda },
+                        {
+                            "name": "receipt",
+                            "pda": { "seeds": [
+                                { "kind": "account", "path": "escrow.label", "account": "Escrow" },
+                                { "kind": "account", "path": "escrow.receive" }
+                            ] }
+                        }
+                    ],
+                    "args": []
+                }
+            ],
+            "accounts": [{ "name": "Escrow", "discriminator": [9, 9] }],
+            "types": [
+                {
+                    "name": "MakeParams",
+                    "type": { "kind": "struct", "fields": [{ "name": "seed", "type": "u64" }] }
+                },
+                {
+                    "name": "Escrow",
+                    "type": {
+                        "kind": "struct",
+                        "fields": [
+                            { "name": "seed", "type": "u64" },
+                            { "name": "maker", "type": "pubkey" },
+                            { "name": "label", "type": "string" },
+                            { "name": "mint_a", "type": "pubkey" },
+                            { "name": "receive", "type": "u64" }
+                        ]
+                    }
+                }
+            ]
+        });
+        parse_idl(&idl.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_idl_instruction_derives_pdas() {
+        let idl = escrow_idl();
+        let program_id = idl_program_id(&idl).unwrap();
+        let (maker, mint_a) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let ix = IdlInstructionBuilder::new(&idl, "make")
+            .arg("params", json!({ "seed": 7 }))
+            .account("maker", maker)
+            .account("mint_a", mint_a)
+            .instruction()
+            .unwrap();
+
+        let (escrow, _) = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), &7u64.to_le_bytes()],
+            &program_id,
+        );
+        let vault = spl_associated_token_account::get_associated_token_address(&escrow, &mint_a);
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys, vec![maker, mint_a, escrow, vault, spl_token::id()]);
+        assert!(ix.accounts[2].is_writable);
+
+        // Without the argument the escrow can't be derived
+        let result = IdlInstructionBuilder::new(&idl, "make")
+            .account("maker", maker)
+            .account("mint_a", mint_a)
+            .instruction();
+        assert_eq!(
+            result.unwrap_err(),
+            BuildError::MissingArg("params".to_string())
+        );
+    }
+
+    #[test]
+    fn test_idl_instruction_reads_account_data() {
+        let idl = escrow_idl();
+        let (maker, mint_a, taker) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let escrow = Pubkey::new_unique();
+        let mut data = vec![9, 9];
+        (
+            7u64,
+            maker.to_bytes(),
+            "SOL-USDC".to_string(),
+            mint_a.to_bytes(),
+            500u64,
+        )
+            .serialize(&mut data)
+            .unwrap();
+        let mut svm = LiteSVM::new();
+        svm.set_account(
+            escrow,
+            solana_sdk::account::Account {
+                lamports: 1_000_000,
+                data,
+                owner: idl_program_id(&idl).unwrap(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        let take = || {
+            IdlInstructionBuilder::new(&idl, "take")
+                .account("taker", taker)
+                .account("escrow", escrow)
+        };
+        // Relations and data seeds need to read the escrow
+        assert_eq!(
+            take().instruction().unwrap_err(),
+            BuildError::MissingAccount("maker".to_string())
+        );
+
+        let ix = take().with_svm(&svm).instruction().unwrap();
+        let vault = spl_associated_token_account::get_associated_token_address(&escrow, &mint_a);
+        let (receipt, _) = Pubkey::find_program_address(
+            &[b"SOL-USDC", &500u64.to_le_bytes()],
+            &idl_program_id(&idl).unwrap(),
+        );
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys, vec![taker, maker, mint_a, escrow, vault, receipt]);
+    }
+
+    #[test]
+    fn test_idl_instruction_fills_program_accounts() {
+        // `Interface<TokenInterface>` and `Sysvar<Rent>` accounts have no fixed address
+        let idl = parse_idl(
+            r#"{
+                "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+                "metadata": { "name": "vault", "version": "0.1.0", "spec": "0.1.0" },
+                "instructions": [{
+                    "name": "open",
+                    "discriminator": [1],
+                    "accounts": [
+                        { "name": "payer", "writable": true, "signer": true },
+                        { "name": "token_program" },
+                        { "name": "associated_token_program" },
+                        { "name": "rent" },
+                        { "name": "system_program" }
+                    ],
+                    "args": []
+                }]
+            }"#,
+        )
+        .unwrap();
+        let payer = Pubkey::new_unique();
+
+        let ix = IdlInstructionBuilder::new(&idl, "open")
+            .account("payer", payer)
+            .instruction()
+            .unwrap();
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            vec![
+                payer,
+                spl_token::id(),
+                spl_associated_token_account::id(),
+                solana_program::sysvar::rent::id(),
+                solana_program::system_program::id(),
+            ]
+        );
+
+        // An explicit account still wins, e.g. for Token-2022 mints
+        let ix = IdlInstructionBuilder::new(&idl, "open")
+            .account("payer", payer)
+            .account("token_program", spl_token_2022::id())
+            .instruction()
+            .unwrap();
+        assert_eq!(ix.accounts[1].pubkey, spl_token_2022::id());
+    }
+
+    #[test]
+    fn test_idl_instruction_accounts_map() {
+        let idl = parse_idl(AMM_IDL).unwrap();
+        let (creator, mint_a, mint_b) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let build = |accounts: HashMap<&str, Pubkey>| {
+            IdlInstructionBuilder::new(&idl, "create_pool")
+                .arg("seed", 1u64)
+                .arg("label", "")
+                .arg("admin", Value::Null)
+                .arg("fees", json!({ "trade_bps": 30, "max": 0 }))
+                .arg("curve", "ConstantProduct")
+                .arg("weights", json!([1, 2]))
+                .accounts_map(accounts)
+                .instruction()
+        };
+
+        let ix = build(HashMap::from([
+            ("mint_b", mint_b),
+            ("creator", creator),
+            ("mint_a", mint_a),
+        ]))
+        .unwrap();
+        let keys: Vec<Pubkey> = ix.accounts.iter().take(3).map(|meta| meta.pubkey).collect();
+        assert_eq!(keys, vec![creator, mint_a, mint_b]);
+
+        let result = build(HashMap::from([
+            ("creator", creator),
+            ("mint_a", mint_a),
+            ("mint_c", mint_b),
+        ]));
+        assert!(matches!(result, Err(BuildError::IdlMismatch(_))));
+    }
+
+    #[test]
+    fn test_context_idl_instruction() {
+        let idl = parse_idl(TEST_IDL).unwrap();
+        let program_id = idl_program_id(&idl).unwrap();
+        let mut ctx = AnchorContext::new(LiteSVM::new(), program_id);
+        let maker = Pubkey::new_unique();
+
+        let result = ctx.idl_instruction("make").instruction();
+        assert_eq!(result.unwrap_err(), BuildError::MissingIdl(program_id));
+
+        ctx.add_idl(idl).unwrap();
+        let ix = ctx
+            .idl_instruction("make")
+            .arg("seed", 42u64)
+            .account("maker", maker)
+            .instruction()
+            .unwrap();
+        assert_eq!(
+            ix,
+            ctx.build_instruction("make", ix.accounts.clone(), 42u64)
+                .unwrap()
+        );
+        assert_eq!(ix.accounts[0], AccountMeta::new(maker, true));
+
+        let result = ctx.idl_instruction("make").arg("seed", 1).instruction();
+        assert_eq!(
+            result.unwrap_err(),
+            BuildError::MissingAccount("maker".to_string())
+        );
+    }
+}
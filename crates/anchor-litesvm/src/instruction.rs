@@ -1,4 +1,4 @@
-use anchor_lang::AnchorSerialize;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize, Discriminator};
 use sha2::{Digest, Sha256};
 use solana_program::instruction::{AccountMeta, Instruction};
 use solana_program::pubkey::Pubkey;
@@ -9,6 +9,9 @@ use solana_program::pubkey::Pubkey;
 /// - Calculates the 8-byte discriminator from the instruction name
 /// - Serializes the instruction arguments using Borsh
 /// - Combines them into the complete instruction data
+///
+/// Plain tuples of up to 20 elements serialize like the matching argument list, so
+/// instructions with many arguments don't need a dedicated args struct.
 pub fn build_anchor_instruction<T>(
     program_id: &Pubkey,
     instruction_name: &str,
@@ -32,6 +35,30 @@ where
     })
 }
 
+/// Builds an Anchor instruction with an explicit discriminator
+///
+/// Use this for programs declaring custom discriminators (Anchor 0.31+), whose
+/// length and value can't be derived from the instruction name. The discriminator
+/// is usually taken from the IDL via [`crate::idl::instruction_discriminator`].
+pub fn build_anchor_instruction_with_discriminator<T>(
+    program_id: &Pubkey,
+    discriminator: &[u8],
+    accounts: Vec<AccountMeta>,
+    args: T,
+) -> Result<Instruction, Box<dyn std::error::Error>>
+where
+    T: AnchorSerialize,
+{
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)?;
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Calculate the Anchor instruction discriminator
 ///
 /// Anchor uses the first 8 bytes of sha256("global:<instruction_name>")
@@ -46,6 +73,57 @@ pub fn calculate_anchor_discriminator(instruction_name: &str) -> [u8; 8] {
     discriminator
 }
 
+/// Assert that an instruction's data decodes exactly as the args struct `T`
+///
+/// Checks that the data starts with `T::DISCRIMINATOR` and that the remaining
+/// bytes deserialize into `T` with nothing left over. This catches argument
+/// order or type mismatches before the program rejects them opaquely.
+///
+/// # Returns
+///
+/// Returns the decoded args for further comparison
+///
+/// # Panics
+///
+/// Panics if the discriminator doesn't match or the args don't round-trip
+///
+/// # Example
+///
+/// ```ignore
+/// let ix = ctx.program()
+///     .accounts(escrow::client::accounts::Make { /* ... */ })
+///     .args(escrow::client::args::Make { seed: 1, receive: 500 })
+///     .instruction()?;
+/// let args = assert_args_roundtrip::<escrow::client::args::Make>(&ix);
+/// assert_eq!(args.receive, 500);
+/// ```
+pub fn assert_args_roundtrip<T>(instruction: &Instruction) -> T
+where
+    T: Discriminator + AnchorDeserialize,
+{
+    let type_name = std::any::type_name::<T>();
+    let args = instruction
+        .data
+        .strip_prefix(T::DISCRIMINATOR)
+        .unwrap_or_else(|| {
+            panic!(
+                "Instruction data does not start with the discriminator of {}.\nExpected: {:?}\nActual data: {:?}",
+                type_name,
+                T::DISCRIMINATOR,
+                instruction.data
+            )
+        });
+
+    T::try_from_slice(args).unwrap_or_else(|e| {
+        panic!(
+            "Instruction args do not round-trip as {} ({} bytes after the discriminator): {}",
+            type_name,
+            args.len(),
+            e
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +175,69 @@ mod tests {
         assert_eq!(instruction.accounts.len(), 2);
         assert!(instruction.data.len() >= 8); // At least discriminator
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_instruction_building_with_long_tuple() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let args = (
+            1u8, 2u16, 3u32, 4u64, 5u128, 6i8, 7i16, 8i32, 9i64, true, owner,
+            "pool".to_string(),
+        );
+
+        let instruction =
+            build_anchor_instruction(&program_id, "configure", vec![], args.clone()).unwrap();
+
+        let mut expected = calculate_anchor_discriminator("configure").to_vec();
+        AnchorSerialize::serialize(&args, &mut expected).unwrap();
+        assert_eq!(instruction.data, expected);
+        assert_eq!(instruction.data.len(), 8 + 1 + 2 + 4 + 8 + 16 + 1 + 2 + 4 + 8 + 1 + 32 + 8);
+    }
+
+    #[test]
+    fn test_instruction_building_with_custom_discriminator() {
+        let program_id = Pubkey::new_unique();
+
+        let instruction =
+            build_anchor_instruction_with_discriminator(&program_id, &[1], vec![], 42u64)
+                .unwrap();
+
+        assert_eq!(instruction.data[0], 1);
+        assert_eq!(instruction.data.len(), 1 + 8);
+        assert_eq!(&instruction.data[1..], &42u64.to_le_bytes());
+    }
+
+    mod roundtrip {
+        use super::super::*;
+        use anchor_lang::prelude::*;
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+        pub struct Make {
+            pub seed: u64,
+            pub receive: u64,
+        }
+
+        impl Discriminator for Make {
+            const DISCRIMINATOR: &'static [u8] = &[138, 227, 232, 77, 223, 166, 96, 197];
+        }
+
+        #[test]
+        fn test_assert_args_roundtrip() {
+            let args = Make { seed: 1, receive: 500 };
+            let ix = build_anchor_instruction(&Pubkey::new_unique(), "make", vec![], &args)
+                .unwrap();
+
+            assert_eq!(assert_args_roundtrip::<Make>(&ix), args);
+        }
+
+        #[test]
+        #[should_panic(expected = "do not round-trip")]
+        fn test_assert_args_roundtrip_wrong_types() {
+            // `receive` serialized as u32 instead of u64
+            let ix = build_anchor_instruction(&Pubkey::new_unique(), "make", vec![], (1u64, 500u32))
+                .unwrap();
+
+            assert_args_roundtrip::<Make>(&ix);
+        }
+    }
+}
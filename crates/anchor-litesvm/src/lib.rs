@@ -27,8 +27,7 @@
 //! ## Quick Start
 //!
 //! ```rust,ignore
-//! use anchor_litesvm::{AnchorLiteSVM, TestHelpers, AssertionHelpers};
-//! use solana_sdk::signature::Signer;
+//! use anchor_litesvm::prelude::*;
 //!
 //! // 1. Generate client types from your program
 //! anchor_lang::declare_program!(my_program);
@@ -121,30 +120,62 @@
 //! - [`account`] - Account deserialization utilities
 //! - [`builder`] - Test environment builders
 //! - [`context`] - Main test context (`AnchorContext`)
+//! - [`errors`] - Typed assertions on Anchor program errors
 //! - [`events`] - Event parsing helpers
+//! - [`idl`] - IDL loading and discovery
+//! - [`idl_instruction`] - Instructions built from an IDL and JSON arguments
 //! - [`instruction`] - Instruction building utilities
+//! - [`prelude`] - Glob import of the traits and types most tests need
 //! - [`program`] - Simplified Program API
+//! - [`return_data`] - Typed access to program return data
+//! - [`scaffold`] - Test module generation from an IDL
 
 pub mod account;
 pub mod builder;
 pub mod context;
+pub mod errors;
 pub mod events;
+pub mod idl;
+pub mod idl_instruction;
 pub mod instruction;
+pub mod prelude;
 pub mod program;
+pub mod return_data;
+pub mod scaffold;
 
 // Re-export main types for convenience
-pub use account::{get_anchor_account, get_anchor_account_unchecked, AccountError};
+pub use account::{
+    get_anchor_account, get_anchor_account_unchecked, AccountError, AnchorStateSurgery,
+};
 pub use builder::{AnchorLiteSVM, ProgramTestExt};
 pub use context::AnchorContext;
+pub use errors::ErrorAssertions;
 pub use events::{parse_event_data, EventError, EventHelpers};
-pub use instruction::{build_anchor_instruction, calculate_anchor_discriminator};
-pub use program::{InstructionBuilder, Program};
+pub use idl::{load_idl, Idl, IdlError};
+pub use idl_instruction::IdlInstructionBuilder;
+pub use instruction::{
+    assert_args_roundtrip, build_anchor_instruction, build_anchor_instruction_with_discriminator,
+    calculate_anchor_discriminator,
+};
+pub use program::{AccountMismatch, BuildError, InstructionBuilder, Program, ValidationReport};
+pub use return_data::{ReturnDataError, ReturnDataHelpers};
+pub use scaffold::{generate_test_module, write_test_module};
 
 // Re-export litesvm-utils functionality for convenience
 pub use litesvm_utils::{
-    AssertionHelpers, LiteSVMBuilder, TestHelpers, TransactionError, TransactionHelpers,
+    Actor, ActorBuilder, ActorHelpers, ActorTransaction, AssertionError, AssertionHelpers,
+    BalanceSnapshot, ComputeBudget, CuBudgets, ExecutionOrder, LiteSVMBuilder, LogFilter,
+    LookupTableHelpers, Nft, NftHelpers, NftMetadata, OrderingRun, PayerPool, PersonaBuilder,
+    ProgramData, RevivalAttempt, RevivalTiming, ScenarioHelpers, SoftAssertions, StakeHelpers,
+    TestHelpers, Token2022Helpers, TransactionBuilder, TransactionError, TransactionHelpers,
     TransactionResult,
 };
+pub use litesvm_utils::{
+    assert_tx_fits, clear_surgery_report, expect, fund_account, keypair_from_name, matrix,
+    product2, product3, product4, record_account_diffs, surgery_report, track_accounts,
+    AccountBuilder, AccountDiff, AccountExpectation, AccountFilter, AccountIter, StateSurgery,
+    SurgeryRecord, SvmSnapshot, TokenAccountExpectation,
+};
 
 // Re-export commonly used external types
 pub use anchor_lang::{AccountDeserialize, AnchorSerialize};
@@ -194,4 +225,269 @@ mod integration_tests {
         assert_eq!(instruction.program_id, program_id);
         assert!(!instruction.data.is_empty());
     }
+
+    #[test]
+    fn test_warn_unused_accounts() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let from = ctx.svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let leftover = Pubkey::new_unique();
+        ctx.warn_unused_accounts(true);
+
+        // A transfer with a copy-pasted extra writable account
+        let mut ix = solana_program::system_instruction::transfer(&from.pubkey(), &to, 1_000_000);
+        ix.accounts.push(AccountMeta::new(leftover, false));
+        ctx.execute_instruction(ix, &[&from]).unwrap().assert_success();
+
+        assert_eq!(ctx.untouched_accounts(), &[leftover]);
+    }
+
+    #[test]
+    fn test_auto_create_atas() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let authority = ctx.svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = ctx.svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let alice = ctx.create_actor().with_tokens(&mint, 1_000).build().unwrap();
+        let bob = Pubkey::new_unique();
+        let bob_ata = spl_associated_token_account::get_associated_token_address(&bob, &mint);
+        ctx.auto_create_atas(true);
+
+        // Anchor instructions typically pass the recipient alongside its ATA
+        let mut ix = spl_token::instruction::transfer_checked(
+            &spl_token::id(),
+            &alice.ata(&mint),
+            &mint,
+            &bob_ata,
+            &alice.pubkey(),
+            &[],
+            400,
+            6,
+        )
+        .unwrap();
+        ix.accounts.push(AccountMeta::new_readonly(bob, false));
+
+        ctx.execute_instruction(ix, &[&alice.keypair]).unwrap().assert_success();
+        ctx.svm.assert_token_balance(&bob_ata, 400);
+    }
+
+    #[test]
+    fn test_schedule_at_slot() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let from = ctx.svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let transfer = |lamports| solana_program::system_instruction::transfer(&from.pubkey(), &to, lamports);
+
+        ctx.schedule_at_slot(20, transfer(1_000_000), &[&from]);
+        ctx.schedule_at_slot(10, transfer(1_000_000), &[&from]);
+        ctx.schedule_at_slot(50, transfer(5_000_000), &[&from]);
+
+        let results = ctx.run_until_slot(30).unwrap();
+        let slots: Vec<u64> = results.iter().map(|(slot, _)| *slot).collect();
+        assert_eq!(slots, vec![10, 20]);
+        assert!(results.iter().all(|(_, result)| result.is_success()));
+        assert_eq!(ctx.svm.get_current_slot(), 30);
+        assert_eq!(ctx.svm.get_balance(&to), Some(2_000_000));
+
+        // The transaction scheduled for slot 50 is still queued
+        let results = ctx.run_until_slot(60).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(ctx.svm.get_balance(&to), Some(7_000_000));
+    }
+
+    #[test]
+    fn test_retry() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+        let transfer = solana_program::system_instruction::transfer(&from.pubkey(), &to, 1_000_000);
+
+        // The same transaction fails until its sender is funded on the third attempt
+        let mut calls = 0;
+        let result = ctx
+            .retry(3, |ctx| {
+                calls += 1;
+                if calls == 3 {
+                    ctx.airdrop(&from.pubkey(), 1_000_000_000)?;
+                }
+                ctx.execute_instruction(transfer.clone(), &[&from])
+            })
+            .unwrap();
+        result.assert_success();
+        assert_eq!(calls, 3);
+        assert_eq!(ctx.svm.get_balance(&to), Some(1_000_000));
+
+        let overdraw = solana_program::system_instruction::transfer(&from.pubkey(), &to, u64::MAX);
+        let err = ctx
+            .retry(2, |ctx| ctx.execute_instruction(overdraw.clone(), &[&from]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("All 2 attempts failed"));
+        assert!(err.contains("attempt 1: ") && err.contains("attempt 2: "));
+    }
+
+    #[test]
+    fn test_fork() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let escrow = ctx.svm.create_funded_account(1_000_000_000).unwrap();
+        let taker = Pubkey::new_unique();
+        let maker = Pubkey::new_unique();
+        let pay = |to: &Pubkey| {
+            solana_program::system_instruction::transfer(&escrow.pubkey(), to, 1_000_000)
+        };
+
+        let mut refund = ctx.fork();
+        assert_eq!(refund.payer().pubkey(), ctx.payer().pubkey());
+
+        ctx.execute_instruction(pay(&taker), &[&escrow]).unwrap().assert_success();
+        refund.execute_instruction(pay(&maker), &[&escrow]).unwrap().assert_success();
+
+        assert_eq!(ctx.svm.get_balance(&taker), Some(1_000_000));
+        assert!(!ctx.account_exists(&maker));
+        assert_eq!(refund.svm.get_balance(&maker), Some(1_000_000));
+        assert!(!refund.account_exists(&taker));
+    }
+
+    #[test]
+    fn test_fork_has_its_own_helper_state() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let account = solana_sdk::account::Account::new(1_000_000, 0, &Pubkey::default());
+        ctx.svm
+            .unsafe_state()
+            .set_account(&Pubkey::new_unique(), account.clone())
+            .unwrap();
+        let report = surgery_report(&ctx.svm);
+
+        let mut fork = ctx.fork();
+        fork.svm
+            .unsafe_state()
+            .set_account(&Pubkey::new_unique(), account)
+            .unwrap();
+        assert_eq!(surgery_report(&fork.svm).len(), report.len() + 1);
+        assert_eq!(surgery_report(&ctx.svm), report);
+
+        clear_surgery_report(&mut fork.svm);
+        assert!(surgery_report(&fork.svm).is_empty());
+        assert_eq!(surgery_report(&ctx.svm), report);
+    }
+
+    #[test]
+    fn test_transaction_history() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let user = ctx.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let transfer = |lamports| {
+            solana_program::system_instruction::transfer(&user.pubkey(), &to, lamports)
+        };
+
+        let ok = ctx.execute_instruction(transfer(1_000_000), &[&user]).unwrap();
+        let failed = ctx.execute_instruction(transfer(u64::MAX), &[&user]).unwrap();
+        let fresh = Pubkey::new_unique();
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[solana_program::system_instruction::transfer(&user.pubkey(), &fresh, 2_000_000)],
+            Some(&user.pubkey()),
+            &[&user],
+            ctx.latest_blockhash(),
+        );
+        let signature = ctx.send_and_confirm_transaction(&tx).unwrap();
+        // Accounts of hand-built transactions are tracked too
+        assert!(ctx.svm.iter_accounts(None).any(|(pubkey, _)| pubkey == fresh));
+
+        let history = ctx.transaction_history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].signature(), ok.signature());
+        assert!(history[0].is_success());
+        assert_eq!(history[1].signature(), failed.signature());
+        assert!(!history[1].is_success());
+        assert_eq!(history[2].signature(), signature);
+
+        ctx.clear_transaction_history();
+        assert!(ctx.transaction_history().is_empty());
+    }
+
+    #[test]
+    fn test_simulate() {
+        let ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let to = Pubkey::new_unique();
+        let payer = ctx.payer().pubkey();
+        let ix = solana_program::system_instruction::transfer(&payer, &to, 5_000_000);
+
+        let preview = ctx.simulate(ix, &[]).unwrap();
+        preview.assert_success();
+        assert!(preview.instruction_name().unwrap().starts_with("instruction to 1111"));
+        assert!(!ctx.account_exists(&to));
+        assert!(ctx.transaction_history().is_empty());
+    }
+
+    #[test]
+    fn test_view_reports_failure() {
+        let ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let err = ctx.view::<u64, _>("get_price", vec![], 1_000u64).unwrap_err();
+        assert!(err.to_string().starts_with("View get_price failed:"));
+    }
+
+    #[test]
+    fn test_compute_budget() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let user = ctx.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let ix = solana_program::system_instruction::transfer(&user.pubkey(), &to, 1_000_000);
+
+        ctx.set_compute_budget(Some(ComputeBudget::new().with_compute_unit_limit(10)));
+        assert!(!ctx.simulate(ix.clone(), &[&user]).unwrap().is_success());
+        ctx.execute_instruction(ix.clone(), &[&user]).unwrap().assert_failure();
+
+        ctx.set_compute_budget(Some(ComputeBudget::new().with_compute_unit_limit(50_000)));
+        let result = ctx.execute_instruction(ix.clone(), &[&user]).unwrap();
+        result.assert_success();
+        assert!(result.has_log("ComputeBudget111111111111111111111111111111 invoke [1]"));
+
+        // Without a context budget the builder's default applies
+        ctx.set_compute_budget(None);
+        litesvm_utils::set_default_compute_budget(
+            &mut ctx.svm,
+            Some(ComputeBudget::new().with_compute_unit_limit(10)),
+        );
+        assert!(!ctx.simulate(ix.clone(), &[&user]).unwrap().is_success());
+        litesvm_utils::set_default_compute_budget(&mut ctx.svm, None);
+        assert!(ctx.simulate(ix.clone(), &[&user]).unwrap().is_success());
+
+        // The caller's own budget instruction replaces the context's
+        ctx.set_compute_budget(Some(ComputeBudget::new().with_compute_unit_limit(10)));
+        let own_budget = ComputeBudget::new().with_compute_unit_limit(60_000).instructions();
+        let result = ctx
+            .execute_instructions([own_budget, vec![ix]].concat(), &[&user])
+            .unwrap();
+        result.assert_success();
+        result.assert_log_count("ComputeBudget111111111111111111111111111111 invoke [1]", 1);
+
+        // Failures are reported in the caller's numbering, past the context's budget
+        ctx.set_compute_budget(Some(ComputeBudget::new().with_compute_unit_limit(50_000)));
+        let overdraft = solana_program::system_instruction::transfer(&user.pubkey(), &to, u64::MAX);
+        let result = ctx.execute_instructions(vec![overdraft], &[&user]).unwrap();
+        result.assert_error_at(0, "Custom(1)");
+    }
+
+    #[test]
+    fn test_execute_with_payer_pool() {
+        let mut ctx = AnchorContext::new(LiteSVM::new(), Pubkey::new_unique());
+        let pool = PayerPool::create(&mut ctx.svm, 2, 1_000_000_000).unwrap();
+        let payers: Vec<Pubkey> = pool.payers().iter().map(|p| p.pubkey()).collect();
+        ctx.set_payer_pool(Some(pool));
+
+        let user = ctx.create_funded_account(1_000_000_000).unwrap();
+        for _ in 0..2 {
+            let ix = solana_program::system_instruction::transfer(
+                &user.pubkey(),
+                &Pubkey::new_unique(),
+                1_000_000,
+            );
+            ctx.execute_instruction(ix, &[&user]).unwrap().assert_success();
+        }
+
+        // Fees were paid by the pooled payers, not by the signing user
+        ctx.svm.assert_sol_balance(&user.pubkey(), 998_000_000);
+        for payer in payers {
+            assert!(ctx.svm.get_balance(&payer).unwrap() < 1_000_000_000);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+//! Everything a typical Anchor test needs, in one import
+//!
+//! Includes the `litesvm-utils` helper traits alongside the Anchor-specific types.
+//!
+//! ```no_run
+//! use anchor_litesvm::prelude::*;
+//!
+//! let program_id = Pubkey::new_unique();
+//! let mut ctx = AnchorLiteSVM::build_with_program(program_id, &[]);
+//! let user = ctx.svm.create_funded_account(10_000_000_000).unwrap();
+//! ctx.svm.assert_sol_balance(&user.pubkey(), 10_000_000_000);
+//! ```
+
+pub use crate::account::AnchorStateSurgery;
+pub use crate::builder::{AnchorLiteSVM, ProgramTestExt};
+pub use crate::context::AnchorContext;
+pub use crate::errors::ErrorAssertions;
+pub use crate::events::EventHelpers;
+pub use crate::program::Program;
+pub use crate::return_data::ReturnDataHelpers;
+
+pub use litesvm_utils::prelude::{
+    Actor, ActorHelpers, AssertionHelpers, LookupTableHelpers, NftHelpers, ScenarioHelpers,
+    SoftAssertions, StakeHelpers, TestHelpers, Token2022Helpers, TransactionHelpers,
+    TransactionResult,
+};
+
+pub use anchor_lang::{AccountDeserialize, AnchorSerialize};
+pub use litesvm::LiteSVM;
+pub use solana_program::instruction::{AccountMeta, Instruction};
+pub use solana_program::pubkey::Pubkey;
+pub use solana_sdk::signature::{Keypair, Signer};
@@ -3,11 +3,120 @@
 //! This module provides a clean, testing-focused API that removes unnecessary
 //! RPC-layer abstractions like `.request()` and `.remove(0)`.
 
+use crate::idl::{leaf_accounts, well_known_account, Idl};
 use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_lang_idl_spec::{IdlInstruction, IdlInstructionAccount, IdlSeed};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Ways an [`InstructionBuilder`] or [`IdlInstructionBuilder`] can be misused
+///
+/// [`IdlInstructionBuilder`]: crate::IdlInstructionBuilder
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    #[error("No instruction data provided. Call .args() before .instruction()")]
+    MissingArgs,
+
+    #[error("No accounts provided, but the IDL instruction `{0}` expects some")]
+    NoAccounts(String),
+
+    #[error("Account {0} is passed as writable more than once")]
+    DuplicateAccount(Pubkey),
+
+    #[error("Instruction does not match the IDL: {0}")]
+    IdlMismatch(String),
+
+    #[error("No IDL is loaded for program {0}")]
+    MissingIdl(Pubkey),
+
+    #[error("The IDL has no instruction `{0}`")]
+    UnknownInstruction(String),
+
+    #[error("Argument `{0}` is not set")]
+    MissingArg(String),
+
+    #[error("Account `{0}` is not set")]
+    MissingAccount(String),
+
+    #[error("Argument `{0}` is invalid: {1}")]
+    InvalidArg(String, String),
+
+    #[error("{0}")]
+    AccountMismatches(ValidationReport),
+}
+
+/// One difference between an instruction's accounts and their IDL declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountMismatch {
+    /// The IDL declares an account at `index` that wasn't passed
+    Missing { index: usize, name: String },
+    /// An account was passed at `index` beyond those the IDL declares
+    Unexpected { index: usize, pubkey: Pubkey },
+    /// The account has a fixed address or is a PDA, and another account was passed
+    WrongAddress {
+        index: usize,
+        name: String,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// The IDL declares the account writable, but it was passed read-only
+    NotWritable { index: usize, name: String },
+    /// The account was passed writable, but the IDL declares it read-only
+    UnexpectedWritable { index: usize, name: String },
+    /// The IDL declares the account a signer, but it wasn't passed as one
+    NotSigner { index: usize, name: String },
+    /// The account was passed as a signer, but the IDL doesn't declare it one
+    UnexpectedSigner { index: usize, name: String },
+}
+
+impl fmt::Display for AccountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { index, name } => write!(f, "#{} {}: missing", index, name),
+            Self::Unexpected { index, pubkey } => {
+                write!(f, "#{} {}: not declared in the IDL", index, pubkey)
+            }
+            Self::WrongAddress {
+                index,
+                name,
+                expected,
+                actual,
+            } => write!(f, "#{} {}: expected {}, got {}", index, name, expected, actual),
+            Self::NotWritable { index, name } => write!(f, "#{} {}: must be writable", index, name),
+            Self::UnexpectedWritable { index, name } => {
+                write!(f, "#{} {}: writable, but the IDL declares it read-only", index, name)
+            }
+            Self::NotSigner { index, name } => write!(f, "#{} {}: must be a signer", index, name),
+            Self::UnexpectedSigner { index, name } => {
+                write!(f, "#{} {}: signer, but the IDL doesn't declare it one", index, name)
+            }
+        }
+    }
+}
+
+/// Every mismatch [`InstructionBuilder::validate`] found, printed one per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The IDL name of the instruction
+    pub instruction: String,
+    /// The mismatches, in account order
+    pub mismatches: Vec<AccountMismatch>,
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Accounts of `{}` don't match the IDL:", self.instruction)?;
+        for mismatch in &self.mismatches {
+            write!(f, "\n  {}", mismatch)?;
+        }
+        Ok(())
+    }
+}
 
 /// A lightweight Program wrapper for building instructions in tests.
 ///
@@ -79,6 +188,94 @@ impl InstructionBuilder {
         self
     }
 
+    /// Check the accounts against the program's IDL without building the instruction
+    ///
+    /// The instruction is identified by the discriminator, so call `.args()` first.
+    /// Each account is compared with its declaration: fixed addresses and PDAs
+    /// whose seeds use only constants and other accounts must match, as must the
+    /// writable and signer flags. Swapped accounts show up as address or flag
+    /// mismatches. Unset optional accounts (the program ID) are accepted.
+    ///
+    /// # Errors
+    ///
+    /// [`BuildError::AccountMismatches`] lists every mismatch, one per line.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let builder = ctx.program().accounts(...).args(...);
+    /// builder.validate(ctx.idl().unwrap())?;
+    /// let ix = builder.instruction()?;
+    /// ```
+    pub fn validate(&self, idl: &Idl) -> Result<(), BuildError> {
+        if self.data.is_empty() {
+            return Err(BuildError::MissingArgs);
+        }
+        let idl_instruction = find_idl_instruction(idl, &self.data)?;
+        let declared = leaf_accounts(&idl_instruction.accounts);
+        let passed: Vec<(&IdlInstructionAccount, Pubkey)> = declared
+            .iter()
+            .zip(&self.accounts)
+            .map(|(account, meta)| (*account, meta.pubkey))
+            .collect();
+
+        let mut mismatches = Vec::new();
+        for (index, account) in declared.iter().enumerate() {
+            let name = account.name.clone();
+            let Some(meta) = self.accounts.get(index) else {
+                mismatches.push(AccountMismatch::Missing { index, name });
+                continue;
+            };
+            if account.optional && meta.pubkey == self.program_id {
+                continue;
+            }
+
+            let expected = account
+                .address
+                .as_deref()
+                .and_then(|address| Pubkey::from_str(address).ok())
+                .or_else(|| expected_pda(account, &passed, &self.program_id));
+            if let Some(expected) = expected.filter(|expected| *expected != meta.pubkey) {
+                mismatches.push(AccountMismatch::WrongAddress {
+                    index,
+                    name: name.clone(),
+                    expected,
+                    actual: meta.pubkey,
+                });
+            }
+            match (account.writable, meta.is_writable) {
+                (true, false) => mismatches.push(AccountMismatch::NotWritable {
+                    index,
+                    name: name.clone(),
+                }),
+                (false, true) => mismatches.push(AccountMismatch::UnexpectedWritable {
+                    index,
+                    name: name.clone(),
+                }),
+                _ => {}
+            }
+            match (account.signer, meta.is_signer) {
+                (true, false) => mismatches.push(AccountMismatch::NotSigner { index, name }),
+                (false, true) => mismatches.push(AccountMismatch::UnexpectedSigner { index, name }),
+                _ => {}
+            }
+        }
+        for (index, meta) in self.accounts.iter().enumerate().skip(declared.len()) {
+            mismatches.push(AccountMismatch::Unexpected {
+                index,
+                pubkey: meta.pubkey,
+            });
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(BuildError::AccountMismatches(ValidationReport {
+                instruction: idl_instruction.name.clone(),
+                mismatches,
+            }))
+        }
+    }
+
     /// Build and return the instruction.
     ///
     /// This is the final method in the chain that produces the `Instruction`.
@@ -90,9 +287,9 @@ impl InstructionBuilder {
     ///     .args(...)
     ///     .instruction()?;
     /// ```
-    pub fn instruction(self) -> Result<Instruction, Box<dyn std::error::Error>> {
+    pub fn instruction(self) -> Result<Instruction, BuildError> {
         if self.data.is_empty() {
-            return Err("No instruction data provided. Call .args() before .instruction()".into());
+            return Err(BuildError::MissingArgs);
         }
 
         Ok(Instruction {
@@ -101,11 +298,137 @@ impl InstructionBuilder {
             data: self.data,
         })
     }
+
+    /// Build the instruction, checking it against the program's IDL
+    ///
+    /// On top of [`instruction`](Self::instruction), this checks that the
+    /// discriminator names an IDL instruction, that the account count matches its
+    /// declaration, and that no account is passed as writable twice. Program and
+    /// sysvar accounts the IDL declares (`system_program`, `token_program`,
+    /// `associated_token_program`, `rent`, or any account with a fixed address) are
+    /// inserted when left out. Use [`instruction`](Self::instruction) for
+    /// deliberately malformed instructions.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let ix = ctx.program()
+    ///     .accounts(...)
+    ///     .args(...)
+    ///     .instruction_checked(ctx.idl().unwrap())?;
+    /// ```
+    pub fn instruction_checked(self, idl: &Idl) -> Result<Instruction, BuildError> {
+        let instruction = self.instruction()?;
+        let idl_instruction = find_idl_instruction(idl, &instruction.data)?;
+
+        let declared = leaf_accounts(&idl_instruction.accounts);
+        let expected = declared.len();
+        if instruction.accounts.is_empty() && expected > 0 {
+            return Err(BuildError::NoAccounts(idl_instruction.name.clone()));
+        }
+        let mut instruction = instruction;
+        fill_program_accounts(&mut instruction.accounts, &declared);
+        if instruction.accounts.len() != expected {
+            return Err(BuildError::IdlMismatch(format!(
+                "`{}` expects {} accounts, got {}",
+                idl_instruction.name,
+                expected,
+                instruction.accounts.len()
+            )));
+        }
+
+        // Unset optional accounts are all represented by the program ID
+        for (i, meta) in instruction.accounts.iter().enumerate() {
+            let duplicate = instruction.accounts[..i].iter().any(|other| {
+                other.pubkey == meta.pubkey && (other.is_writable || meta.is_writable)
+            });
+            if duplicate && meta.pubkey != instruction.program_id {
+                return Err(BuildError::DuplicateAccount(meta.pubkey));
+            }
+        }
+
+        Ok(instruction)
+    }
+}
+
+/// Find the IDL instruction whose discriminator starts `data`
+fn find_idl_instruction<'i>(idl: &'i Idl, data: &[u8]) -> Result<&'i IdlInstruction, BuildError> {
+    idl.instructions
+        .iter()
+        .find(|ix| data.starts_with(&ix.discriminator))
+        .ok_or_else(|| {
+            BuildError::IdlMismatch(format!(
+                "discriminator {:?} matches no instruction of `{}`",
+                &data[..data.len().min(8)],
+                idl.metadata.name
+            ))
+        })
+}
+
+/// Derive the PDA `account` must be, if its seeds only use constants and other accounts
+///
+/// `accounts` pairs the declared accounts with the pubkeys passed for them.
+fn expected_pda(
+    account: &IdlInstructionAccount,
+    accounts: &[(&IdlInstructionAccount, Pubkey)],
+    program_id: &Pubkey,
+) -> Option<Pubkey> {
+    let pda = account.pda.as_ref()?;
+    let seed_bytes = |seed: &IdlSeed| match seed {
+        IdlSeed::Const(seed) => Some(seed.value.clone()),
+        IdlSeed::Account(seed) => accounts
+            .iter()
+            .find(|(declared, _)| declared.name == seed.path)
+            .map(|(_, pubkey)| pubkey.to_bytes().to_vec()),
+        IdlSeed::Arg(_) => None,
+    };
+    let seeds = pda
+        .seeds
+        .iter()
+        .map(seed_bytes)
+        .collect::<Option<Vec<Vec<u8>>>>()?;
+    let program_id = match &pda.program {
+        Some(seed) => Pubkey::try_from(seed_bytes(seed)?.as_slice()).ok()?,
+        None => *program_id,
+    };
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    Some(Pubkey::find_program_address(&seeds, &program_id).0)
+}
+
+/// Insert the program and sysvar accounts `declared` lists but `accounts` leaves out
+///
+/// Only accounts with a fixed IDL address or a conventional name (see
+/// [`well_known_account`]) are inserted, at their declared position, and only when
+/// they aren't passed anywhere else.
+fn fill_program_accounts(accounts: &mut Vec<AccountMeta>, declared: &[&IdlInstructionAccount]) {
+    let passed: Vec<Pubkey> = accounts.iter().map(|meta| meta.pubkey).collect();
+    let mut missing = declared.len().saturating_sub(accounts.len());
+    for (index, account) in declared.iter().enumerate() {
+        if missing == 0 {
+            break;
+        }
+        let known = account
+            .address
+            .as_deref()
+            .and_then(|address| Pubkey::from_str(address).ok())
+            .or_else(|| well_known_account(&account.name));
+        if let Some(pubkey) = known.filter(|pubkey| !passed.contains(pubkey)) {
+            accounts.insert(
+                index.min(accounts.len()),
+                if account.writable {
+                    AccountMeta::new(pubkey, false)
+                } else {
+                    AccountMeta::new_readonly(pubkey, false)
+                },
+            );
+            missing -= 1;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Program;
+    use super::{AccountMismatch, BuildError, Program};
+    use crate::idl::{parse_idl, tests::TEST_IDL};
     use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
     use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
 
@@ -159,4 +482,198 @@ mod tests {
         assert_eq!(ix.accounts.len(), 2);
         assert!(ix.data.len() > 8);
     }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    struct MakeArgs {
+        seed: u64,
+    }
+
+    impl anchor_lang::Discriminator for MakeArgs {
+        const DISCRIMINATOR: &'static [u8] = &[138, 227, 232, 77, 223, 166, 96, 197];
+    }
+
+    impl InstructionData for MakeArgs {
+        fn data(&self) -> Vec<u8> {
+            let mut data = Vec::new();
+            data.extend_from_slice(Self::DISCRIMINATOR);
+            self.serialize(&mut data).unwrap();
+            data
+        }
+    }
+
+    struct NoAccounts;
+
+    impl ToAccountMetas for NoAccounts {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_instruction_missing_args() {
+        let program = Program::new(Pubkey::new_unique());
+        let user = Pubkey::new_unique();
+        let result = program
+            .accounts(TestAccounts { user, account: user })
+            .instruction();
+        assert_eq!(result.unwrap_err(), BuildError::MissingArgs);
+    }
+
+    #[test]
+    fn test_instruction_checked() {
+        let idl = parse_idl(TEST_IDL).unwrap();
+        let program = Program::new(Pubkey::new_unique());
+        let maker = Pubkey::new_unique();
+        let system_program = solana_program::system_program::id();
+
+        let ix = program
+            .accounts(TestAccounts { user: maker, account: system_program })
+            .args(MakeArgs { seed: 1 })
+            .instruction_checked(&idl);
+        assert!(ix.is_ok());
+
+        let result = program
+            .accounts(TestAccounts { user: maker, account: maker })
+            .args(MakeArgs { seed: 1 })
+            .instruction_checked(&idl);
+        assert_eq!(result.unwrap_err(), BuildError::DuplicateAccount(maker));
+
+        let result = program
+            .accounts(NoAccounts)
+            .args(MakeArgs { seed: 1 })
+            .instruction_checked(&idl);
+        assert_eq!(result.unwrap_err(), BuildError::NoAccounts("make".to_string()));
+
+        let result = program
+            .accounts(TestAccounts { user: maker, account: system_program })
+            .args(TestArgs { amount: 1 })
+            .instruction_checked(&idl);
+        assert!(matches!(result, Err(BuildError::IdlMismatch(_))));
+    }
+
+    #[test]
+    fn test_validate() {
+        let idl = parse_idl(
+            r#"{
+                "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+                "metadata": { "name": "escrow", "version": "0.1.0", "spec": "0.1.0" },
+                "instructions": [{
+                    "name": "make",
+                    "discriminator": [138, 227, 232, 77, 223, 166, 96, 197],
+                    "accounts": [
+                        { "name": "maker", "writable": true, "signer": true },
+                        {
+                            "name": "escrow",
+                            "writable": true,
+                            "pda": { "seeds": [
+                                { "kind": "const", "value": [101, 115, 99, 114, 111, 119] },
+                                { "kind": "account", "path": "maker" }
+                            ] }
+                        },
+                        { "name": "system_program", "address": "11111111111111111111111111111111" }
+                    ],
+                    "args": [{ "name": "seed", "type": "u64" }]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let program_id = Pubkey::new_unique();
+        let program = Program::new(program_id);
+        let maker = Pubkey::new_unique();
+        let (escrow, _) = Pubkey::find_program_address(&[b"escrow", maker.as_ref()], &program_id);
+        let system_program = solana_program::system_program::id();
+
+        let builder = program
+            .accounts(vec![
+                AccountMeta::new(maker, true),
+                AccountMeta::new(escrow, false),
+                AccountMeta::new_readonly(system_program, false),
+            ])
+            .args(MakeArgs { seed: 1 });
+        assert_eq!(builder.validate(&idl), Ok(()));
+
+        // Escrow and system program swapped, and maker not signing
+        let builder = program
+            .accounts(vec![
+                AccountMeta::new(maker, false),
+                AccountMeta::new_readonly(system_program, false),
+                AccountMeta::new(escrow, false),
+            ])
+            .args(MakeArgs { seed: 1 });
+        let Err(BuildError::AccountMismatches(report)) = builder.validate(&idl) else {
+            panic!("expected account mismatches");
+        };
+        assert_eq!(report.instruction, "make");
+        assert_eq!(
+            report.mismatches,
+            vec![
+                AccountMismatch::NotSigner { index: 0, name: "maker".to_string() },
+                AccountMismatch::WrongAddress {
+                    index: 1,
+                    name: "escrow".to_string(),
+                    expected: escrow,
+                    actual: system_program,
+                },
+                AccountMismatch::NotWritable { index: 1, name: "escrow".to_string() },
+                AccountMismatch::WrongAddress {
+                    index: 2,
+                    name: "system_program".to_string(),
+                    expected: system_program,
+                    actual: escrow,
+                },
+                AccountMismatch::UnexpectedWritable {
+                    index: 2,
+                    name: "system_program".to_string(),
+                },
+            ]
+        );
+        assert!(report.to_string().starts_with(
+            "Accounts of `make` don't match the IDL:\n  #0 maker: must be a signer\n"
+        ));
+
+        let builder = program
+            .accounts(vec![AccountMeta::new(maker, true)])
+            .args(MakeArgs { seed: 1 });
+        let Err(BuildError::AccountMismatches(report)) = builder.validate(&idl) else {
+            panic!("expected account mismatches");
+        };
+        assert_eq!(
+            report.mismatches[0],
+            AccountMismatch::Missing { index: 1, name: "escrow".to_string() }
+        );
+        assert_eq!(
+            program.accounts(NoAccounts).validate(&idl),
+            Err(BuildError::MissingArgs)
+        );
+    }
+
+    #[test]
+    fn test_instruction_checked_fills_program_accounts() {
+        let idl = parse_idl(TEST_IDL).unwrap();
+        let program = Program::new(Pubkey::new_unique());
+        let maker = Pubkey::new_unique();
+
+        let ix = program
+            .accounts(vec![AccountMeta::new(maker, true)])
+            .args(MakeArgs { seed: 1 })
+            .instruction_checked(&idl)
+            .unwrap();
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(maker, true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ]
+        );
+
+        // Passed in the wrong place, the program isn't added a second time
+        let result = program
+            .accounts(vec![AccountMeta::new_readonly(
+                solana_program::system_program::id(),
+                false,
+            )])
+            .args(MakeArgs { seed: 1 })
+            .instruction_checked(&idl);
+        assert!(matches!(result, Err(BuildError::IdlMismatch(_))));
+    }
 }
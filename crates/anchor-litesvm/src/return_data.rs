@@ -0,0 +1,95 @@
+//! Typed access to program return data
+//!
+//! Anchor instructions that return a value (`-> Result<T>`) pass it back with
+//! `set_return_data`, Borsh-encoded. [`ReturnDataHelpers::return_data_as`] decodes
+//! it from a [`TransactionResult`].
+
+use anchor_lang::AnchorDeserialize;
+use litesvm_utils::TransactionResult;
+
+/// Return data error types
+#[derive(Debug, thiserror::Error)]
+pub enum ReturnDataError {
+    #[error("Transaction has no return data")]
+    NoReturnData,
+
+    #[error("Failed to deserialize return data: {0}")]
+    DeserializationError(String),
+}
+
+/// Extension trait for TransactionResult to decode return data
+pub trait ReturnDataHelpers {
+    /// Deserialize the return data of the transaction as `T`
+    ///
+    /// The whole return data must decode as `T`; leftover bytes are an error.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // pub fn quote(ctx: Context<Quote>, amount_in: u64) -> Result<u64>
+    /// let result = ctx.execute_instruction(quote_ix, &[&user]).unwrap();
+    /// let amount_out: u64 = result.return_data_as().unwrap();
+    /// ```
+    fn return_data_as<T>(&self) -> Result<T, ReturnDataError>
+    where
+        T: AnchorDeserialize;
+}
+
+impl ReturnDataHelpers for TransactionResult {
+    fn return_data_as<T>(&self) -> Result<T, ReturnDataError>
+    where
+        T: AnchorDeserialize,
+    {
+        if self.return_data_program().is_none() {
+            return Err(ReturnDataError::NoReturnData);
+        }
+        T::try_from_slice(self.return_data())
+            .map_err(|e| ReturnDataError::DeserializationError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::{borsh, AnchorSerialize};
+    use litesvm::types::TransactionMetadata;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk::transaction_context::TransactionReturnData;
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+    struct Quote {
+        amount_out: u64,
+        fee: u16,
+    }
+
+    fn result_with_return_data(data: Vec<u8>) -> TransactionResult {
+        let meta = TransactionMetadata {
+            return_data: TransactionReturnData {
+                program_id: Pubkey::new_unique(),
+                data,
+            },
+            ..Default::default()
+        };
+        TransactionResult::new(meta, None)
+    }
+
+    #[test]
+    fn test_return_data_as() {
+        let quote = Quote {
+            amount_out: 990,
+            fee: 30,
+        };
+        let result = result_with_return_data(quote.try_to_vec().unwrap());
+        assert_eq!(result.return_data_as::<Quote>().unwrap(), quote);
+        assert!(matches!(
+            result.return_data_as::<u64>(),
+            Err(ReturnDataError::DeserializationError(_))
+        ));
+
+        let empty = TransactionResult::new(TransactionMetadata::default(), None);
+        assert!(matches!(
+            empty.return_data_as::<u64>(),
+            Err(ReturnDataError::NoReturnData)
+        ));
+    }
+}
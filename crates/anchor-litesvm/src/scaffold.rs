@@ -0,0 +1,348 @@
+//! Test suite scaffolding generated from an IDL
+//!
+//! [`generate_test_module`] turns a program's IDL into a ready-to-edit Rust test
+//! module: a setup function, funded actors, token mints, PDA derivations and one
+//! test skeleton per instruction (e.g. `make`/`take` for an escrow). Values the
+//! IDL can't know (amounts, seeds) are left as `Default::default()` to fill in.
+
+use crate::idl::{Idl, IdlError};
+use anchor_lang_idl_spec::{
+    IdlArrayLen, IdlInstruction, IdlInstructionAccountItem, IdlSeed, IdlType,
+};
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Lamports given to every generated actor
+const ACTOR_LAMPORTS: u64 = 10_000_000_000;
+
+/// Generate a test module for the program described by `idl`
+///
+/// The generated code uses `declare_program!` client types, so the IDL must also
+/// be available under `idls/` as `declare_program!` expects.
+///
+/// # Example
+///
+/// ```ignore
+/// let idl = load_idl("target/idl/escrow.json")?;
+/// std::fs::write("tests/escrow.rs", generate_test_module(&idl))?;
+/// ```
+pub fn generate_test_module(idl: &Idl) -> String {
+    let program = &idl.metadata.name;
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "//! Generated test skeleton for the `{}` program",
+        program
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "use anchor_litesvm::{{AnchorContext, AnchorLiteSVM, AssertionHelpers, Pubkey, Signer, TestHelpers}};"
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "anchor_lang::declare_program!({});", program).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "fn setup() -> AnchorContext {{").unwrap();
+    writeln!(out, "    AnchorLiteSVM::build_with_program(").unwrap();
+    writeln!(out, "        {}::ID,", program).unwrap();
+    writeln!(
+        out,
+        "        include_bytes!(\"../target/deploy/{}.so\"),",
+        program
+    )
+    .unwrap();
+    writeln!(out, "    )").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    for instruction in &idl.instructions {
+        writeln!(out).unwrap();
+        write_instruction_test(&mut out, program, instruction);
+    }
+
+    out
+}
+
+/// Generate a test module and write it to `path`
+pub fn write_test_module(idl: &Idl, path: impl AsRef<Path>) -> Result<(), IdlError> {
+    let path = path.as_ref();
+    std::fs::write(path, generate_test_module(idl)).map_err(|e| IdlError::Io(path.to_path_buf(), e))
+}
+
+fn write_instruction_test(out: &mut String, program: &str, instruction: &IdlInstruction) {
+    let type_name = to_pascal_case(&instruction.name);
+
+    writeln!(out, "#[test]").unwrap();
+    writeln!(out, "fn test_{}() {{", instruction.name).unwrap();
+    writeln!(out, "    let mut ctx = setup();").unwrap();
+
+    // Arguments first, since PDA seeds may reference them
+    if !instruction.args.is_empty() {
+        writeln!(out).unwrap();
+        writeln!(out, "    // TODO: choose argument values").unwrap();
+        for arg in &instruction.args {
+            writeln!(
+                out,
+                "    let {}: {} = Default::default();",
+                arg.name,
+                rust_type(program, &arg.ty)
+            )
+            .unwrap();
+        }
+    }
+
+    let mut signers = Vec::new();
+    let mut fields = Vec::new();
+    let mut needs_mint_authority = true;
+    writeln!(out).unwrap();
+    for item in &instruction.accounts {
+        let account = match item {
+            IdlInstructionAccountItem::Single(account) => account,
+            IdlInstructionAccountItem::Composite(group) => {
+                writeln!(
+                    out,
+                    "    // TODO: composite account group `{}` is not scaffolded",
+                    group.name
+                )
+                .unwrap();
+                continue;
+            }
+        };
+        let name = &account.name;
+
+        let value = if account.signer {
+            writeln!(
+                out,
+                "    let {} = ctx.svm.create_funded_account({}).unwrap();",
+                name, ACTOR_LAMPORTS
+            )
+            .unwrap();
+            signers.push(name.clone());
+            format!("{}.pubkey()", name)
+        } else if let Some(address) = &account.address {
+            writeln!(
+                out,
+                "    let {} = Pubkey::from_str_const(\"{}\");",
+                name, address
+            )
+            .unwrap();
+            name.clone()
+        } else if let Some(pda) = account.pda.as_ref().filter(|pda| pda.program.is_none()) {
+            let seeds: Vec<String> = pda
+                .seeds
+                .iter()
+                .map(|seed| render_seed(seed, instruction, &signers))
+                .collect();
+            writeln!(
+                out,
+                "    let {} = ctx.svm.get_pda(&[{}], &{}::ID);",
+                name,
+                seeds.join(", "),
+                program
+            )
+            .unwrap();
+            name.clone()
+        } else if name.contains("mint") {
+            if needs_mint_authority {
+                writeln!(
+                    out,
+                    "    let mint_authority = ctx.svm.create_funded_account({}).unwrap();",
+                    ACTOR_LAMPORTS
+                )
+                .unwrap();
+                needs_mint_authority = false;
+            }
+            writeln!(
+                out,
+                "    let {} = ctx.svm.create_token_mint(&mint_authority, 6).unwrap().pubkey();",
+                name
+            )
+            .unwrap();
+            name.clone()
+        } else {
+            writeln!(out, "    let {} = Pubkey::new_unique(); // TODO", name).unwrap();
+            name.clone()
+        };
+
+        let value = if account.optional {
+            format!("Some({})", value)
+        } else {
+            value
+        };
+        fields.push(if value == *name {
+            name.clone()
+        } else {
+            format!("{}: {}", name, value)
+        });
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "    let ix = ctx").unwrap();
+    writeln!(out, "        .program()").unwrap();
+    writeln!(
+        out,
+        "        .accounts({}::client::accounts::{} {{",
+        program, type_name
+    )
+    .unwrap();
+    for field in &fields {
+        writeln!(out, "            {},", field).unwrap();
+    }
+    writeln!(out, "        }})").unwrap();
+    writeln!(
+        out,
+        "        .args({}::client::args::{} {{",
+        program, type_name
+    )
+    .unwrap();
+    for arg in &instruction.args {
+        writeln!(out, "            {},", arg.name).unwrap();
+    }
+    writeln!(out, "        }})").unwrap();
+    writeln!(out, "        .instruction()").unwrap();
+    writeln!(out, "        .unwrap();").unwrap();
+    writeln!(out).unwrap();
+
+    let signer_refs: Vec<String> = signers.iter().map(|s| format!("&{}", s)).collect();
+    writeln!(
+        out,
+        "    let result = ctx.execute_instruction(ix, &[{}]).unwrap();",
+        signer_refs.join(", ")
+    )
+    .unwrap();
+    writeln!(out, "    result.assert_success();").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    // TODO: assert on the resulting state").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn render_seed(seed: &IdlSeed, instruction: &IdlInstruction, signers: &[String]) -> String {
+    match seed {
+        IdlSeed::Const(constant) => match std::str::from_utf8(&constant.value) {
+            Ok(text) if text.chars().all(|c| c.is_ascii_graphic()) => format!("b\"{}\"", text),
+            _ => format!("&{:?}", constant.value),
+        },
+        IdlSeed::Arg(arg) => {
+            let ty = instruction
+                .args
+                .iter()
+                .find(|a| a.name == arg.path)
+                .map(|a| &a.ty);
+            match ty {
+                Some(IdlType::String) => format!("{}.as_bytes()", arg.path),
+                Some(IdlType::Pubkey) | Some(IdlType::Bytes) => format!("{}.as_ref()", arg.path),
+                _ => format!("{}.to_le_bytes().as_ref()", arg.path),
+            }
+        }
+        // Signers are keypairs in the generated code, everything else is a pubkey
+        IdlSeed::Account(account) if signers.contains(&account.path) => {
+            format!("{}.pubkey().as_ref()", account.path)
+        }
+        IdlSeed::Account(account) => format!("{}.as_ref()", account.path),
+    }
+}
+
+fn rust_type(program: &str, ty: &IdlType) -> String {
+    match ty {
+        IdlType::Bool => "bool".to_string(),
+        IdlType::U8 => "u8".to_string(),
+        IdlType::I8 => "i8".to_string(),
+        IdlType::U16 => "u16".to_string(),
+        IdlType::I16 => "i16".to_string(),
+        IdlType::U32 => "u32".to_string(),
+        IdlType::I32 => "i32".to_string(),
+        IdlType::F32 => "f32".to_string(),
+        IdlType::U64 => "u64".to_string(),
+        IdlType::I64 => "i64".to_string(),
+        IdlType::F64 => "f64".to_string(),
+        IdlType::U128 => "u128".to_string(),
+        IdlType::I128 => "i128".to_string(),
+        IdlType::Bytes => "Vec<u8>".to_string(),
+        IdlType::String => "String".to_string(),
+        IdlType::Pubkey => "Pubkey".to_string(),
+        IdlType::Option(inner) => format!("Option<{}>", rust_type(program, inner)),
+        IdlType::Vec(inner) => format!("Vec<{}>", rust_type(program, inner)),
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => {
+            format!("[{}; {}]", rust_type(program, inner), len)
+        }
+        IdlType::Defined { name, .. } => format!("{}::types::{}", program, name),
+        _ => "_ /* TODO: unsupported IDL type */".to_string(),
+    }
+}
+
+pub(crate) fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::idl::parse_idl;
+
+    const ESCROW_IDL: &str = r#"{
+        "address": "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+        "metadata": { "name": "escrow", "version": "0.1.0", "spec": "0.1.0" },
+        "instructions": [
+            {
+                "name": "make",
+                "discriminator": [138, 227, 232, 77, 223, 166, 96, 197],
+                "accounts": [
+                    { "name": "maker", "writable": true, "signer": true },
+                    { "name": "mint_a" },
+                    {
+                        "name": "escrow",
+                        "writable": true,
+                        "pda": {
+                            "seeds": [
+                                { "kind": "const", "value": [101, 115, 99, 114, 111, 119] },
+                                { "kind": "account", "path": "maker" },
+                                { "kind": "arg", "path": "seed" }
+                            ]
+                        }
+                    },
+                    { "name": "system_program", "address": "11111111111111111111111111111111" }
+                ],
+                "args": [
+                    { "name": "seed", "type": "u64" },
+                    { "name": "receive", "type": "u64" }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_generate_test_module() {
+        let idl = parse_idl(ESCROW_IDL).unwrap();
+        let module = generate_test_module(&idl);
+
+        assert!(module.contains("anchor_lang::declare_program!(escrow);"));
+        assert!(module.contains("fn test_make() {"));
+        assert!(module.contains("let seed: u64 = Default::default();"));
+        assert!(module.contains("let maker = ctx.svm.create_funded_account(10000000000).unwrap();"));
+        assert!(module.contains("let mint_a = ctx.svm.create_token_mint(&mint_authority, 6)"));
+        assert!(module.contains(
+            "let escrow = ctx.svm.get_pda(&[b\"escrow\", maker.pubkey().as_ref(), seed.to_le_bytes().as_ref()], &escrow::ID);"
+        ));
+        assert!(module.contains("Pubkey::from_str_const(\"11111111111111111111111111111111\")"));
+        assert!(module.contains(".accounts(escrow::client::accounts::Make {"));
+        assert!(module.contains("maker: maker.pubkey(),"));
+        assert!(module.contains("ctx.execute_instruction(ix, &[&maker])"));
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("make"), "Make");
+        assert_eq!(to_pascal_case("make_offer"), "MakeOffer");
+    }
+}
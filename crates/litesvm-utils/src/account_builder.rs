@@ -0,0 +1,142 @@
+//! Fluent builder for writing raw accounts
+//!
+//! Injecting synthetic state otherwise means filling in an [`Account`] struct by
+//! hand and calling `set_account`. [`AccountBuilder`] sets each field by name and
+//! defaults the rest: a rent-exempt balance for the data, the system program as
+//! owner, and no data.
+//!
+//! ```ignore
+//! svm.account_builder(config)
+//!     .owner(&program_id)
+//!     .data(config_bytes)
+//!     .create()?;
+//! ```
+
+use crate::surgery::StateSurgery;
+use litesvm::LiteSVM;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::error::Error;
+
+/// Builder for an account written directly into the VM
+///
+/// Created by [`TestHelpers::account_builder`]. Nothing is written until
+/// [`create`](Self::create) is called; an existing account at the address is
+/// replaced.
+///
+/// [`TestHelpers::account_builder`]: crate::TestHelpers::account_builder
+pub struct AccountBuilder<'a> {
+    svm: &'a mut LiteSVM,
+    pubkey: Pubkey,
+    lamports: Option<u64>,
+    owner: Pubkey,
+    data: Vec<u8>,
+    executable: bool,
+}
+
+impl<'a> AccountBuilder<'a> {
+    pub(crate) fn new(svm: &'a mut LiteSVM, pubkey: Pubkey) -> Self {
+        Self {
+            svm,
+            pubkey,
+            lamports: None,
+            owner: solana_program::system_program::id(),
+            data: Vec::new(),
+            executable: false,
+        }
+    }
+
+    /// Set the balance, instead of the rent-exempt minimum for the data
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = Some(lamports);
+        self
+    }
+
+    /// Set the owning program (default: the system program)
+    pub fn owner(mut self, owner: &Pubkey) -> Self {
+        self.owner = *owner;
+        self
+    }
+
+    /// Set the account data (default: empty)
+    pub fn data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    /// Set the data to `space` zero bytes
+    pub fn space(mut self, space: usize) -> Self {
+        self.data = vec![0; space];
+        self
+    }
+
+    /// Mark the account as executable (default: `false`)
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.executable = executable;
+        self
+    }
+
+    /// Write the account and return its address
+    ///
+    /// The write is recorded in the [`surgery_report`](crate::surgery_report) as
+    /// `account_builder`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if LiteSVM rejects the account
+    #[track_caller]
+    pub fn create(self) -> Result<Pubkey, Box<dyn Error>> {
+        let lamports = self
+            .lamports
+            .unwrap_or_else(|| self.svm.minimum_balance_for_rent_exemption(self.data.len()));
+        let account = Account {
+            lamports,
+            data: self.data,
+            owner: self.owner,
+            executable: self.executable,
+            rent_epoch: 0,
+        };
+        StateSurgery::new(self.svm).write("account_builder", &self.pubkey, account)?;
+        Ok(self.pubkey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::TestHelpers;
+    use litesvm::LiteSVM;
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_account_builder() {
+        let mut svm = LiteSVM::new();
+        let program = Pubkey::new_unique();
+
+        let address = Pubkey::new_unique();
+        let created = svm
+            .account_builder(address)
+            .lamports(5_000_000)
+            .owner(&program)
+            .data([1u8, 2, 3])
+            .executable(false)
+            .create()
+            .unwrap();
+        assert_eq!(created, address);
+        let account = svm.get_account(&address).unwrap();
+        assert_eq!(account.lamports, 5_000_000);
+        assert_eq!(account.owner, program);
+        assert_eq!(account.data, vec![1, 2, 3]);
+        assert!(!account.executable);
+
+        // Defaults: rent-exempt balance, system-owned
+        let sized = Pubkey::new_unique();
+        svm.account_builder(sized).space(100).create().unwrap();
+        let account = svm.get_account(&sized).unwrap();
+        assert_eq!(
+            account.lamports,
+            svm.minimum_balance_for_rent_exemption(100)
+        );
+        assert_eq!(account.owner, solana_program::system_program::id());
+        assert_eq!(account.data, vec![0; 100]);
+    }
+}
@@ -0,0 +1,227 @@
+//! Before/after state of the accounts a transaction writes
+//!
+//! Recording is off by default because it reads every writable account twice per
+//! transaction. Turn it on for a VM with [`record_account_diffs`] (or
+//! [`LiteSVMBuilder::with_account_diffs`]) and every transaction sent through the
+//! helpers on that VM carries its diffs in [`TransactionResult::account_diffs`].
+//!
+//! ```ignore
+//! record_account_diffs(&mut svm, true);
+//! let result = svm.send_instruction(ix, &[&user])?;
+//! for diff in result.account_diffs() {
+//!     println!("{}", diff); // "7xKX...: lamports 2039280 -> 0 (-2039280), data 165 -> 0 bytes"
+//! }
+//! ```
+//!
+//! [`LiteSVMBuilder::with_account_diffs`]: crate::LiteSVMBuilder::with_account_diffs
+//! [`TransactionResult::account_diffs`]: crate::TransactionResult::account_diffs
+
+use crate::vm_state::{vm_state, vm_state_mut};
+use litesvm::LiteSVM;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use solana_sdk::message::VersionedMessage;
+use std::fmt;
+
+/// Turn account diff recording on or off for `svm`
+pub fn record_account_diffs(svm: &mut LiteSVM, enabled: bool) {
+    vm_state_mut(svm, |state| state.record_account_diffs = enabled);
+}
+
+/// Whether account diffs are being recorded for `svm`
+pub fn recording_account_diffs(svm: &LiteSVM) -> bool {
+    vm_state(svm, |state| state.record_account_diffs)
+}
+
+/// How one writable account changed across a transaction
+///
+/// `before` and `after` are `None` when the account didn't exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The account's address
+    pub pubkey: Pubkey,
+    /// State before the transaction
+    pub before: Option<Account>,
+    /// State after the transaction
+    pub after: Option<Account>,
+}
+
+impl AccountDiff {
+    /// Lamports before the transaction, 0 if the account didn't exist
+    pub fn lamports_before(&self) -> u64 {
+        self.before.as_ref().map_or(0, |account| account.lamports)
+    }
+
+    /// Lamports after the transaction, 0 if the account doesn't exist
+    pub fn lamports_after(&self) -> u64 {
+        self.after.as_ref().map_or(0, |account| account.lamports)
+    }
+
+    /// Signed lamport change
+    pub fn lamport_change(&self) -> i128 {
+        self.lamports_after() as i128 - self.lamports_before() as i128
+    }
+
+    /// Whether the account data (including its length) changed
+    pub fn data_changed(&self) -> bool {
+        self.data_before() != self.data_after()
+    }
+
+    /// Byte offsets, within the shorter of the two buffers, whose values changed
+    pub fn changed_offsets(&self) -> Vec<usize> {
+        self.data_before()
+            .iter()
+            .zip(self.data_after())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(offset, _)| offset)
+            .collect()
+    }
+
+    /// Whether anything about the account changed
+    pub fn is_changed(&self) -> bool {
+        self.before != self.after
+    }
+
+    fn data_before(&self) -> &[u8] {
+        self.before.as_ref().map_or(&[], |account| &account.data)
+    }
+
+    fn data_after(&self) -> &[u8] {
+        self.after.as_ref().map_or(&[], |account| &account.data)
+    }
+}
+
+/// e.g. `7xKX...: lamports 2039280 -> 0 (-2039280), data 165 -> 0 bytes`
+impl fmt::Display for AccountDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.pubkey)?;
+        if !self.is_changed() {
+            return write!(f, "unchanged");
+        }
+        write!(
+            f,
+            "lamports {} -> {} ({:+})",
+            self.lamports_before(),
+            self.lamports_after(),
+            self.lamport_change()
+        )?;
+        if self.data_before().len() != self.data_after().len() {
+            write!(
+                f,
+                ", data {} -> {} bytes",
+                self.data_before().len(),
+                self.data_after().len()
+            )?;
+        } else if self.data_changed() {
+            write!(f, ", {} data byte(s) changed", self.changed_offsets().len())?;
+        }
+        if let (Some(before), Some(after)) = (&self.before, &self.after) {
+            if before.owner != after.owner {
+                write!(f, ", owner {} -> {}", before.owner, after.owner)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the writable static accounts of `message`, if recording is on
+///
+/// Pass the result to [`diff_accounts`] after executing the transaction.
+pub fn capture_writable_accounts(
+    svm: &LiteSVM,
+    message: &VersionedMessage,
+) -> Vec<(Pubkey, Option<Account>)> {
+    if !recording_account_diffs(svm) {
+        return Vec::new();
+    }
+    message
+        .static_account_keys()
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_maybe_writable(*index, None))
+        .map(|(_, pubkey)| (*pubkey, existing_account(svm, pubkey)))
+        .collect()
+}
+
+/// Compare accounts captured by [`capture_writable_accounts`] with their current state
+pub fn diff_accounts(svm: &LiteSVM, before: Vec<(Pubkey, Option<Account>)>) -> Vec<AccountDiff> {
+    before
+        .into_iter()
+        .map(|(pubkey, before)| AccountDiff {
+            pubkey,
+            after: existing_account(svm, &pubkey),
+            before,
+        })
+        .collect()
+}
+
+/// LiteSVM keeps closed accounts around with zero lamports, so treat those as missing
+fn existing_account(svm: &LiteSVM, pubkey: &Pubkey) -> Option<Account> {
+    svm.get_account(pubkey)
+        .filter(|account| account.lamports > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use crate::transaction::TransactionHelpers;
+    use solana_program::system_instruction;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_account_diffs() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        let ix = system_instruction::transfer(&from.pubkey(), &to, 1_000_000);
+        let result = svm.send_instruction(ix, &[&from]).unwrap();
+        assert!(result.account_diffs().is_empty());
+
+        record_account_diffs(&mut svm, true);
+        let ix = system_instruction::transfer(&from.pubkey(), &to, 2_000_000);
+        let result = svm.send_instruction(ix, &[&from]).unwrap();
+        assert!(!recording_account_diffs(&LiteSVM::new()));
+        record_account_diffs(&mut svm, false);
+
+        let diffs = result.account_diffs();
+        let keys: Vec<Pubkey> = diffs.iter().map(|diff| diff.pubkey).collect();
+        assert_eq!(keys, vec![from.pubkey(), to]);
+        assert_eq!(diffs[0].lamport_change(), -2_005_000);
+        assert_eq!(diffs[1].lamport_change(), 2_000_000);
+        assert!(!diffs[1].data_changed());
+        assert_eq!(
+            diffs[1].to_string(),
+            format!("{}: lamports 1000000 -> 3000000 (+2000000)", to)
+        );
+        assert!(result.to_pretty_string().contains("Account Changes:"));
+    }
+
+    #[test]
+    fn test_account_diff_data() {
+        let owner = Pubkey::new_unique();
+        let mut before = Account::new(100, 4, &owner);
+        before.data = vec![1, 2, 3, 4];
+        let mut after = before.clone();
+        after.data[2] = 9;
+
+        let diff = AccountDiff {
+            pubkey: Pubkey::new_unique(),
+            before: Some(before.clone()),
+            after: Some(after),
+        };
+        assert!(diff.data_changed());
+        assert_eq!(diff.changed_offsets(), vec![2]);
+        assert!(diff.to_string().ends_with("(+0), 1 data byte(s) changed"));
+
+        let closed = AccountDiff {
+            pubkey: Pubkey::new_unique(),
+            before: Some(before),
+            after: None,
+        };
+        assert_eq!(closed.lamport_change(), -100);
+        assert!(closed.to_string().ends_with("data 4 -> 0 bytes"));
+    }
+}
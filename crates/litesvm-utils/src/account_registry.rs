@@ -0,0 +1,288 @@
+//! Registry of accounts the helpers have touched
+//!
+//! LiteSVM doesn't expose its account store, so the helpers record, per VM, every
+//! address they create, fund or send a transaction with, including addresses a
+//! versioned transaction loads through lookup tables. [`TestHelpers::iter_accounts`]
+//! walks these addresses and yields the ones that still exist in the queried VM.
+//!
+//! The accounts holding the helpers' own per-VM state are never yielded, even if
+//! tracked.
+//!
+//! The registry is not a complete index: accounts written with raw
+//! `LiteSVM::set_account`, or only touched by transactions sent with raw
+//! `LiteSVM::send_transaction`, are invisible until registered with
+//! [`track_accounts`] or used in a helper-sent transaction.
+//!
+//! [`TestHelpers::get_program_accounts_with_filters`] layers `getProgramAccounts`
+//! style [`AccountFilter`]s on top of the same walk.
+//!
+//! [`TestHelpers::iter_accounts`]: crate::TestHelpers::iter_accounts
+//! [`TestHelpers::get_program_accounts_with_filters`]: crate::TestHelpers::get_program_accounts_with_filters
+
+use crate::vm_state::{is_vm_state_address, track, tracked_accounts};
+use litesvm::LiteSVM;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use solana_sdk::message::VersionedMessage;
+
+/// Register addresses of `svm` so [`TestHelpers::iter_accounts`] can find them
+///
+/// The helpers call this for every account they touch; call it yourself for
+/// accounts written through `LiteSVM` directly.
+///
+/// [`TestHelpers::iter_accounts`]: crate::TestHelpers::iter_accounts
+pub fn track_accounts(svm: &mut LiteSVM, pubkeys: impl IntoIterator<Item = Pubkey>) {
+    track(svm, pubkeys);
+}
+
+/// Every account `message` loads, in account index order
+///
+/// Static keys come first, then the writable and read-only addresses of each lookup
+/// table, as the runtime orders them. Tables that can't be read from `svm` are
+/// skipped, along with any index past their end.
+pub(crate) fn message_account_keys(svm: &LiteSVM, message: &VersionedMessage) -> Vec<Pubkey> {
+    let mut keys = message.static_account_keys().to_vec();
+    let Some(lookups) = message.address_table_lookups() else {
+        return keys;
+    };
+    let tables: Vec<Option<Vec<Pubkey>>> = lookups
+        .iter()
+        .map(|lookup| {
+            let account = svm.get_account(&lookup.account_key)?;
+            let table = AddressLookupTable::deserialize(&account.data).ok()?;
+            Some(table.addresses.to_vec())
+        })
+        .collect();
+    let mut readonly = Vec::new();
+    for (lookup, table) in lookups.iter().zip(&tables) {
+        let Some(table) = table else { continue };
+        let resolve = |index: &u8| table.get(*index as usize).copied();
+        keys.extend(lookup.writable_indexes.iter().filter_map(resolve));
+        readonly.extend(lookup.readonly_indexes.iter().filter_map(resolve));
+    }
+    keys.extend(readonly);
+    keys
+}
+
+/// A `getProgramAccounts` filter on account data
+///
+/// Matches the RPC semantics: every filter passed to a query must match, and a
+/// `memcmp` that runs past the end of the data doesn't match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountFilter {
+    /// Data holds `bytes` starting at `offset`
+    Memcmp { offset: usize, bytes: Vec<u8> },
+    /// Data is exactly this many bytes long
+    DataSize(u64),
+}
+
+impl AccountFilter {
+    /// Match accounts whose data holds `bytes` at `offset`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Positions owned by `owner`, after the 8-byte Anchor discriminator
+    /// AccountFilter::memcmp(8, owner.as_ref())
+    /// ```
+    pub fn memcmp(offset: usize, bytes: impl AsRef<[u8]>) -> Self {
+        AccountFilter::Memcmp {
+            offset,
+            bytes: bytes.as_ref().to_vec(),
+        }
+    }
+
+    /// Match accounts whose data is exactly `size` bytes long
+    pub fn data_size(size: u64) -> Self {
+        AccountFilter::DataSize(size)
+    }
+
+    /// Check whether account data passes the filter
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match self {
+            AccountFilter::Memcmp { offset, bytes } => offset
+                .checked_add(bytes.len())
+                .and_then(|end| data.get(*offset..end))
+                .is_some_and(|window| window == bytes.as_slice()),
+            AccountFilter::DataSize(size) => data.len() as u64 == *size,
+        }
+    }
+}
+
+/// Iterator over existing tracked accounts, in ascending address order
+///
+/// Created by [`TestHelpers::iter_accounts`]. The set of addresses is captured
+/// when the iterator is created; account contents are read as it advances.
+///
+/// [`TestHelpers::iter_accounts`]: crate::TestHelpers::iter_accounts
+pub struct AccountIter<'a> {
+    svm: &'a LiteSVM,
+    pubkeys: std::vec::IntoIter<Pubkey>,
+    owner: Option<Pubkey>,
+}
+
+impl<'a> AccountIter<'a> {
+    pub(crate) fn new(svm: &'a LiteSVM, owner: Option<&Pubkey>) -> Self {
+        let pubkeys = tracked_accounts(svm);
+        Self {
+            svm,
+            pubkeys: pubkeys.into_iter(),
+            owner: owner.copied(),
+        }
+    }
+
+    /// Skip to the accounts whose address sorts after `cursor`
+    ///
+    /// Since iteration is ordered by address, passing the last address of one page
+    /// resumes with the next page even if accounts were created in between.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page: Vec<_> = svm.iter_accounts(Some(&spl_token::id())).take(10).collect();
+    /// let cursor = page.last().unwrap().0;
+    /// let next: Vec<_> = svm.iter_accounts(Some(&spl_token::id()))
+    ///     .starting_after(&cursor)
+    ///     .take(10)
+    ///     .collect();
+    /// ```
+    pub fn starting_after(self, cursor: &Pubkey) -> Self {
+        let rest: Vec<Pubkey> = self.pubkeys.filter(|pubkey| pubkey > cursor).collect();
+        Self {
+            pubkeys: rest.into_iter(),
+            ..self
+        }
+    }
+}
+
+impl Iterator for AccountIter<'_> {
+    type Item = (Pubkey, Account);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for pubkey in self.pubkeys.by_ref() {
+            if is_vm_state_address(&pubkey) {
+                continue;
+            }
+            let Some(account) = self.svm.get_account(&pubkey) else {
+                continue;
+            };
+            // Closed accounts linger as empty zero-lamport entries
+            if account.lamports == 0 && account.data.is_empty() {
+                continue;
+            }
+            if self.owner.is_some_and(|owner| owner != account.owner) {
+                continue;
+            }
+            return Some((pubkey, account));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use crate::vm_state::vm_state_addresses;
+    #[cfg(feature = "spl")]
+    use solana_sdk::signature::Signer;
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_iter_accounts() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 6).unwrap();
+        let ata = svm
+            .create_associated_token_account(&mint.pubkey(), &authority)
+            .unwrap();
+
+        // Written behind the helpers' back
+        let untracked = Pubkey::new_unique();
+        svm.set_account(untracked, Account::new(1_000_000, 0, &Pubkey::default()))
+            .unwrap();
+
+        let all: Vec<Pubkey> = svm.iter_accounts(None).map(|(pubkey, _)| pubkey).collect();
+        assert!(all.contains(&authority.pubkey()));
+        assert!(!all.contains(&untracked));
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let token_accounts: Vec<Pubkey> = svm
+            .iter_accounts(Some(&spl_token::id()))
+            .map(|(pubkey, _)| pubkey)
+            .collect();
+        let mut expected = vec![mint.pubkey(), ata];
+        expected.sort();
+        assert_eq!(token_accounts, expected);
+
+        track_accounts(&mut svm, [untracked]);
+        assert!(svm.iter_accounts(None).any(|(pubkey, _)| pubkey == untracked));
+    }
+
+    #[test]
+    fn test_iter_accounts_pagination() {
+        let mut svm = LiteSVM::new();
+        svm.create_funded_accounts(5, 1_000_000_000).unwrap();
+
+        let first: Vec<Pubkey> = svm.iter_accounts(None).take(2).map(|(pk, _)| pk).collect();
+        let rest: Vec<Pubkey> = svm
+            .iter_accounts(None)
+            .starting_after(first.last().unwrap())
+            .map(|(pk, _)| pk)
+            .collect();
+        let all: Vec<Pubkey> = svm.iter_accounts(None).map(|(pk, _)| pk).collect();
+        assert_eq!([first, rest].concat(), all);
+
+        // Another VM on the same thread has its own registry
+        assert_eq!(LiteSVM::new().iter_accounts(None).count(), 0);
+
+        // The helpers' state accounts are never yielded
+        track_accounts(&mut svm, vm_state_addresses());
+        assert_eq!(svm.iter_accounts(None).count(), all.len());
+    }
+
+    #[test]
+    fn test_get_program_accounts_with_filters() {
+        let mut svm = LiteSVM::new();
+        let program_id = Pubkey::new_unique();
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let positions: Vec<Pubkey> = [alice, alice, alice, bob]
+            .iter()
+            .map(|owner| {
+                let position = Pubkey::new_unique();
+                let data = [&[1u8; 8][..], owner.as_ref()].concat();
+                svm.account_builder(position)
+                    .owner(&program_id)
+                    .data(data)
+                    .create()
+                    .unwrap();
+                position
+            })
+            .collect();
+        let config = Pubkey::new_unique();
+        svm.account_builder(config)
+            .owner(&program_id)
+            .data(vec![2; 8])
+            .create()
+            .unwrap();
+
+        assert_eq!(svm.get_program_accounts(&program_id).len(), 5);
+
+        let owned_by_alice = svm.get_program_accounts_with_filters(
+            &program_id,
+            &[AccountFilter::data_size(40), AccountFilter::memcmp(8, alice)],
+        );
+        let mut expected = positions[..3].to_vec();
+        expected.sort();
+        let found: Vec<Pubkey> = owned_by_alice.iter().map(|(pubkey, _)| *pubkey).collect();
+        assert_eq!(found, expected);
+
+        // A memcmp past the end of the data doesn't match
+        assert!(!AccountFilter::memcmp(6, [2, 2, 2]).matches(&[2; 8]));
+        assert!(AccountFilter::memcmp(5, [2, 2, 2]).matches(&[2; 8]));
+        assert!(svm
+            .get_program_accounts(&Pubkey::new_unique())
+            .is_empty());
+    }
+}
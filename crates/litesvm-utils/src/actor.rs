@@ -0,0 +1,314 @@
+//! Persona-centric fixtures: funded actors with token portfolios
+//!
+//! Instead of creating a keypair, funding it, creating ATAs and minting into each
+//! of them separately, describe the actor once:
+//!
+//! ```ignore
+//! let alice = svm.create_actor()
+//!     .with_sol(10)
+//!     .with_tokens(&usdc, 1_000_000)
+//!     .build()?;
+//! svm.assert_token_balance(&alice.ata(&usdc), 1_000_000);
+//! ```
+//!
+//! [`ActorHelpers::persona`] starts the same builder under its [`PersonaBuilder`]
+//! name, with the shorter [`sol`](ActorBuilder::sol) and [`token`](ActorBuilder::token):
+//!
+//! ```ignore
+//! let trader = ctx.persona().sol(10).token(&mint_a, 1_000).token(&mint_b, 0).build()?;
+//! ```
+
+use crate::account_registry::track_accounts;
+use crate::faucet::fund_account;
+use crate::keypairs::new_keypair;
+use crate::surgery::StateSurgery;
+use litesvm::LiteSVM;
+use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program::program_option::COption;
+use solana_program::pubkey::Pubkey;
+use solana_program_pack::Pack;
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use std::collections::HashMap;
+use std::error::Error;
+
+/// SOL given to an actor when [`ActorBuilder::with_sol`] isn't called
+const DEFAULT_SOL: u64 = 10;
+
+/// A funded test participant and its associated token accounts
+#[derive(Debug)]
+pub struct Actor {
+    /// The actor's keypair
+    pub keypair: Keypair,
+    atas: HashMap<Pubkey, Pubkey>,
+}
+
+impl Actor {
+    /// Get the actor's public key
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    /// Get the actor's associated token account for `mint`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the actor wasn't built with tokens of `mint`
+    pub fn ata(&self, mint: &Pubkey) -> Pubkey {
+        *self.atas.get(mint).unwrap_or_else(|| {
+            panic!(
+                "Actor {} has no token account for mint {}",
+                self.pubkey(),
+                mint
+            )
+        })
+    }
+
+    /// Get all of the actor's associated token accounts, keyed by mint
+    pub fn atas(&self) -> &HashMap<Pubkey, Pubkey> {
+        &self.atas
+    }
+}
+
+/// Builder for an [`Actor`], created by [`ActorHelpers::create_actor`]
+pub struct ActorBuilder<'a> {
+    svm: &'a mut LiteSVM,
+    lamports: u64,
+    tokens: Vec<(Pubkey, u64)>,
+}
+
+/// A wallet persona builder, created by [`ActorHelpers::persona`]
+///
+/// The same builder as [`ActorBuilder`]; a persona is an [`Actor`].
+pub type PersonaBuilder<'a> = ActorBuilder<'a>;
+
+impl<'a> ActorBuilder<'a> {
+    /// Fund the actor with `sol` whole SOL (defaults to 10)
+    pub fn with_sol(mut self, sol: u64) -> Self {
+        self.lamports = sol * LAMPORTS_PER_SOL;
+        self
+    }
+
+    /// Fund the actor with an exact lamport amount
+    pub fn with_lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    /// Give the actor an associated token account holding `amount` base units of `mint`
+    ///
+    /// The balance is written directly, so no mint authority is needed. The mint's
+    /// supply is increased to match. Wrapped SOL is backed by real lamports.
+    pub fn with_tokens(mut self, mint: &Pubkey, amount: u64) -> Self {
+        self.tokens.push((*mint, amount));
+        self
+    }
+
+    /// Fund the actor with `sol` whole SOL; shorthand for [`with_sol`](Self::with_sol)
+    pub fn sol(self, sol: u64) -> Self {
+        self.with_sol(sol)
+    }
+
+    /// Give the actor `amount` of `mint`; shorthand for [`with_tokens`](Self::with_tokens)
+    ///
+    /// An `amount` of 0 still creates the associated token account.
+    pub fn token(self, mint: &Pubkey, amount: u64) -> Self {
+        self.with_tokens(mint, amount)
+    }
+
+    /// Create the actor
+    #[track_caller]
+    pub fn build(self) -> Result<Actor, Box<dyn Error>> {
+        let keypair = new_keypair(self.svm);
+        fund_account(self.svm, &keypair.pubkey(), self.lamports)?;
+        track_accounts(self.svm, [keypair.pubkey()]);
+
+        let mut atas = HashMap::new();
+        for (mint, amount) in self.tokens {
+            let ata = provision_tokens(self.svm, &keypair.pubkey(), &mint, amount)?;
+            atas.insert(mint, ata);
+        }
+
+        Ok(Actor { keypair, atas })
+    }
+}
+
+/// Write an initialized associated token account holding `amount` of `mint`
+#[track_caller]
+fn provision_tokens(
+    svm: &mut LiteSVM,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let is_native = *mint == spl_token::native_mint::id();
+    let token_program = match svm.get_account(mint) {
+        Some(mint_account) => {
+            if !is_native {
+                // Keep the mint supply consistent with the new balance
+                let mut mint_data = Mint::unpack(&mint_account.data[..Mint::LEN])
+                    .map_err(|e| format!("Failed to unpack mint {}: {:?}", mint, e))?;
+                mint_data.supply = mint_data
+                    .supply
+                    .checked_add(amount)
+                    .ok_or_else(|| format!("Supply overflow for mint {}", mint))?;
+                let mut updated = mint_account.clone();
+                Mint::pack(mint_data, &mut updated.data[..Mint::LEN])?;
+                StateSurgery::new(svm).write("provision_tokens (mint supply)", mint, updated)?;
+            }
+            mint_account.owner
+        }
+        None if is_native => spl_token::id(),
+        None => return Err(format!("Mint {} not found", mint).into()),
+    };
+
+    let ata = get_associated_token_address_with_program_id(owner, mint, &token_program);
+    let rent = svm.minimum_balance_for_rent_exemption(TokenAccount::LEN);
+    let token_account = TokenAccount {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: AccountState::Initialized,
+        is_native: if is_native {
+            COption::Some(rent)
+        } else {
+            COption::None
+        },
+        ..TokenAccount::default()
+    };
+
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount::pack(token_account, &mut data)?;
+    let lamports = if is_native { rent + amount } else { rent };
+    let account = Account {
+        lamports,
+        data,
+        owner: token_program,
+        executable: false,
+        rent_epoch: 0,
+    };
+    StateSurgery::new(svm).write("provision_tokens", &ata, account)?;
+    track_accounts(svm, [*mint]);
+    Ok(ata)
+}
+
+/// Actor fixture helpers for LiteSVM
+pub trait ActorHelpers {
+    /// Start building a funded actor with a token portfolio
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bob = svm.create_actor()
+    ///     .with_sol(5)
+    ///     .with_tokens(&usdc, 1_000_000)
+    ///     .with_tokens(&spl_token::native_mint::id(), 5_000_000_000)
+    ///     .build()?;
+    /// ```
+    fn create_actor(&mut self) -> ActorBuilder<'_>;
+
+    /// Start building a funded wallet with ATAs and starting token balances
+    ///
+    /// Same as [`create_actor`](Self::create_actor).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let trader = svm.persona().sol(10).token(&mint_a, 1_000).token(&mint_b, 0).build()?;
+    /// ```
+    fn persona(&mut self) -> PersonaBuilder<'_> {
+        self.create_actor()
+    }
+}
+
+impl ActorHelpers for LiteSVM {
+    fn create_actor(&mut self) -> ActorBuilder<'_> {
+        ActorBuilder {
+            svm: self,
+            lamports: DEFAULT_SOL * LAMPORTS_PER_SOL,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assertions::AssertionHelpers;
+    use crate::test_helpers::TestHelpers;
+
+    #[test]
+    fn test_create_actor_with_portfolio() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let usdc = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let wsol = spl_token::native_mint::id();
+
+        let alice = svm
+            .create_actor()
+            .with_sol(3)
+            .with_tokens(&usdc, 1_000_000)
+            .with_tokens(&wsol, 5)
+            .build()
+            .unwrap();
+
+        svm.assert_sol_balance(&alice.pubkey(), 3 * LAMPORTS_PER_SOL);
+        svm.assert_token_balance(&alice.ata(&usdc), 1_000_000);
+        svm.assert_token_balance(&alice.ata(&wsol), 5);
+        svm.assert_mint_supply(&usdc, 1_000_000);
+        svm.assert_ata_exists(&alice.pubkey(), &usdc);
+
+        // The provisioned balance is spendable
+        let bob = svm.create_actor().with_tokens(&usdc, 0).build().unwrap();
+        let ix = spl_token::instruction::transfer(
+            &spl_token::id(),
+            &alice.ata(&usdc),
+            &bob.ata(&usdc),
+            &alice.pubkey(),
+            &[],
+            400_000,
+        )
+        .unwrap();
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&alice.pubkey()),
+            &[&alice.keypair],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+        svm.assert_token_balance(&bob.ata(&usdc), 400_000);
+    }
+
+    #[test]
+    fn test_persona() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint_a = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let mint_b = svm.create_token_mint(&authority, 9).unwrap().pubkey();
+
+        let trader = svm
+            .persona()
+            .sol(2)
+            .token(&mint_a, 1_000)
+            .token(&mint_b, 0)
+            .build()
+            .unwrap();
+
+        svm.assert_sol_balance(&trader.pubkey(), 2 * LAMPORTS_PER_SOL);
+        svm.assert_token_balance(&trader.ata(&mint_a), 1_000);
+        svm.assert_token_balance(&trader.ata(&mint_b), 0);
+        assert_eq!(trader.atas().len(), 2);
+    }
+
+    #[test]
+    fn test_create_actor_unknown_mint() {
+        let mut svm = LiteSVM::new();
+        let result = svm
+            .create_actor()
+            .with_tokens(&Pubkey::new_unique(), 1)
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -3,10 +3,104 @@
 //! This module provides convenient assertion methods for verifying
 //! account states in tests.
 
+use crate::test_helpers::TestHelpers;
 use litesvm::LiteSVM;
+use solana_program::clock::Clock;
 use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::{Sysvar, SysvarId};
+#[cfg(feature = "spl")]
 use litesvm_token::spl_token;
+#[cfg(feature = "spl")]
 use solana_program_pack::Pack;
+#[cfg(feature = "spl")]
+use spl_associated_token_account::get_associated_token_address;
+use solana_sdk::account::Account;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// A failed check from one of the [`AssertionHelpers`] `check_*` methods
+///
+/// The message is the one the matching `assert_*` method panics with.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct AssertionError {
+    message: String,
+}
+
+impl AssertionError {
+    /// Create an error with the given failure message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// Get the failure message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Fail with `message` unless `condition` holds
+fn ensure(condition: bool, message: impl FnOnce() -> String) -> Result<(), AssertionError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(AssertionError::new(message()))
+    }
+}
+
+/// Read an account, failing with "`kind` `pubkey` not found" if it doesn't exist
+fn existing(svm: &LiteSVM, pubkey: &Pubkey, kind: &str) -> Result<Account, AssertionError> {
+    svm.get_account(pubkey)
+        .ok_or_else(|| AssertionError::new(format!("{} {} not found", kind, pubkey)))
+}
+
+/// Balances captured by [`AssertionHelpers::capture_balances`]
+///
+/// Compare against it with [`AssertionHelpers::assert_sol_balance_change`] and
+/// [`AssertionHelpers::assert_token_balance_change`], so tests state how much an
+/// instruction moved rather than absolute amounts that depend on setup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BalanceSnapshot {
+    balances: HashMap<Pubkey, CapturedBalance>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CapturedBalance {
+    lamports: u64,
+    tokens: Option<u64>,
+}
+
+impl BalanceSnapshot {
+    /// Captured SOL balance, `None` if `pubkey` wasn't captured
+    ///
+    /// Accounts that didn't exist are captured with a balance of 0.
+    pub fn sol_balance(&self, pubkey: &Pubkey) -> Option<u64> {
+        self.balances.get(pubkey).map(|balance| balance.lamports)
+    }
+
+    /// Captured token balance, `None` if `pubkey` wasn't captured as a token account
+    pub fn token_balance(&self, pubkey: &Pubkey) -> Option<u64> {
+        self.balances.get(pubkey).and_then(|balance| balance.tokens)
+    }
+}
+
+/// Read the amount of an SPL token account, `None` if it isn't one
+fn token_amount(svm: &LiteSVM, pubkey: &Pubkey) -> Option<u64> {
+    #[cfg(feature = "spl")]
+    {
+        let account = svm.get_account(pubkey)?;
+        spl_token::state::Account::unpack(account.data.get(..spl_token::state::Account::LEN)?)
+            .ok()
+            .map(|data| data.amount)
+    }
+    #[cfg(not(feature = "spl"))]
+    {
+        let _ = (svm, pubkey);
+        None
+    }
+}
 
 /// Assertion helper methods for LiteSVM
 pub trait AssertionHelpers {
@@ -21,7 +115,14 @@ pub trait AssertionHelpers {
     /// # let account = Pubkey::new_unique();
     /// svm.assert_account_closed(&account);
     /// ```
-    fn assert_account_closed(&self, pubkey: &Pubkey);
+    fn assert_account_closed(&self, pubkey: &Pubkey) {
+        if let Err(e) = self.check_account_closed(pubkey) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_account_closed`](Self::assert_account_closed)
+    fn check_account_closed(&self, pubkey: &Pubkey) -> Result<(), AssertionError>;
 
     /// Assert that an account exists
     ///
@@ -34,7 +135,14 @@ pub trait AssertionHelpers {
     /// # let account = Pubkey::new_unique();
     /// svm.assert_account_exists(&account);
     /// ```
-    fn assert_account_exists(&self, pubkey: &Pubkey);
+    fn assert_account_exists(&self, pubkey: &Pubkey) {
+        if let Err(e) = self.check_account_exists(pubkey) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_account_exists`](Self::assert_account_exists)
+    fn check_account_exists(&self, pubkey: &Pubkey) -> Result<(), AssertionError>;
 
     /// Assert token account balance
     ///
@@ -47,7 +155,47 @@ pub trait AssertionHelpers {
     /// # let token_account = Pubkey::new_unique();
     /// svm.assert_token_balance(&token_account, 1_000_000_000); // 1 token with 9 decimals
     /// ```
-    fn assert_token_balance(&self, token_account: &Pubkey, expected: u64);
+    #[cfg(feature = "spl")]
+    fn assert_token_balance(&self, token_account: &Pubkey, expected: u64) {
+        if let Err(e) = self.check_token_balance(token_account, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_token_balance`](Self::assert_token_balance)
+    #[cfg(feature = "spl")]
+    fn check_token_balance(
+        &self,
+        token_account: &Pubkey,
+        expected: u64,
+    ) -> Result<(), AssertionError>;
+
+    /// Assert many token account balances at once
+    ///
+    /// Every entry is checked before failing, and the panic message lists each
+    /// mismatch with its index in `expected` and its address.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let ata1 = Pubkey::new_unique();
+    /// # let ata2 = Pubkey::new_unique();
+    /// # let ata3 = Pubkey::new_unique();
+    /// svm.assert_token_balances(&[(&ata1, 100), (&ata2, 0), (&ata3, 500)]);
+    /// ```
+    #[cfg(feature = "spl")]
+    fn assert_token_balances(&self, expected: &[(&Pubkey, u64)]) {
+        if let Err(e) = self.check_token_balances(expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_token_balances`](Self::assert_token_balances)
+    #[cfg(feature = "spl")]
+    fn check_token_balances(&self, expected: &[(&Pubkey, u64)]) -> Result<(), AssertionError>;
 
     /// Assert SOL balance
     ///
@@ -60,7 +208,112 @@ pub trait AssertionHelpers {
     /// # let account = Pubkey::new_unique();
     /// svm.assert_sol_balance(&account, 1_000_000_000); // 1 SOL
     /// ```
-    fn assert_sol_balance(&self, pubkey: &Pubkey, expected: u64);
+    fn assert_sol_balance(&self, pubkey: &Pubkey, expected: u64) {
+        if let Err(e) = self.check_sol_balance(pubkey, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_sol_balance`](Self::assert_sol_balance)
+    fn check_sol_balance(&self, pubkey: &Pubkey, expected: u64) -> Result<(), AssertionError>;
+
+    /// Capture SOL and token balances to assert changes against later
+    ///
+    /// Token balances are recorded for SPL token accounts only. Accounts that
+    /// don't exist yet are captured with a SOL balance of 0.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let (user, vault_ata) = (Pubkey::new_unique(), Pubkey::new_unique());
+    /// let before = svm.capture_balances(&[user, vault_ata]);
+    /// // ... execute the deposit ...
+    /// svm.assert_sol_balance_change(&before, &user, -5_000);
+    /// ```
+    fn capture_balances(&self, pubkeys: &[Pubkey]) -> BalanceSnapshot;
+
+    /// Assert the SOL balance changed by exactly `expected` since `snapshot`
+    ///
+    /// A closed account counts as a balance of 0. Panics if `pubkey` wasn't captured.
+    fn assert_sol_balance_change(
+        &self,
+        snapshot: &BalanceSnapshot,
+        pubkey: &Pubkey,
+        expected: i128,
+    ) {
+        if let Err(e) = self.check_sol_balance_change(snapshot, pubkey, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_sol_balance_change`](Self::assert_sol_balance_change)
+    fn check_sol_balance_change(
+        &self,
+        snapshot: &BalanceSnapshot,
+        pubkey: &Pubkey,
+        expected: i128,
+    ) -> Result<(), AssertionError>;
+
+    /// Assert the token balance changed by exactly `expected` since `snapshot`
+    ///
+    /// A closed token account counts as a balance of 0. Panics if `token_account`
+    /// wasn't captured as a token account.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let vault_ata = Pubkey::new_unique();
+    /// # let before = svm.capture_balances(&[vault_ata]);
+    /// svm.assert_token_balance_change(&before, &vault_ata, 500);
+    /// ```
+    #[cfg(feature = "spl")]
+    fn assert_token_balance_change(
+        &self,
+        snapshot: &BalanceSnapshot,
+        token_account: &Pubkey,
+        expected: i128,
+    ) {
+        if let Err(e) = self.check_token_balance_change(snapshot, token_account, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_token_balance_change`](Self::assert_token_balance_change)
+    #[cfg(feature = "spl")]
+    fn check_token_balance_change(
+        &self,
+        snapshot: &BalanceSnapshot,
+        token_account: &Pubkey,
+        expected: i128,
+    ) -> Result<(), AssertionError>;
+
+    /// Assert that a token account is frozen
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let token_account = Pubkey::new_unique();
+    /// svm.assert_token_account_frozen(&token_account);
+    /// ```
+    #[cfg(feature = "spl")]
+    fn assert_token_account_frozen(&self, token_account: &Pubkey) {
+        if let Err(e) = self.check_token_account_frozen(token_account) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_token_account_frozen`](Self::assert_token_account_frozen)
+    #[cfg(feature = "spl")]
+    fn check_token_account_frozen(&self, token_account: &Pubkey) -> Result<(), AssertionError>;
 
     /// Assert token mint supply
     ///
@@ -73,7 +326,16 @@ pub trait AssertionHelpers {
     /// # let mint = Pubkey::new_unique();
     /// svm.assert_mint_supply(&mint, 1_000_000_000);
     /// ```
-    fn assert_mint_supply(&self, mint: &Pubkey, expected: u64);
+    #[cfg(feature = "spl")]
+    fn assert_mint_supply(&self, mint: &Pubkey, expected: u64) {
+        if let Err(e) = self.check_mint_supply(mint, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_mint_supply`](Self::assert_mint_supply)
+    #[cfg(feature = "spl")]
+    fn check_mint_supply(&self, mint: &Pubkey, expected: u64) -> Result<(), AssertionError>;
 
     /// Assert that an account is owned by a specific program
     ///
@@ -87,7 +349,18 @@ pub trait AssertionHelpers {
     /// # let owner = Pubkey::new_unique();
     /// svm.assert_account_owner(&account, &owner);
     /// ```
-    fn assert_account_owner(&self, account: &Pubkey, expected_owner: &Pubkey);
+    fn assert_account_owner(&self, account: &Pubkey, expected_owner: &Pubkey) {
+        if let Err(e) = self.check_account_owner(account, expected_owner) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_account_owner`](Self::assert_account_owner)
+    fn check_account_owner(
+        &self,
+        account: &Pubkey,
+        expected_owner: &Pubkey,
+    ) -> Result<(), AssertionError>;
 
     /// Assert that an account has a specific data length
     ///
@@ -100,95 +373,511 @@ pub trait AssertionHelpers {
     /// # let account = Pubkey::new_unique();
     /// svm.assert_account_data_len(&account, 100);
     /// ```
-    fn assert_account_data_len(&self, account: &Pubkey, expected_len: usize);
+    fn assert_account_data_len(&self, account: &Pubkey, expected_len: usize) {
+        if let Err(e) = self.check_account_data_len(account, expected_len) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_account_data_len`](Self::assert_account_data_len)
+    fn check_account_data_len(
+        &self,
+        account: &Pubkey,
+        expected_len: usize,
+    ) -> Result<(), AssertionError>;
+
+    /// Assert that an account's data contains `expected` starting at byte `offset`
+    ///
+    /// For native programs and other accounts without a Rust struct to deserialize into.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let mint = Pubkey::new_unique();
+    /// // Decimals of an SPL mint live at byte 44
+    /// svm.assert_account_bytes(&mint, 44, &[6]);
+    /// ```
+    fn assert_account_bytes(&self, account: &Pubkey, offset: usize, expected: &[u8]) {
+        if let Err(e) = self.check_account_bytes(account, offset, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_account_bytes`](Self::assert_account_bytes)
+    fn check_account_bytes(
+        &self,
+        account: &Pubkey,
+        offset: usize,
+        expected: &[u8],
+    ) -> Result<(), AssertionError>;
+
+    /// Assert that the associated token account of `owner` for `mint` exists
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let owner = Pubkey::new_unique();
+    /// # let mint = Pubkey::new_unique();
+    /// svm.assert_ata_exists(&owner, &mint);
+    /// ```
+    #[cfg(feature = "spl")]
+    fn assert_ata_exists(&self, owner: &Pubkey, mint: &Pubkey) {
+        if let Err(e) = self.check_ata_exists(owner, mint) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_ata_exists`](Self::assert_ata_exists)
+    #[cfg(feature = "spl")]
+    fn check_ata_exists(&self, owner: &Pubkey, mint: &Pubkey) -> Result<(), AssertionError>;
+
+    /// Assert that the associated token account of `owner` for `mint` is closed
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let owner = Pubkey::new_unique();
+    /// # let mint = Pubkey::new_unique();
+    /// svm.assert_ata_closed(&owner, &mint);
+    /// ```
+    #[cfg(feature = "spl")]
+    fn assert_ata_closed(&self, owner: &Pubkey, mint: &Pubkey) {
+        if let Err(e) = self.check_ata_closed(owner, mint) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_ata_closed`](Self::assert_ata_closed)
+    #[cfg(feature = "spl")]
+    fn check_ata_closed(&self, owner: &Pubkey, mint: &Pubkey) -> Result<(), AssertionError>;
+
+    /// Assert the upgrade authority of an upgradeable program (`None` for immutable)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let program_id = Pubkey::new_unique();
+    /// # let authority = Pubkey::new_unique();
+    /// svm.assert_upgrade_authority(&program_id, Some(&authority));
+    /// ```
+    fn assert_upgrade_authority(&self, program_id: &Pubkey, expected: Option<&Pubkey>) {
+        if let Err(e) = self.check_upgrade_authority(program_id, expected) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_upgrade_authority`](Self::assert_upgrade_authority)
+    fn check_upgrade_authority(
+        &self,
+        program_id: &Pubkey,
+        expected: Option<&Pubkey>,
+    ) -> Result<(), AssertionError>;
+
+    /// Assert the slot an upgradeable program was last deployed at
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let program_id = Pubkey::new_unique();
+    /// svm.assert_program_deployed_at(&program_id, 0);
+    /// ```
+    fn assert_program_deployed_at(&self, program_id: &Pubkey, expected_slot: u64) {
+        if let Err(e) = self.check_program_deployed_at(program_id, expected_slot) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_program_deployed_at`](Self::assert_program_deployed_at)
+    fn check_program_deployed_at(
+        &self,
+        program_id: &Pubkey,
+        expected_slot: u64,
+    ) -> Result<(), AssertionError>;
+
+    /// Assert a condition on a sysvar, making a test's time/epoch pre-conditions explicit
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::epoch_schedule::EpochSchedule;
+    /// # let svm = LiteSVM::new();
+    /// svm.assert_sysvar::<EpochSchedule, _>(|s| s.slots_per_epoch >= 32);
+    /// ```
+    fn assert_sysvar<T, F>(&self, predicate: F)
+    where
+        T: Sysvar + SysvarId + std::fmt::Debug,
+        F: FnOnce(&T) -> bool,
+    {
+        if let Err(e) = self.check_sysvar::<T, F>(predicate) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Non-panicking [`assert_sysvar`](Self::assert_sysvar)
+    fn check_sysvar<T, F>(&self, predicate: F) -> Result<(), AssertionError>
+    where
+        T: Sysvar + SysvarId + std::fmt::Debug,
+        F: FnOnce(&T) -> bool;
+
+    /// Assert a condition on the `Clock` sysvar
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::AssertionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let svm = LiteSVM::new();
+    /// # let deadline = 0;
+    /// svm.assert_clock(|c| c.unix_timestamp >= deadline);
+    /// ```
+    fn assert_clock<F>(&self, predicate: F)
+    where
+        F: FnOnce(&Clock) -> bool,
+    {
+        self.assert_sysvar::<Clock, F>(predicate)
+    }
+
+    /// Non-panicking [`assert_clock`](Self::assert_clock)
+    fn check_clock<F>(&self, predicate: F) -> Result<(), AssertionError>
+    where
+        F: FnOnce(&Clock) -> bool,
+    {
+        self.check_sysvar::<Clock, F>(predicate)
+    }
 }
 
 impl AssertionHelpers for LiteSVM {
-    fn assert_account_closed(&self, pubkey: &Pubkey) {
+    fn check_account_closed(&self, pubkey: &Pubkey) -> Result<(), AssertionError> {
         let account = self.get_account(pubkey);
-        assert!(
-            account.is_none() || (account.as_ref().unwrap().lamports == 0 && account.as_ref().unwrap().data.is_empty()),
-            "Expected account {} to be closed, but it exists with {} lamports and {} bytes of data",
-            pubkey,
-            account.as_ref().map_or(0, |a| a.lamports),
-            account.as_ref().map_or(0, |a| a.data.len())
-        );
+        ensure(
+            account
+                .as_ref()
+                .is_none_or(|a| a.lamports == 0 && a.data.is_empty()),
+            || {
+                format!(
+                    "Expected account {} to be closed, but it exists with {} lamports and {} bytes of data",
+                    pubkey,
+                    account.as_ref().map_or(0, |a| a.lamports),
+                    account.as_ref().map_or(0, |a| a.data.len())
+                )
+            },
+        )
     }
 
-    fn assert_account_exists(&self, pubkey: &Pubkey) {
-        let account = self.get_account(pubkey);
-        assert!(
-            account.is_some(),
-            "Expected account {} to exist, but it doesn't",
-            pubkey
-        );
+    fn check_account_exists(&self, pubkey: &Pubkey) -> Result<(), AssertionError> {
+        ensure(self.get_account(pubkey).is_some(), || {
+            format!("Expected account {} to exist, but it doesn't", pubkey)
+        })
     }
 
-    fn assert_token_balance(&self, token_account: &Pubkey, expected: u64) {
-        let account = self
-            .get_account(token_account)
-            .unwrap_or_else(|| panic!("Token account {} not found", token_account));
+    #[cfg(feature = "spl")]
+    fn check_token_balance(
+        &self,
+        token_account: &Pubkey,
+        expected: u64,
+    ) -> Result<(), AssertionError> {
+        let account = existing(self, token_account, "Token account")?;
+        let token_data = spl_token::state::Account::unpack(&account.data).map_err(|_| {
+            AssertionError::new(format!("Failed to unpack token account {}", token_account))
+        })?;
 
-        let token_data = spl_token::state::Account::unpack(&account.data)
-            .unwrap_or_else(|_| panic!("Failed to unpack token account {}", token_account));
+        ensure(token_data.amount == expected, || {
+            format!(
+                "Token balance mismatch for account {}. Expected: {}, Actual: {}",
+                token_account, expected, token_data.amount
+            )
+        })
+    }
 
-        assert_eq!(
-            token_data.amount, expected,
-            "Token balance mismatch for account {}. Expected: {}, Actual: {}",
-            token_account, expected, token_data.amount
-        );
+    #[cfg(feature = "spl")]
+    fn check_token_balances(&self, expected: &[(&Pubkey, u64)]) -> Result<(), AssertionError> {
+        let mismatches: Vec<String> = expected
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (token_account, expected))| {
+                let actual = self
+                    .get_account(token_account)
+                    .ok_or("not found")
+                    .and_then(|account| {
+                        spl_token::state::Account::unpack(&account.data)
+                            .map_err(|_| "not a token account")
+                    });
+                match actual {
+                    Ok(data) if data.amount == *expected => None,
+                    Ok(data) => Some(format!(
+                        "[{}] {}: expected {}, actual {}",
+                        index, token_account, expected, data.amount
+                    )),
+                    Err(reason) => Some(format!(
+                        "[{}] {}: expected {}, {}",
+                        index, token_account, expected, reason
+                    )),
+                }
+            })
+            .collect();
+
+        ensure(mismatches.is_empty(), || {
+            format!(
+                "{} of {} token balances mismatched:\n  {}",
+                mismatches.len(),
+                expected.len(),
+                mismatches.join("\n  ")
+            )
+        })
     }
 
-    fn assert_sol_balance(&self, pubkey: &Pubkey, expected: u64) {
-        let account = self.get_account(pubkey);
-        let actual = account.map_or(0, |a| a.lamports);
-        assert_eq!(
-            actual, expected,
-            "SOL balance mismatch for account {}. Expected: {}, Actual: {}",
-            pubkey, expected, actual
-        );
+    #[cfg(feature = "spl")]
+    fn check_token_account_frozen(&self, token_account: &Pubkey) -> Result<(), AssertionError> {
+        let account = existing(self, token_account, "Token account")?;
+        let token_data = spl_token::state::Account::unpack(&account.data).map_err(|_| {
+            AssertionError::new(format!("Failed to unpack token account {}", token_account))
+        })?;
+
+        ensure(token_data.is_frozen(), || {
+            format!(
+                "Expected token account {} to be frozen, but it is {:?}",
+                token_account, token_data.state
+            )
+        })
     }
 
-    fn assert_mint_supply(&self, mint: &Pubkey, expected: u64) {
-        let account = self
-            .get_account(mint)
-            .unwrap_or_else(|| panic!("Mint {} not found", mint));
+    fn check_sol_balance(&self, pubkey: &Pubkey, expected: u64) -> Result<(), AssertionError> {
+        let actual = self.get_account(pubkey).map_or(0, |a| a.lamports);
+        ensure(actual == expected, || {
+            format!(
+                "SOL balance mismatch for account {}. Expected: {}, Actual: {}",
+                pubkey, expected, actual
+            )
+        })
+    }
+
+    fn capture_balances(&self, pubkeys: &[Pubkey]) -> BalanceSnapshot {
+        let balances = pubkeys
+            .iter()
+            .map(|pubkey| {
+                let balance = CapturedBalance {
+                    lamports: self.get_balance(pubkey).unwrap_or(0),
+                    tokens: token_amount(self, pubkey),
+                };
+                (*pubkey, balance)
+            })
+            .collect();
+        BalanceSnapshot { balances }
+    }
 
+    fn check_sol_balance_change(
+        &self,
+        snapshot: &BalanceSnapshot,
+        pubkey: &Pubkey,
+        expected: i128,
+    ) -> Result<(), AssertionError> {
+        let before = snapshot.sol_balance(pubkey).ok_or_else(|| {
+            AssertionError::new(format!("Balance of {} was not captured", pubkey))
+        })?;
+        let after = self.get_balance(pubkey).unwrap_or(0);
+        let actual = after as i128 - before as i128;
+        ensure(actual == expected, || {
+            format!(
+                "SOL balance change mismatch for account {}. Expected: {:+}, Actual: {:+} ({} -> {})",
+                pubkey, expected, actual, before, after
+            )
+        })
+    }
+
+    #[cfg(feature = "spl")]
+    fn check_token_balance_change(
+        &self,
+        snapshot: &BalanceSnapshot,
+        token_account: &Pubkey,
+        expected: i128,
+    ) -> Result<(), AssertionError> {
+        let before = snapshot.token_balance(token_account).ok_or_else(|| {
+            AssertionError::new(format!(
+                "Token balance of {} was not captured",
+                token_account
+            ))
+        })?;
+        let after = match self.get_account(token_account) {
+            Some(account) if account.lamports > 0 => {
+                token_amount(self, token_account).ok_or_else(|| {
+                    AssertionError::new(format!("Failed to unpack token account {}", token_account))
+                })?
+            }
+            _ => 0,
+        };
+        let actual = after as i128 - before as i128;
+        ensure(actual == expected, || {
+            format!(
+                "Token balance change mismatch for account {}. Expected: {:+}, Actual: {:+} ({} -> {})",
+                token_account, expected, actual, before, after
+            )
+        })
+    }
+
+    #[cfg(feature = "spl")]
+    fn check_mint_supply(&self, mint: &Pubkey, expected: u64) -> Result<(), AssertionError> {
+        let account = existing(self, mint, "Mint")?;
         let mint_data = spl_token::state::Mint::unpack(&account.data)
-            .unwrap_or_else(|_| panic!("Failed to unpack mint {}", mint));
+            .map_err(|_| AssertionError::new(format!("Failed to unpack mint {}", mint)))?;
 
-        assert_eq!(
-            mint_data.supply, expected,
-            "Mint supply mismatch for {}. Expected: {}, Actual: {}",
-            mint, expected, mint_data.supply
-        );
+        ensure(mint_data.supply == expected, || {
+            format!(
+                "Mint supply mismatch for {}. Expected: {}, Actual: {}",
+                mint, expected, mint_data.supply
+            )
+        })
     }
 
-    fn assert_account_owner(&self, account: &Pubkey, expected_owner: &Pubkey) {
-        let acc = self
-            .get_account(account)
-            .unwrap_or_else(|| panic!("Account {} not found", account));
+    fn check_account_owner(
+        &self,
+        account: &Pubkey,
+        expected_owner: &Pubkey,
+    ) -> Result<(), AssertionError> {
+        let acc = existing(self, account, "Account")?;
+        ensure(&acc.owner == expected_owner, || {
+            format!(
+                "Account owner mismatch for {}. Expected: {}, Actual: {}",
+                account, expected_owner, acc.owner
+            )
+        })
+    }
 
-        assert_eq!(
-            &acc.owner, expected_owner,
-            "Account owner mismatch for {}. Expected: {}, Actual: {}",
-            account, expected_owner, acc.owner
-        );
+    fn check_account_data_len(
+        &self,
+        account: &Pubkey,
+        expected_len: usize,
+    ) -> Result<(), AssertionError> {
+        let acc = existing(self, account, "Account")?;
+        ensure(acc.data.len() == expected_len, || {
+            format!(
+                "Account data length mismatch for {}. Expected: {}, Actual: {}",
+                account,
+                expected_len,
+                acc.data.len()
+            )
+        })
     }
 
-    fn assert_account_data_len(&self, account: &Pubkey, expected_len: usize) {
-        let acc = self
-            .get_account(account)
-            .unwrap_or_else(|| panic!("Account {} not found", account));
+    fn check_account_bytes(
+        &self,
+        account: &Pubkey,
+        offset: usize,
+        expected: &[u8],
+    ) -> Result<(), AssertionError> {
+        let acc = existing(self, account, "Account")?;
+        let actual = acc
+            .data
+            .get(offset..offset + expected.len())
+            .ok_or_else(|| {
+                AssertionError::new(format!(
+                    "Account {} has {} bytes of data, can't read {} bytes at offset {}",
+                    account,
+                    acc.data.len(),
+                    expected.len(),
+                    offset
+                ))
+            })?;
+        ensure(actual == expected, || {
+            format!(
+                "Account bytes mismatch for {} at offset {}. Expected: {:?}, Actual: {:?}",
+                account, offset, expected, actual
+            )
+        })
+    }
 
-        assert_eq!(
-            acc.data.len(),
-            expected_len,
-            "Account data length mismatch for {}. Expected: {}, Actual: {}",
-            account,
-            expected_len,
-            acc.data.len()
-        );
+    #[cfg(feature = "spl")]
+    fn check_ata_exists(&self, owner: &Pubkey, mint: &Pubkey) -> Result<(), AssertionError> {
+        let ata = get_associated_token_address(owner, mint);
+        ensure(self.get_account(&ata).is_some(), || {
+            format!(
+                "Expected associated token account {} (owner {}, mint {}) to exist, but it doesn't",
+                ata, owner, mint
+            )
+        })
+    }
+
+    #[cfg(feature = "spl")]
+    fn check_ata_closed(&self, owner: &Pubkey, mint: &Pubkey) -> Result<(), AssertionError> {
+        let ata = get_associated_token_address(owner, mint);
+        let account = self.get_account(&ata);
+        ensure(
+            account
+                .as_ref()
+                .is_none_or(|a| a.lamports == 0 && a.data.is_empty()),
+            || {
+                format!(
+                    "Expected associated token account {} (owner {}, mint {}) to be closed, but it exists with {} lamports",
+                    ata,
+                    owner,
+                    mint,
+                    account.as_ref().map_or(0, |a| a.lamports)
+                )
+            },
+        )
+    }
+
+    fn check_upgrade_authority(
+        &self,
+        program_id: &Pubkey,
+        expected: Option<&Pubkey>,
+    ) -> Result<(), AssertionError> {
+        let program_data = self.get_program_data(program_id).ok_or_else(|| {
+            AssertionError::new(format!("Program {} has no programdata account", program_id))
+        })?;
+
+        ensure(program_data.upgrade_authority.as_ref() == expected, || {
+            format!(
+                "Upgrade authority mismatch for program {}. Expected: {:?}, Actual: {:?}",
+                program_id, expected, program_data.upgrade_authority
+            )
+        })
+    }
+
+    fn check_program_deployed_at(
+        &self,
+        program_id: &Pubkey,
+        expected_slot: u64,
+    ) -> Result<(), AssertionError> {
+        let program_data = self.get_program_data(program_id).ok_or_else(|| {
+            AssertionError::new(format!("Program {} has no programdata account", program_id))
+        })?;
+
+        ensure(program_data.slot == expected_slot, || {
+            format!(
+                "Deployment slot mismatch for program {}. Expected: {}, Actual: {}",
+                program_id, expected_slot, program_data.slot
+            )
+        })
+    }
+
+    fn check_sysvar<T, F>(&self, predicate: F) -> Result<(), AssertionError>
+    where
+        T: Sysvar + SysvarId + std::fmt::Debug,
+        F: FnOnce(&T) -> bool,
+    {
+        let sysvar = self.get_sysvar::<T>();
+        ensure(predicate(&sysvar), || {
+            format!(
+                "Sysvar {} did not satisfy the expected condition: {:?}",
+                std::any::type_name::<T>(),
+                sysvar
+            )
+        })
     }
 }
 
@@ -226,6 +915,7 @@ mod tests {
         svm.assert_account_exists(&nonexistent);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_assert_token_balance() {
         let mut svm = LiteSVM::new();
@@ -244,6 +934,7 @@ mod tests {
         svm.assert_token_balance(&token_account, amount);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     #[should_panic(expected = "Token balance mismatch")]
     fn test_assert_token_balance_fails() {
@@ -262,6 +953,35 @@ mod tests {
         svm.assert_token_balance(&token_account, 2000);
     }
 
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_assert_token_balances_reports_every_mismatch() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 9).unwrap();
+        let token_account = svm
+            .create_associated_token_account(&mint.pubkey(), &authority)
+            .unwrap();
+        svm.mint_to(&mint.pubkey(), &token_account, &authority, 1000)
+            .unwrap();
+        let missing = Pubkey::new_unique();
+
+        svm.assert_token_balances(&[(&token_account, 1000)]);
+
+        let panic = std::panic::catch_unwind(|| {
+            svm.assert_token_balances(&[
+                (&token_account, 5),
+                (&token_account, 1000),
+                (&missing, 0),
+            ]);
+        })
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("2 of 3 token balances mismatched"));
+        assert!(message.contains(&format!("[0] {}: expected 5, actual 1000", token_account)));
+        assert!(message.contains(&format!("[2] {}: expected 0, not found", missing)));
+    }
+
     #[test]
     fn test_assert_sol_balance() {
         let mut svm = LiteSVM::new();
@@ -282,6 +1002,61 @@ mod tests {
         svm.assert_sol_balance(&account.pubkey(), 2_000_000_000);
     }
 
+    #[test]
+    fn test_assert_sol_balance_change() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let before = svm.capture_balances(&[from.pubkey(), to]);
+        assert_eq!(before.sol_balance(&to), Some(0));
+
+        let ix = solana_program::system_instruction::transfer(&from.pubkey(), &to, 1_000_000);
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&from.pubkey()),
+            &[&from],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+
+        svm.assert_sol_balance_change(&before, &to, 1_000_000);
+        svm.assert_sol_balance_change(&before, &from.pubkey(), -1_005_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "SOL balance change mismatch")]
+    fn test_assert_sol_balance_change_fails() {
+        let mut svm = LiteSVM::new();
+        let account = svm.create_funded_account(1_000_000_000).unwrap();
+        let before = svm.capture_balances(&[account.pubkey()]);
+        svm.airdrop(&account.pubkey(), 100).unwrap();
+
+        svm.assert_sol_balance_change(&before, &account.pubkey(), 200);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_assert_token_balance_change() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let alice = svm
+            .create_associated_token_account(&mint, &authority)
+            .unwrap();
+        let bob = svm
+            .create_token_account(&mint, &authority)
+            .unwrap()
+            .pubkey();
+        svm.mint_to(&mint, &alice, &authority, 1_000).unwrap();
+
+        let before = svm.capture_balances(&[alice, bob, authority.pubkey()]);
+        assert_eq!(before.token_balance(&authority.pubkey()), None);
+        svm.transfer_tokens(&alice, &bob, &authority, 500).unwrap();
+
+        svm.assert_token_balance_change(&before, &alice, -500);
+        svm.assert_token_balance_change(&before, &bob, 500);
+    }
+
     #[test]
     fn test_assert_sol_balance_zero_for_nonexistent() {
         let svm = LiteSVM::new();
@@ -291,6 +1066,7 @@ mod tests {
         svm.assert_sol_balance(&nonexistent, 0);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_assert_mint_supply() {
         let mut svm = LiteSVM::new();
@@ -309,6 +1085,7 @@ mod tests {
         svm.assert_mint_supply(&mint.pubkey(), amount);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     #[should_panic(expected = "Mint supply mismatch")]
     fn test_assert_mint_supply_fails() {
@@ -327,6 +1104,7 @@ mod tests {
         svm.assert_mint_supply(&mint.pubkey(), 200);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_assert_mint_supply_zero_for_new_mint() {
         let mut svm = LiteSVM::new();
@@ -337,6 +1115,7 @@ mod tests {
         svm.assert_mint_supply(&mint.pubkey(), 0);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_assert_account_owner() {
         let mut svm = LiteSVM::new();
@@ -347,6 +1126,7 @@ mod tests {
         svm.assert_account_owner(&mint.pubkey(), &spl_token::id());
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     #[should_panic(expected = "Account owner mismatch")]
     fn test_assert_account_owner_fails() {
@@ -359,6 +1139,7 @@ mod tests {
         svm.assert_account_owner(&mint.pubkey(), &wrong_owner);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_assert_account_data_len() {
         let mut svm = LiteSVM::new();
@@ -369,6 +1150,7 @@ mod tests {
         svm.assert_account_data_len(&mint.pubkey(), 82);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     #[should_panic(expected = "Account data length mismatch")]
     fn test_assert_account_data_len_fails() {
@@ -380,6 +1162,7 @@ mod tests {
         svm.assert_account_data_len(&mint.pubkey(), 100);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_assert_account_data_len_token_account() {
         let mut svm = LiteSVM::new();
@@ -390,4 +1173,134 @@ mod tests {
         // Token account data is 165 bytes
         svm.assert_account_data_len(&token_account.pubkey(), 165);
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_assert_token_account_frozen() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_freezable_token_mint(&owner, 6).unwrap().pubkey();
+        let ata = svm.create_associated_token_account(&mint, &owner).unwrap();
+
+        svm.freeze_token_account(&ata, &mint, &owner).unwrap();
+        svm.assert_token_account_frozen(&ata);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    #[should_panic(expected = "to be frozen")]
+    fn test_assert_token_account_frozen_fails() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&owner, 6).unwrap().pubkey();
+        let ata = svm.create_associated_token_account(&mint, &owner).unwrap();
+
+        svm.assert_token_account_frozen(&ata);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_assert_account_bytes() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&owner, 6).unwrap();
+
+        svm.assert_account_bytes(&mint.pubkey(), 44, &[6, 1]); // decimals, is_initialized
+        svm.assert_account_bytes(&mint.pubkey(), 4, owner.pubkey().as_ref());
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    #[should_panic(expected = "Account bytes mismatch")]
+    fn test_assert_account_bytes_fails() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&owner, 6).unwrap();
+
+        svm.assert_account_bytes(&mint.pubkey(), 44, &[9]);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_assert_ata_exists_and_closed() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&owner, 9).unwrap();
+
+        svm.assert_ata_closed(&owner.pubkey(), &mint.pubkey());
+        svm.create_associated_token_account(&mint.pubkey(), &owner)
+            .unwrap();
+        svm.assert_ata_exists(&owner.pubkey(), &mint.pubkey());
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    #[should_panic(expected = "Expected associated token account")]
+    fn test_assert_ata_exists_fails() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&owner, 9).unwrap();
+
+        svm.assert_ata_exists(&owner.pubkey(), &mint.pubkey());
+    }
+
+    #[test]
+    fn test_assert_program_data() {
+        let mut svm = LiteSVM::new();
+        let program_id = Pubkey::new_unique();
+        let memo_id = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+        let elf = svm.get_account(&memo_id).unwrap().data;
+        svm.deploy_upgradeable_program(program_id, &elf, None)
+            .unwrap();
+
+        svm.assert_upgrade_authority(&program_id, None);
+        svm.assert_program_deployed_at(&program_id, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no programdata account")]
+    fn test_assert_upgrade_authority_not_upgradeable() {
+        let svm = LiteSVM::new();
+        let memo_id = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+        svm.assert_upgrade_authority(&memo_id, None);
+    }
+
+    #[test]
+    fn test_assert_clock() {
+        let mut svm = LiteSVM::new();
+        svm.advance_slot(3);
+
+        svm.assert_clock(|c| c.slot == 3);
+        svm.assert_sysvar::<solana_program::rent::Rent, _>(|r| r.lamports_per_byte_year > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "did not satisfy the expected condition")]
+    fn test_assert_clock_fails() {
+        let svm = LiteSVM::new();
+        svm.assert_clock(|c| c.slot > 100);
+    }
+
+    #[test]
+    fn test_check_variants_return_errors() {
+        let mut svm = LiteSVM::new();
+        let account = svm.create_funded_account(1_000_000).unwrap();
+        let missing = Pubkey::new_unique();
+
+        assert_eq!(svm.check_sol_balance(&account.pubkey(), 1_000_000), Ok(()));
+        let err = svm.check_sol_balance(&account.pubkey(), 5).unwrap_err();
+        assert_eq!(
+            err.message(),
+            format!(
+                "SOL balance mismatch for account {}. Expected: 5, Actual: 1000000",
+                account.pubkey()
+            )
+        );
+
+        assert!(svm.check_account_exists(&account.pubkey()).is_ok());
+        assert!(svm.check_account_closed(&missing).is_ok());
+        let err = svm.check_account_owner(&missing, &Pubkey::default()).unwrap_err();
+        assert_eq!(err.to_string(), format!("Account {} not found", missing));
+        assert!(svm.check_clock(|c| c.slot > 100).is_err());
+    }
+}
@@ -3,8 +3,24 @@
 //! This module provides a fluent API for setting up test environments
 //! with automatic program deployment and configuration.
 
+#[cfg(feature = "cluster")]
+use crate::cluster::{fetch_account, fetch_program};
+use crate::account_diff::record_account_diffs;
+use crate::faucet::set_faucet;
+use crate::fixtures::{load_fixtures, parse_account_file};
+use crate::keypairs::set_keypair_seed;
+use crate::test_helpers::TestHelpers;
+use crate::transaction::{set_default_compute_budget, ComputeBudget};
 use litesvm::LiteSVM;
+use solana_feature_set::FeatureSet;
+use solana_program::native_token::LAMPORTS_PER_SOL;
 use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use std::path::{Path, PathBuf};
+
+/// LiteSVM's default cap on collected program log bytes per transaction
+const DEFAULT_LOG_BYTES_LIMIT: Option<usize> = Some(10_000);
 
 /// Builder for creating a LiteSVM instance with programs pre-deployed
 ///
@@ -29,6 +45,21 @@ use solana_program::pubkey::Pubkey;
 pub struct LiteSVMBuilder {
     svm: LiteSVM,
     programs: Vec<(Pubkey, Vec<u8>)>,
+    rent: Option<Rent>,
+    faucet: Option<(String, Pubkey, u64)>,
+    #[cfg(feature = "cluster")]
+    cloned_accounts: Vec<(String, Pubkey)>,
+    #[cfg(feature = "cluster")]
+    cloned_programs: Vec<(String, Pubkey)>,
+    fixture_dirs: Vec<PathBuf>,
+    account_files: Vec<(Option<Pubkey>, PathBuf)>,
+    account_diffs: bool,
+    compute_budget: Option<ComputeBudget>,
+    features: Vec<(Pubkey, bool)>,
+    keypair_seed: Option<u64>,
+    sigverify: bool,
+    blockhash_check: bool,
+    log_bytes_limit: Option<usize>,
 }
 
 impl LiteSVMBuilder {
@@ -37,9 +68,212 @@ impl LiteSVMBuilder {
         Self {
             svm: LiteSVM::new(),
             programs: Vec::new(),
+            rent: None,
+            faucet: None,
+            #[cfg(feature = "cluster")]
+            cloned_accounts: Vec::new(),
+            #[cfg(feature = "cluster")]
+            cloned_programs: Vec::new(),
+            fixture_dirs: Vec::new(),
+            account_files: Vec::new(),
+            account_diffs: false,
+            compute_budget: None,
+            features: Vec::new(),
+            keypair_seed: None,
+            sigverify: true,
+            blockhash_check: true,
+            log_bytes_limit: DEFAULT_LOG_BYTES_LIMIT,
         }
     }
 
+    /// Enable or disable transaction signature verification
+    ///
+    /// Signature verification is enabled by default. Disabling it lets tests
+    /// submit transactions without valid signatures.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().with_sigverify(false).build();
+    /// ```
+    pub fn with_sigverify(mut self, sigverify: bool) -> Self {
+        self.sigverify = sigverify;
+        self
+    }
+
+    /// Load the SPL program binaries bundled with LiteSVM
+    ///
+    /// Loads SPL Token, Token-2022, Associated Token Account and Memo at their
+    /// canonical addresses, so programs that CPI into them work without embedding
+    /// their `.so` files. `LiteSVM::new()` already loads them, so this only makes the
+    /// dependency explicit. Programs added with
+    /// [`deploy_program`](Self::deploy_program) are deployed afterwards and win.
+    ///
+    /// The Token Metadata program can't be preloaded: neither LiteSVM nor this crate
+    /// ships its binary. Dump it from a cluster and load it with
+    /// [`with_token_metadata_program`](Self::with_token_metadata_program).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().with_spl_programs().build();
+    /// ```
+    pub fn with_spl_programs(mut self) -> Self {
+        self.svm = std::mem::take(&mut self.svm).with_spl_programs();
+        self
+    }
+
+    /// Enable or disable the recent blockhash check
+    ///
+    /// The check is enabled by default. Disabling it lets transactions built
+    /// with stale or arbitrary blockhashes be processed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().with_blockhash_check(false).build();
+    /// ```
+    pub fn with_blockhash_check(mut self, check: bool) -> Self {
+        self.blockhash_check = check;
+        self
+    }
+
+    /// Set the rent rate in lamports per byte-year
+    ///
+    /// The remaining `Rent` parameters keep their default values, or those passed to
+    /// [`with_rent`](Self::with_rent). Affects `minimum_balance_for_rent_exemption`
+    /// and every helper built on it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().with_lamports_per_byte_year(1).build();
+    /// ```
+    pub fn with_lamports_per_byte_year(mut self, lamports_per_byte_year: u64) -> Self {
+        self.rent = Some(Rent {
+            lamports_per_byte_year,
+            ..self.rent.unwrap_or_default()
+        });
+        self
+    }
+
+    /// Replace the Rent sysvar
+    ///
+    /// Programs that read `Rent::get()` and the rent-exemption checks LiteSVM runs
+    /// after each transaction both see these parameters.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new()
+    ///     .with_rent(Rent {
+    ///         lamports_per_byte_year: 1_000,
+    ///         exemption_threshold: 1.0,
+    ///         burn_percent: 0,
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn with_rent(mut self, rent: Rent) -> Self {
+        self.rent = Some(rent);
+        self
+    }
+
+    /// Make every account rent-exempt, whatever its balance
+    ///
+    /// Sets a zero rent rate, so `minimum_balance_for_rent_exemption` is 1 lamport
+    /// and accounts can be created or left with any non-zero balance. Use it for
+    /// programs written for rent-paying accounts, or to keep rent out of lamport
+    /// arithmetic in tests.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().rent_free().build();
+    /// ```
+    pub fn rent_free(self) -> Self {
+        self.with_rent(Rent {
+            lamports_per_byte_year: 0,
+            exemption_threshold: 0.0,
+            burn_percent: 0,
+        })
+    }
+
+    /// Limit how many bytes of program logs are collected per transaction
+    ///
+    /// LiteSVM truncates logs at 10,000 bytes by default. Pass `None` to
+    /// collect everything, or a smaller limit to keep large scenarios quiet. Once
+    /// the limit is hit the remaining logs are replaced by a single
+    /// `"Log truncated"` entry.
+    ///
+    /// LiteSVM has no log level setting: programs always log everything they emit.
+    /// To control how much of it is printed while a test runs, use
+    /// [`LogFilter`](crate::LogFilter).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().with_log_bytes_limit(None).build();
+    /// ```
+    pub fn with_log_bytes_limit(mut self, limit: Option<usize>) -> Self {
+        self.log_bytes_limit = limit;
+        self
+    }
+
+    /// Activate a runtime feature that is off in the VM's feature set
+    ///
+    /// LiteSVM starts with every feature known to its runtime enabled, so this is
+    /// mostly useful to undo an earlier [`with_feature_disabled`](Self::with_feature_disabled).
+    /// Features are applied in the order they are given.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new()
+    ///     .with_feature_enabled(solana_feature_set::enable_secp256r1_precompile::id())
+    ///     .build();
+    /// ```
+    pub fn with_feature_enabled(mut self, feature_id: Pubkey) -> Self {
+        self.features.push((feature_id, true));
+        self
+    }
+
+    /// Deactivate a runtime feature, to test against a cluster that hasn't activated it
+    ///
+    /// Builtins and precompiles gated behind the feature aren't loaded, and runtime
+    /// behavior switched by it falls back to the old path. The VM is recreated with
+    /// the adjusted feature set when it is built, before anything else is loaded.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new()
+    ///     .with_feature_disabled(solana_feature_set::enable_secp256r1_precompile::id())
+    ///     .build();
+    /// ```
+    pub fn with_feature_disabled(mut self, feature_id: Pubkey) -> Self {
+        self.features.push((feature_id, false));
+        self
+    }
+
+    /// Pay all helper airdrops from a named faucet holding at most `cap` lamports
+    ///
+    /// The faucet account is funded with `cap` on build. Helpers such as
+    /// `create_funded_account` then move lamports out of it and fail with a
+    /// "cap exceeded" error once it runs dry. See [`crate::faucet`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let faucet = Keypair::new();
+    /// let svm = LiteSVMBuilder::new()
+    ///     .with_faucet("deployer", &faucet, 50 * LAMPORTS_PER_SOL)
+    ///     .build();
+    /// ```
+    pub fn with_faucet(mut self, name: &str, faucet: &Keypair, cap: u64) -> Self {
+        self.faucet = Some((name.to_string(), faucet.pubkey(), cap));
+        self
+    }
+
     /// Add a program to be deployed
     ///
     /// Programs are deployed in the order they are added.
@@ -59,6 +293,158 @@ impl LiteSVMBuilder {
         self
     }
 
+    /// Deploy the Metaplex Token Metadata program from its binary
+    ///
+    /// The binary isn't bundled with this crate; see the [`nft`](crate::nft) module.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new()
+    ///     .with_token_metadata_program(include_bytes!("fixtures/mpl_token_metadata.so"))
+    ///     .build();
+    /// ```
+    #[cfg(feature = "spl")]
+    pub fn with_token_metadata_program(self, program_bytes: &[u8]) -> Self {
+        self.deploy_program(crate::nft::TOKEN_METADATA_PROGRAM_ID, program_bytes)
+    }
+
+    /// Copy an account from a live cluster into the VM at build time
+    ///
+    /// `rpc_url` is an RPC endpoint or one of the `mainnet-beta`, `devnet` and
+    /// `testnet` monikers. The account keeps its address, lamports, owner and data.
+    /// [`build`](Self::build) panics if the account can't be fetched. Requires the
+    /// `cluster` feature.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let usdc = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+    /// let svm = LiteSVMBuilder::new().clone_account_from("mainnet-beta", usdc).build();
+    /// ```
+    #[cfg(feature = "cluster")]
+    pub fn clone_account_from(mut self, rpc_url: &str, pubkey: Pubkey) -> Self {
+        self.cloned_accounts.push((rpc_url.to_string(), pubkey));
+        self
+    }
+
+    /// Copy an executable program from a live cluster into the VM at build time
+    ///
+    /// Upgradeable programs are deployed with the upgradeable loader and keep their
+    /// upgrade authority. Programs added with [`deploy_program`](Self::deploy_program)
+    /// are deployed afterwards, so a local build can override a cloned program.
+    /// [`build`](Self::build) panics if the program can't be fetched. Requires the
+    /// `cluster` feature.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new()
+    ///     .clone_program_from("devnet", oracle_program_id)
+    ///     .clone_account_from("devnet", price_feed)
+    ///     .build();
+    /// ```
+    #[cfg(feature = "cluster")]
+    pub fn clone_program_from(mut self, rpc_url: &str, program_id: Pubkey) -> Self {
+        self.cloned_programs.push((rpc_url.to_string(), program_id));
+        self
+    }
+
+    /// Load every account fixture in `dir` at build time
+    ///
+    /// See the [`fixtures`](crate::fixtures) module for the file format. Fixtures are
+    /// loaded after cloned accounts, so they win when both set the same address.
+    /// [`build`](Self::build) panics if a fixture can't be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new().with_fixtures("tests/fixtures/pool").build();
+    /// ```
+    pub fn with_fixtures(mut self, dir: impl AsRef<Path>) -> Self {
+        self.fixture_dirs.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Load an account from a `solana account --output json` dump at build time
+    ///
+    /// The account is written at the `pubkey` recorded in the file. Files prepared
+    /// for `solana-test-validator --account` are accepted as they are; use
+    /// [`add_account_from_file_at`](Self::add_account_from_file_at) for files without
+    /// a `pubkey`. [`build`](Self::build) panics if the file can't be read.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let svm = LiteSVMBuilder::new()
+    ///     .add_account_from_file("tests/fixtures/usdc_mint.json")
+    ///     .build();
+    /// ```
+    pub fn add_account_from_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.account_files.push((None, path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Load an account from a JSON dump at build time, writing it at `address`
+    ///
+    /// Matches `solana-test-validator --account <ADDRESS> <FILE>`: any `pubkey` in
+    /// the file is ignored.
+    pub fn add_account_from_file_at(mut self, address: Pubkey, path: impl AsRef<Path>) -> Self {
+        self.account_files
+            .push((Some(address), path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Record account diffs for every transaction sent through the helpers
+    ///
+    /// Calls [`record_account_diffs`] on the built VM, so each
+    /// [`TransactionResult`](crate::TransactionResult) carries the before/after state
+    /// of its writable accounts in
+    /// [`account_diffs`](crate::TransactionResult::account_diffs).
+    pub fn with_account_diffs(mut self) -> Self {
+        self.account_diffs = true;
+        self
+    }
+
+    /// Prepend a compute budget to every transaction sent through the helpers
+    ///
+    /// Calls [`set_default_compute_budget`] on the built VM. `limit` is the
+    /// compute unit limit for the whole transaction and `price` the priority fee in
+    /// micro-lamports per compute unit. Transactions that set their own budget keep it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut svm = LiteSVMBuilder::new()
+    ///     .deploy_program(program_id, program_bytes)
+    ///     .with_default_compute_budget(1_400_000, 0)
+    ///     .build();
+    /// ```
+    pub fn with_default_compute_budget(mut self, limit: u32, price: u64) -> Self {
+        self.compute_budget = Some(
+            ComputeBudget::new()
+                .with_compute_unit_limit(limit)
+                .with_compute_unit_price(price),
+        );
+        self
+    }
+
+    /// Create helper keypairs from a sequence seeded with `seed`
+    ///
+    /// Calls [`set_keypair_seed`] on the built VM, so accounts created by the
+    /// helpers get the same pubkeys on every run. See [`crate::keypairs`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut svm = LiteSVMBuilder::new().with_keypair_seed(42).build();
+    /// let user = svm.create_funded_account(LAMPORTS_PER_SOL)?; // same pubkey every run
+    /// ```
+    pub fn with_keypair_seed(mut self, seed: u64) -> Self {
+        self.keypair_seed = Some(seed);
+        self
+    }
+
     /// Build the LiteSVM instance with all programs deployed
     ///
     /// # Returns
@@ -70,12 +456,106 @@ impl LiteSVMBuilder {
     /// ```ignore
     /// let mut svm = builder.build();
     /// ```
+    ///
+    /// Cloned accounts, fixtures and account files are direct writes, recorded in the
+    /// [`surgery_report`](crate::surgery_report) at the line that called `build`.
+    #[track_caller]
     pub fn build(mut self) -> LiteSVM {
+        if !self.features.is_empty() {
+            let mut feature_set = FeatureSet::all_enabled();
+            for (feature_id, enabled) in &self.features {
+                if *enabled {
+                    feature_set.activate(feature_id, 0);
+                } else {
+                    feature_set.deactivate(feature_id);
+                }
+            }
+            // Builtins and precompiles are only loaded if their feature is active
+            self.svm = LiteSVM::default()
+                .with_feature_set(feature_set)
+                .with_builtins()
+                .with_lamports(1_000_000 * LAMPORTS_PER_SOL)
+                .with_sysvars()
+                .with_precompiles()
+                .with_spl_programs();
+        }
+        self.svm = self
+            .svm
+            .with_sigverify(self.sigverify)
+            .with_blockhash_check(self.blockhash_check)
+            .with_log_bytes_limit(self.log_bytes_limit);
+
+        if let Some(rent) = &self.rent {
+            self.svm.set_sysvar(rent);
+        }
+
+        #[cfg(feature = "cluster")]
+        for (rpc_url, pubkey) in &self.cloned_accounts {
+            let account = fetch_account(rpc_url, pubkey)
+                .unwrap_or_else(|e| panic!("Failed to clone account {}: {}", pubkey, e));
+            self.svm
+                .unsafe_state()
+                .write("clone_account", pubkey, account)
+                .unwrap_or_else(|e| panic!("Failed to clone account {}: {}", pubkey, e));
+        }
+
+        for dir in &self.fixture_dirs {
+            let fixtures = load_fixtures(dir).unwrap_or_else(|e| panic!("{}", e));
+            for (pubkey, account) in fixtures {
+                self.svm
+                    .unsafe_state()
+                    .write("load_fixture", &pubkey, account)
+                    .unwrap_or_else(|e| panic!("Failed to load fixture {}: {}", pubkey, e));
+            }
+        }
+
+        for (address, path) in &self.account_files {
+            let (pubkey, account) = parse_account_file(path)
+                .map_err(|e| e.to_string())
+                .and_then(|(pubkey, account)| {
+                    let pubkey = address.or(pubkey).ok_or("Missing 'pubkey'")?;
+                    Ok((pubkey, account))
+                })
+                .unwrap_or_else(|e| panic!("Invalid account file {}: {}", path.display(), e));
+            self.svm
+                .unsafe_state()
+                .write("load_account_file", &pubkey, account)
+                .unwrap_or_else(|e| panic!("Failed to load account {}: {}", pubkey, e));
+        }
+
+        #[cfg(feature = "cluster")]
+        for (rpc_url, program_id) in &self.cloned_programs {
+            let program = fetch_program(rpc_url, program_id)
+                .unwrap_or_else(|e| panic!("Failed to clone program {}: {}", program_id, e));
+            if program.upgradeable {
+                let authority = program.upgrade_authority;
+                self.svm
+                    .deploy_upgradeable_program(*program_id, &program.elf, authority)
+                    .unwrap_or_else(|e| panic!("Failed to clone program {}: {}", program_id, e));
+            } else {
+                self.svm.add_program(*program_id, &program.elf);
+            }
+        }
+
         // Deploy all programs
         for (program_id, program_bytes) in self.programs {
             self.svm.add_program(program_id, &program_bytes);
         }
 
+        if let Some((name, pubkey, cap)) = &self.faucet {
+            self.svm.airdrop(pubkey, *cap).expect("Failed to fund faucet");
+            set_faucet(&mut self.svm, name, *pubkey);
+        }
+        if self.account_diffs {
+            record_account_diffs(&mut self.svm, true);
+        }
+        if let Some(seed) = self.keypair_seed {
+            set_keypair_seed(&mut self.svm, Some(seed));
+        }
+        if let Some(budget) = self.compute_budget {
+            set_default_compute_budget(&mut self.svm, Some(budget));
+        }
+
         self.svm
     }
 
@@ -235,4 +715,207 @@ mod tests {
         // Verify all 3 programs were added
         assert_eq!(builder.programs.len(), 3);
     }
+
+    #[test]
+    fn test_builder_with_sigverify() {
+        let svm = LiteSVMBuilder::new().with_sigverify(false).build();
+        assert!(!svm.get_sigverify());
+
+        let svm = LiteSVMBuilder::new().build();
+        assert!(svm.get_sigverify());
+    }
+
+    #[test]
+    fn test_builder_with_blockhash_check_disabled() {
+        use solana_sdk::hash::Hash;
+        use solana_sdk::signature::{Keypair, Signer};
+        use solana_sdk::transaction::Transaction;
+
+        let mut svm = LiteSVMBuilder::new().with_blockhash_check(false).build();
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+        // A transaction with an unknown blockhash is accepted when the check is off
+        let ix = solana_program::system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000_000,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::new_unique(),
+        );
+        assert!(svm.send_transaction(tx).is_ok());
+    }
+
+    #[test]
+    fn test_builder_with_lamports_per_byte_year() {
+        let default_svm = LiteSVMBuilder::new().build();
+        let svm = LiteSVMBuilder::new().with_lamports_per_byte_year(1).build();
+
+        assert_eq!(svm.get_sysvar::<Rent>().lamports_per_byte_year, 1);
+        assert!(
+            svm.minimum_balance_for_rent_exemption(100)
+                < default_svm.minimum_balance_for_rent_exemption(100)
+        );
+    }
+
+    #[test]
+    fn test_builder_with_rent() {
+        let rent = Rent {
+            lamports_per_byte_year: 10,
+            exemption_threshold: 1.0,
+            burn_percent: 0,
+        };
+        let svm = LiteSVMBuilder::new()
+            .with_rent(rent.clone())
+            .with_lamports_per_byte_year(20)
+            .build();
+        assert_eq!(
+            svm.get_sysvar::<Rent>(),
+            Rent {
+                lamports_per_byte_year: 20,
+                ..rent
+            }
+        );
+    }
+
+    #[test]
+    fn test_builder_rent_free() {
+        use crate::transaction::TransactionHelpers;
+
+        let mut svm = LiteSVMBuilder::new().rent_free().build();
+        assert_eq!(svm.minimum_balance_for_rent_exemption(1_000), 1);
+
+        // A 1 lamport account would be below the exemption threshold by default
+        let payer = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let ix = solana_program::system_instruction::transfer(&payer.pubkey(), &to, 1);
+        svm.send_instruction(ix, &[&payer]).unwrap().assert_success();
+        assert_eq!(svm.get_balance(&to), Some(1));
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_builder_with_spl_programs() {
+        let svm = LiteSVMBuilder::new().with_spl_programs().build();
+        for program_id in [
+            spl_token::id(),
+            spl_token_2022::id(),
+            spl_associated_token_account::id(),
+        ] {
+            assert!(svm.get_account(&program_id).unwrap().executable);
+        }
+    }
+
+    #[test]
+    fn test_builder_with_feature_disabled() {
+        let secp256r1 = solana_program::pubkey!("Secp256r1SigVerify1111111111111111111111111");
+        let feature = solana_feature_set::enable_secp256r1_precompile::id();
+
+        let svm = LiteSVMBuilder::new()
+            .with_sigverify(false)
+            .with_feature_disabled(feature)
+            .build();
+        assert!(svm.get_account(&secp256r1).is_none());
+        assert!(!svm.get_sigverify());
+        // The rest of the default VM is still there
+        assert!(svm.get_account(&solana_sdk::ed25519_program::id()).is_some());
+
+        let svm = LiteSVMBuilder::new()
+            .with_feature_disabled(feature)
+            .with_feature_enabled(feature)
+            .build();
+        assert!(svm.get_account(&secp256r1).is_some());
+    }
+
+    #[test]
+    fn test_builder_with_keypair_seed() {
+        let mut first_vm = LiteSVMBuilder::new().with_keypair_seed(42).build();
+        let first = first_vm.create_funded_account(1_000_000_000).unwrap().pubkey();
+
+        let mut svm = LiteSVMBuilder::new().with_keypair_seed(42).build();
+        assert_eq!(
+            svm.create_funded_account(1_000_000_000).unwrap().pubkey(),
+            first
+        );
+        // Building the second VM didn't rewind the first one's sequence
+        assert_ne!(
+            first_vm.create_funded_account(1_000_000_000).unwrap().pubkey(),
+            first
+        );
+
+        let alice = svm.create_funded_account_with_seed("alice", 1_000).unwrap();
+        assert_eq!(alice.pubkey(), crate::keypairs::keypair_from_name("alice").pubkey());
+        assert_eq!(svm.get_balance(&alice.pubkey()), Some(1_000));
+
+        // Without a seed the VM goes back to random keypairs
+        let mut svm = LiteSVMBuilder::new().build();
+        assert_ne!(
+            svm.create_funded_account(1_000_000_000).unwrap().pubkey(),
+            first
+        );
+    }
+
+    #[test]
+    fn test_builder_with_log_bytes_limit() {
+        use crate::transaction::TransactionHelpers;
+        use solana_program::instruction::Instruction;
+
+        // The memo program logs the whole memo, well past 100 bytes
+        let memo_id = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+        let memo = Instruction::new_with_bytes(memo_id, &[b'a'; 400], vec![]);
+
+        for (limit, truncated) in [(Some(100), true), (None, false)] {
+            let mut svm = LiteSVMBuilder::new().with_log_bytes_limit(limit).build();
+            let payer = svm.create_funded_account(LAMPORTS_PER_SOL).unwrap();
+
+            let result = svm.send_instruction(memo.clone(), &[&payer]).unwrap();
+            result.assert_success();
+            assert_eq!(result.has_log("Log truncated"), truncated, "limit {limit:?}");
+            assert_eq!(result.has_log(&"a".repeat(400)), !truncated, "limit {limit:?}");
+        }
+    }
+
+    #[test]
+    fn test_builder_with_account_diffs() {
+        use crate::account_diff::recording_account_diffs;
+
+        let svm = LiteSVMBuilder::new().with_account_diffs().build();
+        let other = LiteSVMBuilder::new().build();
+        assert!(recording_account_diffs(&svm));
+        assert!(!recording_account_diffs(&other));
+    }
+
+    #[test]
+    fn test_builder_with_default_compute_budget() {
+        use crate::transaction::TransactionHelpers;
+        use solana_program::system_instruction;
+
+        const INVOKE: &str = "ComputeBudget111111111111111111111111111111 invoke [1]";
+        let mut svm = LiteSVMBuilder::new()
+            .with_default_compute_budget(300_000, 1)
+            .build();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000);
+
+        let result = svm.send_instruction(ix.clone(), &[&payer]).unwrap();
+        result.assert_success();
+        assert_eq!(result.log_count(INVOKE), 2);
+
+        // A per-call budget replaces the default instead of doubling up
+        let budget = ComputeBudget::new().with_compute_unit_limit(250_000);
+        let result = svm
+            .send_instruction_with_budget(ix, &[&payer], &budget)
+            .unwrap();
+        result.assert_success();
+        assert_eq!(result.log_count(INVOKE), 1);
+
+        // Other VMs keep their own default
+        let _other = LiteSVMBuilder::new().build();
+        assert!(crate::transaction::default_compute_budget(&svm).is_some());
+        assert_eq!(crate::transaction::default_compute_budget(&LiteSVM::new()), None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,177 @@
+//! Fetching accounts and programs from a live cluster
+//!
+//! Used by [`LiteSVMBuilder::clone_account_from`] and
+//! [`LiteSVMBuilder::clone_program_from`] to fork real state, such as a live mint or
+//! oracle, into a test. Requests go through the cluster's JSON-RPC API with
+//! `getAccountInfo`; `rpc_url` is either a full URL or one of the `mainnet-beta`,
+//! `devnet` and `testnet` monikers.
+//!
+//! Only available with the `cluster` feature, which pulls in a blocking HTTP client.
+//!
+//! [`LiteSVMBuilder::clone_account_from`]: crate::LiteSVMBuilder::clone_account_from
+//! [`LiteSVMBuilder::clone_program_from`]: crate::LiteSVMBuilder::clone_program_from
+
+use crate::fixtures::account_from_json;
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::error::Error;
+
+/// An executable program fetched with [`fetch_program`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClonedProgram {
+    /// The program's ELF binary
+    pub elf: Vec<u8>,
+    /// Whether the program is deployed with the upgradeable loader
+    pub upgradeable: bool,
+    /// Upgrade authority, `None` if the program is immutable or not upgradeable
+    pub upgrade_authority: Option<Pubkey>,
+}
+
+/// Expand a cluster moniker to its public RPC URL
+pub fn resolve_rpc_url(rpc_url: &str) -> &str {
+    match rpc_url {
+        "mainnet-beta" | "mainnet" | "m" => "https://api.mainnet-beta.solana.com",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        url => url,
+    }
+}
+
+/// Fetch an account from the cluster at `rpc_url`
+///
+/// # Errors
+///
+/// Fails if the request fails or the account doesn't exist
+pub fn fetch_account(rpc_url: &str, pubkey: &Pubkey) -> Result<Account, Box<dyn Error>> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getAccountInfo",
+        "params": [pubkey.to_string(), { "encoding": "base64" }],
+    });
+    let response: serde_json::Value = reqwest::blocking::Client::new()
+        .post(resolve_rpc_url(rpc_url))
+        .json(&request)
+        .send()
+        .and_then(|response| response.json())
+        .map_err(|e| format!("Failed to fetch account {} from {}: {}", pubkey, rpc_url, e))?;
+
+    parse_account_info(&response)?
+        .ok_or_else(|| format!("Account {} not found on {}", pubkey, rpc_url).into())
+}
+
+/// Fetch an executable program, following upgradeable programs to their programdata
+///
+/// # Errors
+///
+/// Fails if the request fails or `program_id` isn't an executable program
+pub fn fetch_program(rpc_url: &str, program_id: &Pubkey) -> Result<ClonedProgram, Box<dyn Error>> {
+    let account = fetch_account(rpc_url, program_id)?;
+    if !account.executable {
+        return Err(format!("Account {} is not an executable program", program_id).into());
+    }
+    if account.owner != bpf_loader_upgradeable::id() {
+        return Ok(ClonedProgram {
+            elf: account.data,
+            upgradeable: false,
+            upgrade_authority: None,
+        });
+    }
+
+    let UpgradeableLoaderState::Program {
+        programdata_address,
+    } = bincode::deserialize(&account.data)?
+    else {
+        return Err(format!("Program {} has no programdata account", program_id).into());
+    };
+    let programdata = fetch_account(rpc_url, &programdata_address)?;
+    program_from_programdata(&programdata.data)
+        .ok_or_else(|| format!("Invalid programdata account for program {}", program_id).into())
+}
+
+/// Split a programdata account into its upgrade authority and ELF
+fn program_from_programdata(data: &[u8]) -> Option<ClonedProgram> {
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    let UpgradeableLoaderState::ProgramData {
+        upgrade_authority_address,
+        ..
+    } = bincode::deserialize(data.get(..metadata_len)?).ok()?
+    else {
+        return None;
+    };
+    Some(ClonedProgram {
+        elf: data[metadata_len..].to_vec(),
+        upgradeable: true,
+        upgrade_authority: upgrade_authority_address,
+    })
+}
+
+/// Parse a `getAccountInfo` response with base64 encoding
+///
+/// Returns `Ok(None)` for an account that doesn't exist.
+fn parse_account_info(response: &serde_json::Value) -> Result<Option<Account>, Box<dyn Error>> {
+    if let Some(error) = response.get("error") {
+        return Err(format!("RPC error: {}", error).into());
+    }
+    let value = &response["result"]["value"];
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    account_from_json(value).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_account_info() {
+        let owner = Pubkey::new_unique();
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": {
+                "context": { "slot": 1 },
+                "value": {
+                    "data": ["AQIDBA==", "base64"],
+                    "executable": false,
+                    "lamports": 1_461_600,
+                    "owner": owner.to_string(),
+                    "rentEpoch": u64::MAX,
+                    "space": 4,
+                },
+            },
+            "id": 1,
+        });
+        let account = parse_account_info(&response).unwrap().unwrap();
+        assert_eq!(account.data, vec![1, 2, 3, 4]);
+        assert_eq!(account.lamports, 1_461_600);
+        assert_eq!(account.owner, owner);
+        assert_eq!(account.rent_epoch, u64::MAX);
+
+        let missing = serde_json::json!({ "result": { "context": { "slot": 1 }, "value": null } });
+        assert_eq!(parse_account_info(&missing).unwrap(), None);
+
+        let error = serde_json::json!({ "error": { "code": -32602, "message": "Invalid param" } });
+        assert!(parse_account_info(&error).is_err());
+    }
+
+    #[test]
+    fn test_program_from_programdata() {
+        let authority = Pubkey::new_unique();
+        let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 42,
+            upgrade_authority_address: Some(authority),
+        })
+        .unwrap();
+        data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+        data.extend_from_slice(b"\x7fELF");
+
+        let program = program_from_programdata(&data).unwrap();
+        assert_eq!(program.elf, b"\x7fELF");
+        assert!(program.upgradeable);
+        assert_eq!(program.upgrade_authority, Some(authority));
+        assert_eq!(resolve_rpc_url("devnet"), "https://api.devnet.solana.com");
+    }
+}
@@ -0,0 +1,245 @@
+//! Compute unit regression tracking against a checked-in baseline
+//!
+//! [`TransactionResult::assert_cu_within_baseline`] compares the compute units an
+//! instruction used with the value recorded for its name in a JSON baseline file,
+//! and fails the test when usage grew by more than the given tolerance. Commit the
+//! file so a review shows every CU change next to the code that caused it.
+//!
+//! - The baseline defaults to `cu_baseline.json` in the directory tests run from
+//!   (the package root under `cargo test`); [`CU_BASELINE_ENV`] points elsewhere.
+//! - Names missing from the file are recorded on first use instead of failing.
+//! - With [`CU_BASELINE_UPDATE_ENV`] set, every tracked instruction overwrites its
+//!   entry, which is how an intended change (or an improvement) is accepted.
+//!
+//! ```ignore
+//! let result = ctx.execute_instruction(make_ix, &[&maker])?;
+//! result.assert_cu_within_baseline("make", 0.05); // allow 5% above the baseline
+//! ```
+//!
+//! ```text
+//! LITESVM_CU_BASELINE_UPDATE=1 cargo test
+//! ```
+//!
+//! [`TransactionResult::assert_cu_within_baseline`]: crate::TransactionResult::assert_cu_within_baseline
+
+use crate::assertions::AssertionError;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Environment variable that overrides the baseline file path
+pub const CU_BASELINE_ENV: &str = "LITESVM_CU_BASELINE";
+
+/// Environment variable that rewrites baseline entries instead of checking them
+pub const CU_BASELINE_UPDATE_ENV: &str = "LITESVM_CU_BASELINE_UPDATE";
+
+/// Baseline file used when [`CU_BASELINE_ENV`] isn't set
+pub const DEFAULT_CU_BASELINE_FILE: &str = "cu_baseline.json";
+
+// Tests run on parallel threads but share one baseline file
+static BASELINE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Errors reading or writing a baseline file
+#[derive(Error, Debug)]
+pub enum CuBaselineError {
+    #[error("Failed to access CU baseline {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid CU baseline {}: {message}", path.display())]
+    Invalid { path: PathBuf, message: String },
+}
+
+/// Recorded compute units per instruction name
+///
+/// Stored as a JSON object sorted by name, so the file diffs cleanly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CuBaseline {
+    entries: BTreeMap<String, u64>,
+}
+
+impl CuBaseline {
+    /// Create an empty baseline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a baseline file; a missing file is an empty baseline
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CuBaselineError> {
+        let path = path.as_ref();
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(source) => {
+                return Err(CuBaselineError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let entries = serde_json::from_str(&contents).map_err(|e| CuBaselineError::Invalid {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Ok(Self { entries })
+    }
+
+    /// Write the baseline to `path`, replacing the file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CuBaselineError> {
+        let path = path.as_ref();
+        let json =
+            serde_json::to_string_pretty(&self.entries).map_err(|e| CuBaselineError::Invalid {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        std::fs::write(path, json + "\n").map_err(|source| CuBaselineError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Get the compute units recorded for `name`
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.entries.get(name).copied()
+    }
+
+    /// Record `units` for `name`, returning the previous value
+    pub fn record(&mut self, name: &str, units: u64) -> Option<u64> {
+        self.entries.insert(name.to_string(), units)
+    }
+
+    /// Iterate over the recorded names and compute units, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.entries
+            .iter()
+            .map(|(name, units)| (name.as_str(), *units))
+    }
+
+    /// Check that `used` is at most `tolerance` (a fraction, `0.05` for 5%) above
+    /// the baseline for `name`
+    ///
+    /// # Errors
+    ///
+    /// Fails if `name` has no baseline or `used` exceeds the allowance
+    pub fn check(&self, name: &str, used: u64, tolerance: f64) -> Result<(), AssertionError> {
+        let baseline = self.get(name).ok_or_else(|| {
+            AssertionError::new(format!("No CU baseline recorded for '{}'", name))
+        })?;
+        let allowed = (baseline as f64 * (1.0 + tolerance)).floor() as u64;
+        if used <= allowed {
+            return Ok(());
+        }
+        Err(AssertionError::new(format!(
+            "Instruction '{}' regressed against its CU baseline: used {} CU, baseline {} CU \
+             (+{}, {:+.1}%), tolerance {:.1}%. Set {}=1 to accept the new value",
+            name,
+            used,
+            baseline,
+            used - baseline,
+            (used - baseline) as f64 * 100.0 / baseline.max(1) as f64,
+            tolerance * 100.0,
+            CU_BASELINE_UPDATE_ENV
+        )))
+    }
+}
+
+/// Path of the baseline file, from [`CU_BASELINE_ENV`] or the default
+pub fn cu_baseline_path() -> PathBuf {
+    std::env::var_os(CU_BASELINE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CU_BASELINE_FILE))
+}
+
+/// Check whether baseline entries are being rewritten
+pub fn cu_baseline_update_enabled() -> bool {
+    std::env::var(CU_BASELINE_UPDATE_ENV).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Compare `used` with the baseline for `name` in the baseline file
+///
+/// Records `used` instead when `name` is new or updating is enabled.
+pub fn track_compute_units(name: &str, used: u64, tolerance: f64) -> Result<(), AssertionError> {
+    track_in(
+        &cu_baseline_path(),
+        name,
+        used,
+        tolerance,
+        cu_baseline_update_enabled(),
+    )
+}
+
+fn track_in(
+    path: &Path,
+    name: &str,
+    used: u64,
+    tolerance: f64,
+    update: bool,
+) -> Result<(), AssertionError> {
+    // A test that panicked while holding the lock left the file intact
+    let _guard = BASELINE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut baseline = CuBaseline::load(path).map_err(|e| AssertionError::new(e.to_string()))?;
+    if !update && baseline.get(name).is_some() {
+        return baseline.check(name, used, tolerance);
+    }
+    if baseline.record(name, used) != Some(used) {
+        baseline
+            .save(path)
+            .map_err(|e| AssertionError::new(e.to_string()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_baseline() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "cu-baseline-{}.json",
+            solana_program::pubkey::Pubkey::new_unique()
+        ))
+    }
+
+    #[test]
+    fn test_track_records_then_checks() {
+        let path = temp_baseline();
+
+        // First run records the baseline
+        track_in(&path, "make", 10_000, 0.05, false).unwrap();
+        assert_eq!(CuBaseline::load(&path).unwrap().get("make"), Some(10_000));
+
+        // Within tolerance, and improvements, pass without touching the file
+        track_in(&path, "make", 10_500, 0.05, false).unwrap();
+        track_in(&path, "make", 9_000, 0.05, false).unwrap();
+        assert_eq!(CuBaseline::load(&path).unwrap().get("make"), Some(10_000));
+
+        let error = track_in(&path, "make", 10_501, 0.05, false).unwrap_err();
+        assert!(error
+            .message()
+            .starts_with("Instruction 'make' regressed against its CU baseline: used 10501 CU"));
+
+        // Updating accepts the new value
+        track_in(&path, "make", 10_501, 0.05, true).unwrap();
+        track_in(&path, "take", 20_000, 0.0, false).unwrap();
+        let baseline = CuBaseline::load(&path).unwrap();
+        assert_eq!(
+            baseline.iter().collect::<Vec<_>>(),
+            vec![("make", 10_501), ("take", 20_000)]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_baseline_invalid_file() {
+        let path = temp_baseline();
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            CuBaseline::load(&path),
+            Err(CuBaselineError::Invalid { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+//! Fluent account expectations
+//!
+//! An alternative to a run of separate `assert_*` calls about the same account:
+//! [`expect`] starts a chain that reads left to right and panics at the first
+//! expectation that doesn't hold, with the same message as the matching
+//! [`AssertionHelpers`] method.
+//!
+//! ```ignore
+//! expect(&svm, &vault_ata)
+//!     .to_exist()
+//!     .owned_by(&spl_token::id())
+//!     .with_lamports(2_039_280)
+//!     .as_token_account()
+//!     .with_mint(&mint)
+//!     .with_balance(500);
+//! ```
+
+use crate::assertions::{AssertionError, AssertionHelpers};
+use litesvm::LiteSVM;
+#[cfg(feature = "spl")]
+use litesvm_token::spl_token;
+use solana_program::pubkey::Pubkey;
+#[cfg(feature = "spl")]
+use solana_program_pack::Pack;
+
+/// Start a chain of expectations about the account at `pubkey`
+pub fn expect<'a>(svm: &'a LiteSVM, pubkey: &Pubkey) -> AccountExpectation<'a> {
+    AccountExpectation {
+        svm,
+        pubkey: *pubkey,
+    }
+}
+
+#[track_caller]
+fn fulfil(result: Result<(), AssertionError>) {
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+}
+
+/// Expectations about an account, created by [`expect`]
+pub struct AccountExpectation<'a> {
+    svm: &'a LiteSVM,
+    pubkey: Pubkey,
+}
+
+impl<'a> AccountExpectation<'a> {
+    /// Expect the account to exist
+    #[track_caller]
+    pub fn to_exist(self) -> Self {
+        fulfil(self.svm.check_account_exists(&self.pubkey));
+        self
+    }
+
+    /// Expect the account to be closed or never created
+    #[track_caller]
+    pub fn to_be_closed(self) -> Self {
+        fulfil(self.svm.check_account_closed(&self.pubkey));
+        self
+    }
+
+    /// Expect the account to be owned by `owner`
+    #[track_caller]
+    pub fn owned_by(self, owner: &Pubkey) -> Self {
+        fulfil(self.svm.check_account_owner(&self.pubkey, owner));
+        self
+    }
+
+    /// Expect the account to hold exactly `lamports`
+    #[track_caller]
+    pub fn with_lamports(self, lamports: u64) -> Self {
+        fulfil(self.svm.check_sol_balance(&self.pubkey, lamports));
+        self
+    }
+
+    /// Expect the account data to be `len` bytes long
+    #[track_caller]
+    pub fn with_data_len(self, len: usize) -> Self {
+        fulfil(self.svm.check_account_data_len(&self.pubkey, len));
+        self
+    }
+
+    /// Expect the account data to contain `bytes` at `offset`
+    #[track_caller]
+    pub fn with_bytes_at(self, offset: usize, bytes: &[u8]) -> Self {
+        fulfil(self.svm.check_account_bytes(&self.pubkey, offset, bytes));
+        self
+    }
+
+    /// Continue with expectations about the account as an SPL token account
+    ///
+    /// Panics if the account doesn't exist or isn't a token account.
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    pub fn as_token_account(self) -> TokenAccountExpectation<'a> {
+        let account = self
+            .svm
+            .get_account(&self.pubkey)
+            .unwrap_or_else(|| panic!("Token account {} not found", self.pubkey));
+        let token = spl_token::state::Account::unpack(&account.data)
+            .unwrap_or_else(|_| panic!("Failed to unpack token account {}", self.pubkey));
+        TokenAccountExpectation {
+            svm: self.svm,
+            pubkey: self.pubkey,
+            token,
+        }
+    }
+}
+
+/// Expectations about an SPL token account, created by
+/// [`AccountExpectation::as_token_account`]
+#[cfg(feature = "spl")]
+pub struct TokenAccountExpectation<'a> {
+    svm: &'a LiteSVM,
+    pubkey: Pubkey,
+    token: spl_token::state::Account,
+}
+
+#[cfg(feature = "spl")]
+impl TokenAccountExpectation<'_> {
+    /// Expect the token balance to be exactly `amount`
+    #[track_caller]
+    pub fn with_balance(self, amount: u64) -> Self {
+        fulfil(self.svm.check_token_balance(&self.pubkey, amount));
+        self
+    }
+
+    /// Expect the token account to hold tokens of `mint`
+    #[track_caller]
+    pub fn with_mint(self, mint: &Pubkey) -> Self {
+        assert_eq!(
+            &self.token.mint, mint,
+            "Token account mint mismatch for {}. Expected: {}, Actual: {}",
+            self.pubkey, mint, self.token.mint
+        );
+        self
+    }
+
+    /// Expect the token account to belong to `owner`
+    #[track_caller]
+    pub fn with_owner(self, owner: &Pubkey) -> Self {
+        assert_eq!(
+            &self.token.owner, owner,
+            "Token account owner mismatch for {}. Expected: {}, Actual: {}",
+            self.pubkey, owner, self.token.owner
+        );
+        self
+    }
+
+    /// Expect the token account to be frozen
+    #[track_caller]
+    pub fn to_be_frozen(self) -> Self {
+        fulfil(self.svm.check_token_account_frozen(&self.pubkey));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_expect_account() {
+        let mut svm = LiteSVM::new();
+        let user = svm.create_funded_account(1_000_000).unwrap();
+
+        expect(&svm, &user.pubkey())
+            .to_exist()
+            .owned_by(&solana_program::system_program::id())
+            .with_lamports(1_000_000)
+            .with_data_len(0);
+        expect(&svm, &Pubkey::new_unique()).to_be_closed();
+    }
+
+    #[test]
+    #[should_panic(expected = "SOL balance mismatch")]
+    fn test_expect_account_fails() {
+        let mut svm = LiteSVM::new();
+        let user = svm.create_funded_account(1_000_000).unwrap();
+        expect(&svm, &user.pubkey()).to_exist().with_lamports(5);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_expect_token_account() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&owner, 9).unwrap();
+        let token_account = svm.create_token_account(&mint.pubkey(), &owner).unwrap();
+        svm.mint_to(&mint.pubkey(), &token_account.pubkey(), &owner, 500)
+            .unwrap();
+
+        expect(&svm, &token_account.pubkey())
+            .to_exist()
+            .owned_by(&spl_token::id())
+            .as_token_account()
+            .with_mint(&mint.pubkey())
+            .with_owner(&owner.pubkey())
+            .with_balance(500);
+    }
+}
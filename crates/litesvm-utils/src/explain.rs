@@ -0,0 +1,377 @@
+//! Explorer-style transaction reports
+//!
+//! [`TransactionResult::explain`] prints what a block explorer shows for a
+//! transaction: its status, fee and compute units, the role of every account, the
+//! instructions with their CPI tree, and the SOL and token balance changes.
+//!
+//! ```ignore
+//! record_account_diffs(&mut svm, true);
+//! let result = svm.send_instruction(ix, &[&payer])?;
+//! result.explain();
+//! ```
+//!
+//! Balance changes come from the recorded account diffs, so they are only shown
+//! when [`record_account_diffs`](crate::record_account_diffs) was on.
+
+use crate::account_diff::AccountDiff;
+use crate::log_tree::{Invocation, InvocationStatus};
+use crate::transaction::{signature_fee, TransactionResult};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use solana_sdk::transaction::VersionedTransaction;
+
+pub(crate) const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub(crate) const TOKEN_2022_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Readable names of well-known programs and sysvars
+const KNOWN_ACCOUNTS: [(Pubkey, &str); 10] = [
+    (solana_program::system_program::ID, "System Program"),
+    (TOKEN_PROGRAM_ID, "Token Program"),
+    (TOKEN_2022_PROGRAM_ID, "Token-2022 Program"),
+    (
+        Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+        "Associated Token Program",
+    ),
+    (solana_sdk::compute_budget::ID, "Compute Budget Program"),
+    (
+        Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+        "Memo Program",
+    ),
+    (
+        Pubkey::from_str_const("AddressLookupTab1e1111111111111111111111111"),
+        "Address Lookup Table Program",
+    ),
+    (solana_program::stake::program::ID, "Stake Program"),
+    (solana_program::sysvar::rent::ID, "Rent Sysvar"),
+    (solana_program::sysvar::clock::ID, "Clock Sysvar"),
+];
+
+/// Size of an SPL token account, and the offset of Token-2022's account type byte
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+impl TransactionResult {
+    /// Print an explorer-style report of the transaction
+    ///
+    /// See [`explanation`](Self::explanation) for what it contains.
+    pub fn explain(&self) {
+        print!("{}", self.explanation());
+    }
+
+    /// Format the report printed by [`explain`](Self::explain)
+    ///
+    /// The report lists the status, fee and compute units, every account with its
+    /// roles (signer, writable, fee payer, program), each instruction with the name
+    /// Anchor logs for it and its CPI tree, and the SOL and token balance changes.
+    /// Well-known programs are shown by name.
+    ///
+    /// The fee is what LiteSVM charges: [`LAMPORTS_PER_SIGNATURE`](crate::LAMPORTS_PER_SIGNATURE)
+    /// per signature, with no prioritization fee. Accounts, instructions and the fee
+    /// need the transaction, which the send helpers record; balance changes need
+    /// [`record_account_diffs`](crate::record_account_diffs).
+    pub fn explanation(&self) -> String {
+        let transaction: Option<VersionedTransaction> = self
+            .transaction_bytes()
+            .and_then(|bytes| bincode::deserialize(bytes).ok());
+
+        let mut out = format!("=== Transaction {} ===\n", self.signature());
+        match self.error_report() {
+            None => out.push_str("Status: Success\n"),
+            Some(error) => out.push_str(&format!("Status: Failed: {}\n", error)),
+        }
+        if let Some(transaction) = &transaction {
+            let message = &transaction.message;
+            let fee = signature_fee(
+                message.header().num_required_signatures,
+                message.static_account_keys(),
+                message.instructions(),
+            );
+            out.push_str(&format!("Fee: {} lamports\n", fee));
+        }
+        out.push_str(&format!("Compute Units: {}\n", self.compute_units()));
+
+        if let Some(transaction) = &transaction {
+            out.push_str("Accounts:\n");
+            out.push_str(&explain_accounts(transaction));
+        }
+        out.push_str("Instructions:\n");
+        out.push_str(&self.explain_instructions(transaction.as_ref()));
+
+        if self.account_diffs().is_empty() {
+            out.push_str("Balance Changes: not recorded (see record_account_diffs)\n");
+        } else {
+            out.push_str(&explain_balance_changes(self.account_diffs()));
+        }
+        out.push_str("========================\n");
+        out
+    }
+
+    /// The instructions, each followed by its CPIs indented by depth
+    fn explain_instructions(&self, transaction: Option<&VersionedTransaction>) -> String {
+        let tree = self.log_tree();
+        let instructions: Vec<(Pubkey, Vec<u8>)> = match transaction {
+            Some(transaction) => {
+                let keys = transaction.message.static_account_keys();
+                transaction
+                    .message
+                    .instructions()
+                    .iter()
+                    .map(|ix| {
+                        let program_id = keys
+                            .get(ix.program_id_index as usize)
+                            .copied()
+                            .unwrap_or_default();
+                        (program_id, ix.accounts.clone())
+                    })
+                    .collect()
+            }
+            // Without the transaction, fall back to the top-level invocations
+            None => tree
+                .roots()
+                .iter()
+                .map(|invocation| (invocation.program_id, Vec::new()))
+                .collect(),
+        };
+
+        let mut out = String::new();
+        for (index, (program_id, accounts)) in instructions.iter().enumerate() {
+            let Some(root) = tree.roots().get(index) else {
+                out.push_str(&format!(
+                    "  #{} {} (not executed)\n",
+                    index,
+                    program_label(program_id)
+                ));
+                if !accounts.is_empty() {
+                    out.push_str(&format!("      accounts: {}\n", account_indexes(accounts)));
+                }
+                continue;
+            };
+            out.push_str(&format!("  #{} {}\n", index, invocation_label(root)));
+            if !accounts.is_empty() {
+                out.push_str(&format!("      accounts: {}\n", account_indexes(accounts)));
+            }
+            for cpi in root.iter().skip(1) {
+                out.push_str(&format!(
+                    "  {}└ {}\n",
+                    "  ".repeat(cpi.depth),
+                    invocation_label(cpi)
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Every static account of the transaction with its roles
+fn explain_accounts(transaction: &VersionedTransaction) -> String {
+    let message = &transaction.message;
+    let header = message.header();
+    let keys = message.static_account_keys();
+    let signers = usize::from(header.num_required_signatures);
+    let readonly_signed = usize::from(header.num_readonly_signed_accounts);
+    let readonly_unsigned = usize::from(header.num_readonly_unsigned_accounts);
+    let programs: Vec<u8> = message
+        .instructions()
+        .iter()
+        .map(|ix| ix.program_id_index)
+        .collect();
+
+    let mut out = String::new();
+    for (index, key) in keys.iter().enumerate() {
+        let mut roles = Vec::new();
+        if index < signers {
+            roles.push("signer");
+        }
+        let writable = if index < signers {
+            index < signers - readonly_signed
+        } else {
+            index < keys.len() - readonly_unsigned
+        };
+        roles.push(if writable { "writable" } else { "readonly" });
+        if index == 0 {
+            roles.push("fee payer");
+        }
+        if programs.contains(&(index as u8)) {
+            roles.push("program");
+        }
+        out.push_str(&format!("  #{} {} [{}]", index, key, roles.join(", ")));
+        if let Some(name) = known_name(key) {
+            out.push_str(&format!(" {}", name));
+        }
+        out.push('\n');
+    }
+    if let Some(lookups) = message.address_table_lookups() {
+        for lookup in lookups {
+            out.push_str(&format!(
+                "  lookup table {}: {} writable, {} readonly\n",
+                lookup.account_key,
+                lookup.writable_indexes.len(),
+                lookup.readonly_indexes.len()
+            ));
+        }
+    }
+    out
+}
+
+/// The SOL and token balance changes among the recorded account diffs
+fn explain_balance_changes(diffs: &[AccountDiff]) -> String {
+    let mut out = String::new();
+    let sol: Vec<&AccountDiff> = diffs
+        .iter()
+        .filter(|diff| diff.lamport_change() != 0)
+        .collect();
+    if !sol.is_empty() {
+        out.push_str("SOL Balance Changes:\n");
+        for diff in sol {
+            out.push_str(&format!(
+                "  {}: {} -> {} ({:+} lamports)\n",
+                diff.pubkey,
+                diff.lamports_before(),
+                diff.lamports_after(),
+                diff.lamport_change()
+            ));
+        }
+    }
+
+    let mut tokens = Vec::new();
+    for diff in diffs {
+        let before = diff.before.as_ref().and_then(token_balance);
+        let after = diff.after.as_ref().and_then(token_balance);
+        let Some((mint, _)) = after.or(before) else {
+            continue;
+        };
+        let before = before.map_or(0, |(_, amount)| amount);
+        let after = after.map_or(0, |(_, amount)| amount);
+        if before != after {
+            tokens.push(format!(
+                "  {} (mint {}): {} -> {} ({:+})\n",
+                diff.pubkey,
+                mint,
+                before,
+                after,
+                i128::from(after) - i128::from(before)
+            ));
+        }
+    }
+    if !tokens.is_empty() {
+        out.push_str("Token Balance Changes:\n");
+        out.extend(tokens);
+    }
+
+    if out.is_empty() {
+        out.push_str("Balance Changes: none\n");
+    }
+    out
+}
+
+/// The mint and amount of an SPL token account, read from its raw layout
+fn token_balance(account: &Account) -> Option<(Pubkey, u64)> {
+    if account.owner != TOKEN_PROGRAM_ID && account.owner != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+    let data = &account.data;
+    // Token-2022 accounts with extensions are longer and tagged 2; mints are tagged 1
+    let is_account = data.len() == TOKEN_ACCOUNT_LEN
+        || (data.len() > TOKEN_ACCOUNT_LEN && data[TOKEN_ACCOUNT_LEN] == 2);
+    if !is_account {
+        return None;
+    }
+    let mint = Pubkey::try_from(&data[0..32]).ok()?;
+    let amount = u64::from_le_bytes(data[64..72].try_into().ok()?);
+    Some((mint, amount))
+}
+
+fn known_name(pubkey: &Pubkey) -> Option<&'static str> {
+    KNOWN_ACCOUNTS
+        .iter()
+        .find(|(known, _)| known == pubkey)
+        .map(|(_, name)| *name)
+}
+
+fn program_label(program_id: &Pubkey) -> String {
+    known_name(program_id).map_or_else(|| program_id.to_string(), str::to_string)
+}
+
+/// The program, the instruction name it logged and its compute units
+pub(crate) fn invocation_label(invocation: &Invocation) -> String {
+    let mut label = program_label(&invocation.program_id);
+    if let Some(name) = invocation.instruction_name() {
+        label.push_str(&format!(": {}", name));
+    }
+    if let Some(units) = invocation.compute_units {
+        label.push_str(&format!(" ({} CU)", units));
+    }
+    if let InvocationStatus::Failed(error) = &invocation.status {
+        label.push_str(&format!(" failed: {}", error));
+    }
+    label
+}
+
+fn account_indexes(accounts: &[u8]) -> String {
+    accounts
+        .iter()
+        .map(|index| format!("#{}", index))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::account_diff::record_account_diffs;
+    use crate::test_helpers::TestHelpers;
+    use crate::transaction::TransactionHelpers;
+    use litesvm::LiteSVM;
+    use solana_program::pubkey::Pubkey;
+    use solana_program::system_instruction;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_explanation() {
+        let mut svm = LiteSVM::new();
+        record_account_diffs(&mut svm, true);
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let ix = system_instruction::transfer(&from.pubkey(), &to, 1_000_000);
+        let result = svm.send_instruction(ix, &[&from]).unwrap();
+
+        let report = result.explanation();
+        assert!(report.contains("Status: Success"), "{}", report);
+        assert!(report.contains("Fee: 5000 lamports"), "{}", report);
+        assert!(report.contains(&format!("Compute Units: {}", result.compute_units())));
+        assert!(report.contains(&format!("{} [signer, writable, fee payer]", from.pubkey())));
+        assert!(report.contains(&format!("{} [writable]", to)), "{}", report);
+        assert!(
+            report.contains("[readonly, program] System Program"),
+            "{}",
+            report
+        );
+        assert!(
+            report.contains("  #0 System Program\n      accounts: #0, #1"),
+            "{}",
+            report
+        );
+        assert!(report.contains(&format!("{}: 0 -> 1000000 (+1000000 lamports)", to)));
+    }
+
+    #[test]
+    fn test_explanation_of_failure() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), u64::MAX);
+        let result = svm.send_instruction(ix, &[&from]).unwrap();
+
+        let report = result.explanation();
+        assert!(report.contains("Status: Failed: "), "{}", report);
+        assert!(
+            report.contains("#0 System Program failed: custom program error: 0x1"),
+            "{}",
+            report
+        );
+        assert!(
+            report.contains("Balance Changes: not recorded"),
+            "{}",
+            report
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Capped faucet for helper airdrops
+//!
+//! By default the helpers mint lamports out of thin air. With
+//! [`LiteSVMBuilder::with_faucet`](crate::LiteSVMBuilder::with_faucet), every helper
+//! airdrop is instead paid from a named faucet account funded with a fixed cap, so
+//! fixtures that consume more SOL than the deployment plan allows fail loudly.
+//!
+//! The faucet belongs to the VM it was built with; other VMs keep plain airdrops.
+//! Funding is routine setup rather than hand-written state, so faucet payouts don't
+//! appear in the [`surgery_report`](crate::surgery_report), just like plain airdrops.
+
+use crate::account_registry::track_accounts;
+use crate::vm_state::{vm_state, vm_state_mut};
+use litesvm::LiteSVM;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::error::Error;
+
+/// Route helper airdrops on `svm` through the faucet account at `pubkey`
+pub(crate) fn set_faucet(svm: &mut LiteSVM, name: &str, pubkey: Pubkey) {
+    vm_state_mut(svm, |state| state.faucet = Some((name.to_string(), pubkey)));
+}
+
+/// Take `lamports` out of the VM's faucet on behalf of `pubkey`
+///
+/// Returns `false` without touching anything when no faucet is configured, so the
+/// caller mints the lamports itself.
+pub(crate) fn draw_from_faucet(
+    svm: &mut LiteSVM,
+    pubkey: &Pubkey,
+    lamports: u64,
+) -> Result<bool, Box<dyn Error>> {
+    let Some((name, faucet)) = vm_state(svm, |state| state.faucet.clone()) else {
+        return Ok(false);
+    };
+    let mut source = svm.get_account(&faucet).unwrap_or_default();
+    if source.lamports < lamports {
+        return Err(format!(
+            "Faucet '{}' cap exceeded: {} lamports requested for {}, {} left",
+            name, lamports, pubkey, source.lamports
+        )
+        .into());
+    }
+    source.lamports -= lamports;
+    svm.set_account(faucet, source)
+        .map_err(|e| format!("Failed to write faucet '{}': {:?}", name, e))?;
+    Ok(true)
+}
+
+/// Give `lamports` to `pubkey`, from the configured faucet if there is one
+///
+/// This is what the helpers use instead of `LiteSVM::airdrop`.
+///
+/// # Errors
+///
+/// Fails if a faucet is configured and doesn't have `lamports` left, or if the
+/// balance of `pubkey` would overflow
+pub fn fund_account(
+    svm: &mut LiteSVM,
+    pubkey: &Pubkey,
+    lamports: u64,
+) -> Result<(), Box<dyn Error>> {
+    let mut target = svm
+        .get_account(pubkey)
+        .unwrap_or_else(|| Account::new(0, 0, &solana_program::system_program::id()));
+    target.lamports = target.lamports.checked_add(lamports).ok_or_else(|| {
+        format!("Funding {} with {} lamports overflows its balance", pubkey, lamports)
+    })?;
+
+    if !draw_from_faucet(svm, pubkey, lamports)? {
+        svm.airdrop(pubkey, lamports)
+            .map_err(|e| format!("Failed to airdrop: {:?}", e))?;
+        return Ok(());
+    }
+
+    svm.set_account(*pubkey, target)
+        .map_err(|e| format!("Failed to write account {}: {:?}", pubkey, e))?;
+    track_accounts(svm, [*pubkey]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::LiteSVMBuilder;
+    use crate::surgery::surgery_report;
+    use crate::test_helpers::TestHelpers;
+    use litesvm::LiteSVM;
+    use solana_program::native_token::LAMPORTS_PER_SOL;
+    use solana_program::pubkey::Pubkey;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_faucet_cap() {
+        let faucet = Keypair::new();
+        let mut svm = LiteSVMBuilder::new()
+            .with_faucet("treasury", &faucet, 3 * LAMPORTS_PER_SOL)
+            .build();
+
+        let user = svm.create_funded_account(2 * LAMPORTS_PER_SOL).unwrap();
+        assert_eq!(svm.get_balance(&user.pubkey()), Some(2 * LAMPORTS_PER_SOL));
+        assert_eq!(svm.get_balance(&faucet.pubkey()), Some(LAMPORTS_PER_SOL));
+        // Funding isn't surgery
+        assert!(surgery_report(&svm).is_empty());
+
+        // A balance that would overflow is an error, and the faucet keeps its lamports
+        let err = fund_account(&mut svm, &user.pubkey(), u64::MAX).unwrap_err();
+        assert!(err.to_string().contains("overflows"));
+        assert_eq!(svm.get_balance(&faucet.pubkey()), Some(LAMPORTS_PER_SOL));
+
+        let err = svm.create_funded_account(2 * LAMPORTS_PER_SOL).unwrap_err();
+        assert!(err.to_string().contains("Faucet 'treasury' cap exceeded"));
+
+        // Fixed addresses are paid from the faucet too
+        let err = svm
+            .create_funded_account_at(&Pubkey::new_unique(), 2 * LAMPORTS_PER_SOL)
+            .unwrap_err();
+        assert!(err.to_string().contains("cap exceeded"));
+
+        // Other VMs keep plain airdrops
+        let mut other = LiteSVM::new();
+        other.create_funded_account(10 * LAMPORTS_PER_SOL).unwrap();
+        let err = svm.create_funded_account(2 * LAMPORTS_PER_SOL).unwrap_err();
+        assert!(err.to_string().contains("Faucet 'treasury' cap exceeded"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Account fixtures saved to and loaded from JSON files
+//!
+//! [`save_fixtures`] dumps accounts from a VM, one `<pubkey>.json` file each, and
+//! [`LiteSVMBuilder::with_fixtures`] loads a directory of them into a fresh VM. The
+//! files use the same format as `solana account --output json`, so fixtures can be
+//! committed, shared across a team and diffed in review.
+//!
+//! ```ignore
+//! // Once, from a test that builds the state
+//! save_fixtures(&svm, &[pool, oracle], "tests/fixtures/pool")?;
+//!
+//! // In every test that needs it
+//! let svm = LiteSVMBuilder::new().with_fixtures("tests/fixtures/pool").build();
+//! ```
+//!
+//! [`LiteSVMBuilder::with_fixtures`]: crate::LiteSVMBuilder::with_fixtures
+
+use crate::vm_state::is_vm_state_address;
+use base64::Engine;
+use litesvm::LiteSVM;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Write each of `pubkeys` to `dir/<pubkey>.json`, creating `dir` if needed
+///
+/// Returns the paths written. The helpers' state accounts at
+/// [`vm_state_addresses`](crate::vm_state_addresses) are skipped: they belong to
+/// the VM they were written in.
+///
+/// # Errors
+///
+/// Fails if an account doesn't exist or a file can't be written
+pub fn save_fixtures(
+    svm: &LiteSVM,
+    pubkeys: &[Pubkey],
+    dir: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+
+    let mut paths = Vec::with_capacity(pubkeys.len());
+    for pubkey in pubkeys.iter().filter(|pubkey| !is_vm_state_address(pubkey)) {
+        let account = svm
+            .get_account(pubkey)
+            .ok_or_else(|| format!("Account {} not found", pubkey))?;
+        let fixture = serde_json::json!({
+            "pubkey": pubkey.to_string(),
+            "account": account_to_json(&account),
+        });
+        let path = dir.join(format!("{}.json", pubkey));
+        std::fs::write(&path, serde_json::to_string_pretty(&fixture)?)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Read every `*.json` fixture in `dir`, in file name order
+///
+/// # Errors
+///
+/// Fails if the directory can't be read or a file isn't a valid fixture
+pub fn load_fixtures(dir: impl AsRef<Path>) -> Result<Vec<(Pubkey, Account)>, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read fixture directory {}: {}", dir.display(), e))?
+    {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            load_account_file(path)
+                .map_err(|e| format!("Invalid fixture {}: {}", path.display(), e).into())
+        })
+        .collect()
+}
+
+/// Read a single account file in the `solana account --output json` format
+///
+/// This is also the format `solana-test-validator --account` and `--account-dir`
+/// accept, so existing validator fixtures can be loaded as they are.
+///
+/// # Errors
+///
+/// Fails if the file can't be read, isn't a valid account or has no `pubkey`
+pub fn load_account_file(path: impl AsRef<Path>) -> Result<(Pubkey, Account), Box<dyn Error>> {
+    let (pubkey, account) = parse_account_file(path.as_ref())?;
+    Ok((pubkey.ok_or("Missing 'pubkey'")?, account))
+}
+
+/// Read an account file whose `pubkey` may be omitted
+///
+/// `solana-test-validator --account <ADDRESS> <FILE>` only needs the address in the
+/// file when `ADDRESS` is `-`, so hand-written files often leave it out.
+pub(crate) fn parse_account_file(path: &Path) -> Result<(Option<Pubkey>, Account), Box<dyn Error>> {
+    let file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let pubkey = match file.get("pubkey") {
+        Some(pubkey) => Some(Pubkey::from_str(pubkey.as_str().ok_or("Invalid pubkey")?)?),
+        None => None,
+    };
+    let account = file.get("account").ok_or("Missing 'account'")?;
+    Ok((pubkey, account_from_json(account)?))
+}
+
+/// Encode an account the way RPC and `solana account --output json` do
+pub(crate) fn account_to_json(account: &Account) -> serde_json::Value {
+    serde_json::json!({
+        "lamports": account.lamports,
+        "data": [base64::engine::general_purpose::STANDARD.encode(&account.data), "base64"],
+        "owner": account.owner.to_string(),
+        "executable": account.executable,
+        "rentEpoch": account.rent_epoch,
+        "space": account.data.len(),
+    })
+}
+
+/// Decode an account encoded by [`account_to_json`] or an RPC node
+pub(crate) fn account_from_json(value: &serde_json::Value) -> Result<Account, Box<dyn Error>> {
+    let field = |name: &str| {
+        value
+            .get(name)
+            .ok_or_else(|| format!("Missing '{}' in account info", name))
+    };
+    let encoded = field("data")?[0]
+        .as_str()
+        .ok_or("Account data is not base64 encoded")?;
+    Ok(Account {
+        lamports: field("lamports")?.as_u64().ok_or("Invalid lamports")?,
+        data: base64::engine::general_purpose::STANDARD.decode(encoded)?,
+        owner: Pubkey::from_str(field("owner")?.as_str().ok_or("Invalid owner")?)?,
+        executable: field("executable")?
+            .as_bool()
+            .ok_or("Invalid executable flag")?,
+        rent_epoch: field("rentEpoch")?.as_u64().ok_or("Invalid rent epoch")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::LiteSVMBuilder;
+
+    #[test]
+    fn test_fixture_roundtrip() {
+        let mut svm = LiteSVM::new();
+        let owner = Pubkey::new_unique();
+        let pubkeys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for (i, pubkey) in pubkeys.iter().enumerate() {
+            let mut account = Account::new(1_000_000 + i as u64, 3, &owner);
+            account.data = vec![i as u8, 0xAB, 0xCD];
+            svm.set_account(*pubkey, account).unwrap();
+        }
+
+        let dir = std::env::temp_dir().join(format!("litesvm-fixtures-{}", Pubkey::new_unique()));
+        let paths = save_fixtures(&svm, &pubkeys, &dir).unwrap();
+        assert_eq!(paths.len(), 2);
+        let with_state = [pubkeys[0], crate::vm_state_addresses()[0]];
+        assert_eq!(save_fixtures(&svm, &with_state, &dir).unwrap().len(), 1);
+        assert!(save_fixtures(&svm, &[Pubkey::new_unique()], &dir).is_err());
+
+        let fresh = LiteSVMBuilder::new().with_fixtures(&dir).build();
+        for pubkey in &pubkeys {
+            assert_eq!(fresh.get_account(pubkey), svm.get_account(pubkey));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_fixtures_reports_bad_file() {
+        let dir = std::env::temp_dir().join(format!("litesvm-fixtures-{}", Pubkey::new_unique()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("broken.json"), "{}").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let err = load_fixtures(&dir).unwrap_err().to_string();
+        assert!(err.contains("broken.json"));
+        assert!(err.contains("Missing 'account'"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_add_account_from_file() {
+        let owner = Pubkey::new_unique();
+        let pubkey = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let dir = std::env::temp_dir().join(format!("litesvm-accounts-{}", Pubkey::new_unique()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // As written by `solana account <PUBKEY> --output json`
+        let dump = dir.join("dump.json");
+        let account = serde_json::json!({
+            "lamports": 1_461_600,
+            "data": ["AQIDBA==", "base64"],
+            "owner": owner.to_string(),
+            "executable": false,
+            "rentEpoch": u64::MAX,
+            "space": 4,
+        });
+        let contents = serde_json::json!({ "pubkey": pubkey.to_string(), "account": account });
+        std::fs::write(&dump, contents.to_string()).unwrap();
+        // Without a pubkey, as used with `solana-test-validator --account <ADDRESS> <FILE>`
+        let bare = dir.join("bare.json");
+        std::fs::write(&bare, serde_json::json!({ "account": account }).to_string()).unwrap();
+
+        assert_eq!(load_account_file(&dump).unwrap().0, pubkey);
+        assert!(load_account_file(&bare).is_err());
+
+        let svm = LiteSVMBuilder::new()
+            .add_account_from_file(&dump)
+            .add_account_from_file_at(address, &bare)
+            .build();
+        for key in [pubkey, address] {
+            let loaded = svm.get_account(&key).unwrap();
+            assert_eq!(loaded.data, vec![1, 2, 3, 4]);
+            assert_eq!(loaded.owner, owner);
+            assert_eq!(loaded.lamports, 1_461_600);
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+//! Deterministic keypairs for reproducible test runs
+//!
+//! Helpers normally create accounts from fresh random keypairs, so every run logs
+//! different addresses. With a keypair seed set on a VM through
+//! [`LiteSVMBuilder::with_keypair_seed`](crate::LiteSVMBuilder::with_keypair_seed)
+//! (or [`set_keypair_seed`]), the helpers draw that VM's keypairs from a seeded
+//! sequence instead, and the same test produces the same pubkeys on every run.
+//! Logs, snapshots and golden files then diff cleanly. The sequence belongs to the
+//! VM, so other VMs in the same test neither restart nor advance it.
+//!
+//! Named accounts don't depend on the sequence at all:
+//!
+//! ```ignore
+//! let alice = svm.create_funded_account_with_seed("alice", LAMPORTS_PER_SOL)?;
+//! assert_eq!(alice.pubkey(), keypair_from_name("alice").pubkey());
+//! ```
+
+use crate::vm_state::{vm_state, vm_state_mut};
+use litesvm::LiteSVM;
+use solana_program::hash::hashv;
+use solana_sdk::signature::{keypair_from_seed, Keypair};
+
+/// Draw helper keypairs for `svm` from a sequence seeded with `seed`
+///
+/// The sequence restarts each time the seed is set. `None` goes back to random
+/// keypairs.
+pub fn set_keypair_seed(svm: &mut LiteSVM, seed: Option<u64>) {
+    vm_state_mut(svm, |state| state.keypair_seed = seed.map(|seed| (seed, 0)));
+}
+
+/// Get the keypair seed set on `svm`, if any
+pub fn keypair_seed(svm: &LiteSVM) -> Option<u64> {
+    vm_state(svm, |state| state.keypair_seed.map(|(seed, _)| seed))
+}
+
+/// Create a keypair for a helper-created account on `svm`
+///
+/// Returns the next keypair in the VM's seeded sequence when a seed is set, and a
+/// random keypair otherwise.
+pub fn new_keypair(svm: &mut LiteSVM) -> Keypair {
+    if keypair_seed(svm).is_none() {
+        return Keypair::new();
+    }
+    let (seed, index) = vm_state_mut(svm, |state| {
+        let (seed, index) = state.keypair_seed.expect("checked above");
+        state.keypair_seed = Some((seed, index + 1));
+        (seed, index)
+    });
+    let hash = hashv(&[b"keypair", &seed.to_le_bytes(), &index.to_le_bytes()]);
+    keypair_from_seed(hash.as_ref()).expect("a hash is a valid keypair seed")
+}
+
+/// Derive the keypair for `name`, the same on every run
+///
+/// Independent of the keypair seed.
+pub fn keypair_from_name(name: &str) -> Keypair {
+    let hash = hashv(&[b"name", name.as_bytes()]);
+    keypair_from_seed(hash.as_ref()).expect("a hash is a valid keypair seed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_seeded_keypairs_repeat() {
+        let mut svm = LiteSVM::new();
+        set_keypair_seed(&mut svm, Some(7));
+        assert_eq!(keypair_seed(&svm), Some(7));
+        let first: Vec<_> = (0..3).map(|_| new_keypair(&mut svm).pubkey()).collect();
+        assert_ne!(first[0], first[1]);
+
+        set_keypair_seed(&mut svm, Some(7));
+        let second: Vec<_> = (0..3).map(|_| new_keypair(&mut svm).pubkey()).collect();
+        assert_eq!(first, second);
+
+        set_keypair_seed(&mut svm, Some(8));
+        assert_ne!(new_keypair(&mut svm).pubkey(), first[0]);
+
+        set_keypair_seed(&mut svm, None);
+        assert_ne!(new_keypair(&mut svm).pubkey(), new_keypair(&mut svm).pubkey());
+    }
+
+    #[test]
+    fn test_keypair_sequence_is_per_vm() {
+        let mut svm = LiteSVM::new();
+        set_keypair_seed(&mut svm, Some(7));
+        let first = new_keypair(&mut svm).pubkey();
+
+        // Another seeded VM neither restarts nor advances this one's sequence
+        let mut other = LiteSVM::new();
+        set_keypair_seed(&mut other, Some(7));
+        assert_eq!(new_keypair(&mut other).pubkey(), first);
+        assert_eq!(keypair_seed(&LiteSVM::new()), None);
+
+        let second = new_keypair(&mut svm).pubkey();
+        assert_ne!(second, first);
+        assert_eq!(new_keypair(&mut other).pubkey(), second);
+    }
+
+    #[test]
+    fn test_keypair_from_name() {
+        assert_eq!(
+            keypair_from_name("alice").pubkey(),
+            keypair_from_name("alice").pubkey()
+        );
+        assert_ne!(
+            keypair_from_name("alice").pubkey(),
+            keypair_from_name("bob").pubkey()
+        );
+    }
+}
@@ -78,8 +78,7 @@
 //! ## Quick Start
 //!
 //! ```rust,ignore
-//! use litesvm_utils::{LiteSVMBuilder, TestHelpers, AssertionHelpers, TransactionHelpers};
-//! use solana_program::pubkey::Pubkey;
+//! use litesvm_utils::prelude::*;
 //!
 //! // 1. Initialize with one line
 //! let program_id = Pubkey::new_unique();
@@ -143,29 +142,138 @@
 //! - Solana Program Library (SPL) programs
 //! - Custom frameworks
 //!
+//! ## Feature Flags
+//!
+//! - `spl` (default) - Token, ATA, Token-2022, actor and NFT helpers. Disable it with
+//!   `default-features = false` to avoid compiling `spl-token`,
+//!   `spl-associated-token-account` and `spl-token-2022` when testing programs that
+//!   don't use tokens.
+//! - `color` - Highlight program ids and failures in
+//!   [`print_logs`](TransactionResult::print_logs) output.
+//! - `tracing` - A `tracing` span and event for every transaction sent through the
+//!   helpers, with its instruction name, signature, compute units and result.
+//!
 //! ## Traits
 //!
+//! - [`ActorHelpers`] - Funded actors with token portfolios
 //! - [`TestHelpers`] - Account and token creation helpers
 //! - [`AssertionHelpers`] - Test assertion methods
 //! - [`TransactionHelpers`] - Transaction execution helpers
+//! - [`ScenarioHelpers`] - Adversarial scenarios for security tests
+//! - [`Token2022Helpers`] - Token-2022 extension helpers
+//! - [`StakeHelpers`] - Stake accounts and simulated epoch rewards
+//! - [`LookupTableHelpers`] - Address lookup tables and v0 transactions
+//! - [`NftHelpers`] - Metaplex NFT fixtures
 //!
 //! ## Modules
 //!
+//! - [`account_builder`] - Fluent builder for raw accounts
+//! - [`account_diff`] - Before/after state of the accounts a transaction writes
+//! - [`account_registry`] - Accounts known to the helpers, for iteration
+//! - [`actor`] - Persona-centric actor fixtures
 //! - [`assertions`] - Assertion helper implementations
 //! - [`builder`] - Test environment builders
+//! - `cluster` - Fetching accounts and programs from a live cluster (`cluster` feature)
+//! - [`cu_tracker`] - Compute unit regression tracking against a baseline file
+//! - [`expectations`] - Fluent account expectations
+//! - [`explain`] - Explorer-style transaction reports
+//! - [`faucet`] - Capped faucet for helper airdrops
+//! - [`fixtures`] - Account fixtures saved to and loaded from JSON files
+//! - [`keypairs`] - Deterministic keypairs for reproducible test runs
+//! - [`log_capture`] - Per-test transaction log files and printed log filtering
+//! - [`log_tree`] - Program invocation trees parsed from transaction logs
+//! - [`lookup_table`] - Address lookup table fixtures and v0 transactions
+//! - [`matrix`] - Parameterized test runner
+//! - [`nft`] - Metaplex NFT fixtures
+//! - [`payer_pool`] - Rotating fee payers
+//! - [`prelude`] - Glob import of the traits and types most tests need
+//! - [`scenarios`] - Adversarial test scenarios
+//! - [`soft_assertions`] - Collecting assertion failures instead of stopping at the first
+//! - [`stake`] - Stake account fixtures and epoch rewards
+//! - [`surgery`] - Recorded direct state surgery
 //! - [`test_helpers`] - Test helper implementations
+//! - [`token_2022`] - Token-2022 extension helpers
 //! - [`transaction`] - Transaction execution and result analysis
+//! - [`transaction_builder`] - Fluent builder for multi-instruction transactions
 
+pub mod account_builder;
+pub mod account_diff;
+pub mod account_registry;
+#[cfg(feature = "spl")]
+pub mod actor;
 pub mod assertions;
 pub mod builder;
+#[cfg(feature = "cluster")]
+pub mod cluster;
+pub mod cu_tracker;
+pub mod expectations;
+pub mod explain;
+pub mod faucet;
+pub mod fixtures;
+pub mod keypairs;
+pub mod log_capture;
+pub mod log_tree;
+pub mod lookup_table;
+pub mod matrix;
+#[cfg(feature = "spl")]
+pub mod nft;
+pub mod payer_pool;
+pub mod prelude;
+pub mod scenarios;
+pub mod soft_assertions;
+pub mod stake;
+pub mod surgery;
 pub mod test_helpers;
+#[cfg(feature = "spl")]
+pub mod token_2022;
 pub mod transaction;
+pub mod transaction_builder;
+mod vm_state;
 
 // Re-export main types for convenience
-pub use assertions::AssertionHelpers;
+pub use account_builder::AccountBuilder;
+pub use account_diff::{
+    capture_writable_accounts, diff_accounts, record_account_diffs, recording_account_diffs,
+    AccountDiff,
+};
+pub use account_registry::{track_accounts, AccountFilter, AccountIter};
+#[cfg(feature = "spl")]
+pub use actor::{Actor, ActorBuilder, ActorHelpers, PersonaBuilder};
+pub use assertions::{AssertionError, AssertionHelpers, BalanceSnapshot};
 pub use builder::{LiteSVMBuilder, ProgramTestExt};
-pub use test_helpers::TestHelpers;
-pub use transaction::{TransactionError, TransactionHelpers, TransactionResult};
+#[cfg(feature = "cluster")]
+pub use cluster::{fetch_account, fetch_program, ClonedProgram};
+pub use cu_tracker::{CuBaseline, CuBaselineError, CU_BASELINE_ENV, CU_BASELINE_UPDATE_ENV};
+pub use expectations::{expect, AccountExpectation};
+#[cfg(feature = "spl")]
+pub use expectations::TokenAccountExpectation;
+pub use faucet::fund_account;
+pub use fixtures::{load_account_file, load_fixtures, save_fixtures};
+pub use keypairs::{keypair_from_name, keypair_seed, new_keypair, set_keypair_seed};
+pub use log_capture::{capture_transaction, LogFilter, LOG_CAPTURE_ENV, LOG_FILTER_ENV};
+pub use log_tree::{Invocation, InvocationStatus, LogTree};
+pub use lookup_table::LookupTableHelpers;
+pub use matrix::{matrix, product2, product3, product4};
+#[cfg(feature = "spl")]
+pub use nft::{Nft, NftHelpers, NftMetadata, TOKEN_METADATA_PROGRAM_ID};
+pub use payer_pool::PayerPool;
+pub use scenarios::{
+    ActorTransaction, ExecutionOrder, OrderingRun, RevivalAttempt, RevivalTiming, ScenarioHelpers,
+};
+pub use soft_assertions::SoftAssertions;
+pub use stake::StakeHelpers;
+pub use surgery::{clear_surgery_report, surgery_report, StateSurgery, SurgeryRecord};
+pub use test_helpers::{ProgramData, SvmSnapshot, TestHelpers};
+#[cfg(feature = "spl")]
+pub use token_2022::Token2022Helpers;
+pub use transaction::{
+    add_signatures, assert_tx_fits, default_compute_budget, missing_signers,
+    set_default_compute_budget, transaction_size, with_compute_budget, BatchResult, ComputeBudget, CuBudgets,
+    InstructionComputeUnits, TransactionError, TransactionHelpers, TransactionResult,
+    LAMPORTS_PER_SIGNATURE, MAX_TRANSACTION_SIZE,
+};
+pub use transaction_builder::TransactionBuilder;
+pub use vm_state::vm_state_addresses;
 
 // Re-export commonly used external types
 pub use litesvm::LiteSVM;
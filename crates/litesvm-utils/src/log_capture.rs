@@ -0,0 +1,292 @@
+//! Per-test transaction log capture
+//!
+//! When the `LITESVM_CAPTURE_LOGS` environment variable is set (to anything but
+//! `0` or an empty string), every transaction executed through the helpers is
+//! written to `target/litesvm-logs/<test_name>/<n>.json`. CI jobs can upload that
+//! directory as an artifact to get full diagnostics for failing tests.
+//!
+//! [`LogFilter`] separately controls how much of each transaction's log output is
+//! printed while the test runs.
+//!
+//! With the `tracing` feature, every transaction also emits a `transaction` span
+//! with its instruction name and signature, holding an event with its compute
+//! units and result, so tests feed into existing tracing subscribers. The program
+//! logs are emitted at `TRACE` level under the `litesvm::logs` target.
+
+use crate::transaction::TransactionResult;
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that enables log capture
+pub const LOG_CAPTURE_ENV: &str = "LITESVM_CAPTURE_LOGS";
+
+/// Environment variable that overrides the configured [`LogFilter`]
+///
+/// Accepts `quiet`, `errors` or `full`, so a single run can be made verbose without
+/// editing the test.
+pub const LOG_FILTER_ENV: &str = "LITESVM_LOG_FILTER";
+
+/// How much transaction log output to print while a test runs
+///
+/// Only printing is affected: [`TransactionResult::logs`] always holds the full logs
+/// and `LITESVM_CAPTURE_LOGS` still records every transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFilter {
+    /// Print nothing
+    #[default]
+    Quiet,
+    /// Print the logs of failed transactions only
+    ErrorsOnly,
+    /// Print the logs of every transaction
+    Full,
+}
+
+impl LogFilter {
+    /// Parse a filter name as accepted by [`LOG_FILTER_ENV`]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "quiet" | "off" => Some(Self::Quiet),
+            "errors" | "errors-only" => Some(Self::ErrorsOnly),
+            "full" | "all" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    /// The filter set in [`LOG_FILTER_ENV`], if it names a valid filter
+    pub fn from_env() -> Option<Self> {
+        std::env::var(LOG_FILTER_ENV)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+    }
+
+    /// Whether this filter prints `result`'s logs
+    pub fn allows(&self, result: &TransactionResult) -> bool {
+        match self {
+            Self::Quiet => false,
+            Self::ErrorsOnly => !result.is_success(),
+            Self::Full => true,
+        }
+    }
+
+    /// Print `result`'s logs if the filter allows it
+    ///
+    /// [`LOG_FILTER_ENV`] takes precedence over `self` when set.
+    pub fn print(&self, result: &TransactionResult) {
+        if Self::from_env().unwrap_or(*self).allows(result) {
+            result.print_logs();
+        }
+    }
+}
+
+thread_local! {
+    // cargo test runs each test on its own thread, so this counts per test
+    static TRANSACTION_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Check whether log capture is enabled
+pub fn log_capture_enabled() -> bool {
+    std::env::var(LOG_CAPTURE_ENV).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Write a transaction's logs and result to the capture directory, if enabled
+///
+/// The helpers call this for every transaction they execute; call it yourself
+/// for transactions sent through `LiteSVM` directly. Failures to write are
+/// reported on stderr but never fail the test. With the `tracing` feature, the
+/// transaction is also traced.
+pub fn capture_transaction(result: &TransactionResult) {
+    #[cfg(feature = "tracing")]
+    trace_transaction(result);
+
+    if !log_capture_enabled() {
+        return;
+    }
+
+    let dir = target_dir().join("litesvm-logs").join(current_test_name());
+    if let Err(e) = write_capture(&dir, result) {
+        eprintln!("Failed to capture transaction logs in {}: {}", dir.display(), e);
+    }
+}
+
+/// Emit a span and event for the transaction, and its logs at `TRACE` level
+#[cfg(feature = "tracing")]
+fn trace_transaction(result: &TransactionResult) {
+    let span = tracing::info_span!(
+        "transaction",
+        instruction = result.instruction_name(),
+        signature = %result.signature(),
+    );
+    let _entered = span.enter();
+
+    for log in result.logs() {
+        tracing::trace!(target: "litesvm::logs", "{}", log);
+    }
+    match result.error_report() {
+        None => tracing::info!(
+            compute_units = result.compute_units(),
+            success = true,
+            "transaction succeeded"
+        ),
+        Some(error) => tracing::warn!(
+            compute_units = result.compute_units(),
+            success = false,
+            error = %error,
+            "transaction failed"
+        ),
+    }
+}
+
+/// Write the next numbered capture file for the current thread into `dir`
+fn write_capture(dir: &Path, result: &TransactionResult) -> std::io::Result<PathBuf> {
+    let n = TRANSACTION_COUNT.with(|count| {
+        count.set(count.get() + 1);
+        count.get()
+    });
+
+    let record = serde_json::json!({
+        "index": n,
+        "instruction": result.instruction_name(),
+        "success": result.is_success(),
+        "error": result.error(),
+        "signature": result.signature().to_string(),
+        "blockhash": result.blockhash().map(|hash| hash.to_string()),
+        "fee_payer": result.fee_payer().map(|payer| payer.to_string()),
+        "compute_units": result.compute_units(),
+        "logs": result.logs(),
+    });
+
+    // Drop files left over from a previous run of the same test
+    if n == 1 && dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", n));
+    std::fs::write(&path, serde_json::to_string_pretty(&record)?)?;
+    Ok(path)
+}
+
+/// Name of the running test, derived from the thread name cargo test assigns
+fn current_test_name() -> String {
+    std::thread::current()
+        .name()
+        .unwrap_or("unknown")
+        .replace("::", "__")
+}
+
+/// The workspace `target` directory, found via the nearest `Cargo.lock`
+fn target_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    cwd.ancestors()
+        .find(|dir| dir.join("Cargo.lock").is_file())
+        .unwrap_or(&cwd)
+        .join("target")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use crate::transaction::TransactionHelpers;
+    use solana_program::pubkey::Pubkey;
+    use solana_program::system_instruction;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_write_capture() {
+        let mut svm = litesvm::LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&from]).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("litesvm-logs-{}", Pubkey::new_unique()));
+        let first = write_capture(&dir, &result).unwrap();
+        let second = write_capture(&dir, &result).unwrap();
+        assert_eq!(first.file_name().unwrap(), "1.json");
+        assert_eq!(second.file_name().unwrap(), "2.json");
+
+        let record: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&first).unwrap()).unwrap();
+        assert_eq!(record["success"], true);
+        assert_eq!(record["logs"].as_array().unwrap().len(), result.logs().len());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_log_filter() {
+        let mut svm = litesvm::LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), 1_000_000);
+        let ok = svm.send_instruction(ix, &[&from]).unwrap();
+        let ix = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), u64::MAX);
+        let failed = svm.send_instruction(ix, &[&from]).unwrap();
+
+        assert!(!LogFilter::Quiet.allows(&failed));
+        assert!(!LogFilter::ErrorsOnly.allows(&ok));
+        assert!(LogFilter::ErrorsOnly.allows(&failed));
+        assert!(LogFilter::Full.allows(&ok));
+
+        assert_eq!(LogFilter::parse("Errors"), Some(LogFilter::ErrorsOnly));
+        assert_eq!(LogFilter::parse(" full "), Some(LogFilter::Full));
+        assert_eq!(LogFilter::parse("verbose"), None);
+    }
+
+    /// Records the fields of every span and event as `name=value` strings
+    #[cfg(feature = "tracing")]
+    #[derive(Default, Clone)]
+    struct Recorder(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for Recorder {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.lock().unwrap().push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for Recorder {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            span.record(&mut self.clone());
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_trace_transaction() {
+        let recorder = Recorder::default();
+        let mut svm = litesvm::LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), u64::MAX);
+        let failed = tracing::subscriber::with_default(recorder.clone(), || {
+            svm.send_instruction(ix, &[&from]).unwrap()
+        });
+
+        let fields = recorder.0.lock().unwrap();
+        assert!(fields.contains(&format!("signature={}", failed.signature())));
+        assert!(fields.contains(&format!("compute_units={}", failed.compute_units())));
+        assert!(fields.contains(&"success=false".to_string()));
+        assert!(fields.contains(&"message=transaction failed".to_string()));
+        assert!(fields.iter().any(|field| field.starts_with("error=InstructionError")));
+    }
+}
@@ -0,0 +1,439 @@
+//! Structured program invocation trees parsed from transaction logs
+//!
+//! [`LogTree`] turns the flat `Program ... invoke [n]` log lines into a tree of
+//! [`Invocation`]s, so tests can assert on which programs ran, in what order and
+//! with what outcome instead of matching substrings.
+//!
+//! ```ignore
+//! let tree = result.log_tree();
+//! let deposit = &tree.roots()[0];
+//! assert_eq!(deposit.program_id, program_id);
+//! assert_eq!(deposit.children[0].program_id, spl_token::id());
+//! assert!(deposit.is_success());
+//! ```
+
+use crate::explain::{invocation_label, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use solana_program::pubkey::Pubkey;
+
+/// How an invocation ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvocationStatus {
+    /// The program returned successfully
+    Success,
+    /// The program failed with this error
+    Failed(String),
+    /// The logs end before the program returned, e.g. because they were truncated
+    Unfinished,
+}
+
+/// One program invocation and the CPIs it made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    /// The invoked program
+    pub program_id: Pubkey,
+    /// Invocation depth: 1 for a top-level instruction, 2 and up for CPIs
+    pub depth: usize,
+    /// The program's `msg!` output, without the `Program log: ` prefix
+    pub messages: Vec<String>,
+    /// Compute units consumed, including nested CPIs; `None` for builtins
+    pub compute_units: Option<u64>,
+    /// How the invocation ended
+    pub status: InvocationStatus,
+    /// The CPIs made by this invocation, in order
+    pub children: Vec<Invocation>,
+}
+
+impl Invocation {
+    /// Whether the program returned successfully
+    pub fn is_success(&self) -> bool {
+        self.status == InvocationStatus::Success
+    }
+
+    /// The instruction name Anchor logs on entry, e.g. `Deposit`
+    pub fn instruction_name(&self) -> Option<&str> {
+        self.messages
+            .iter()
+            .find_map(|message| message.strip_prefix("Instruction: "))
+    }
+
+    /// This invocation followed by all of its nested CPIs, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &Invocation> {
+        let mut invocations = vec![self];
+        for child in &self.children {
+            invocations.extend(child.iter());
+        }
+        invocations.into_iter()
+    }
+}
+
+/// Program invocations parsed from transaction logs
+///
+/// Get one from [`TransactionResult::log_tree`](crate::TransactionResult::log_tree)
+/// or parse logs directly with [`LogTree::parse`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogTree {
+    roots: Vec<Invocation>,
+}
+
+impl LogTree {
+    /// Parse transaction logs into an invocation tree
+    ///
+    /// Lines outside any invocation are ignored.
+    pub fn parse(logs: &[String]) -> Self {
+        let mut roots: Vec<Invocation> = Vec::new();
+        let mut stack: Vec<Invocation> = Vec::new();
+
+        for log in logs {
+            if let Some(message) = log.strip_prefix("Program log: ") {
+                if let Some(frame) = stack.last_mut() {
+                    frame.messages.push(message.to_string());
+                }
+                continue;
+            }
+            let Some((id, event)) = log
+                .strip_prefix("Program ")
+                .and_then(|rest| rest.split_once(' '))
+            else {
+                continue;
+            };
+
+            if let Some(depth) = event
+                .strip_prefix("invoke [")
+                .and_then(|depth| depth.strip_suffix(']'))
+                .and_then(|depth| depth.parse::<usize>().ok())
+            {
+                let Ok(program_id) = id.parse() else {
+                    continue;
+                };
+                stack.push(Invocation {
+                    program_id,
+                    depth,
+                    messages: Vec::new(),
+                    compute_units: None,
+                    status: InvocationStatus::Unfinished,
+                    children: Vec::new(),
+                });
+                continue;
+            }
+
+            let Some(frame) = stack.last_mut() else {
+                continue;
+            };
+            if frame.program_id.to_string() != id {
+                continue;
+            }
+            if let Some(consumed) = event
+                .strip_prefix("consumed ")
+                .and_then(|consumed| consumed.split_once(' '))
+                .and_then(|(units, _)| units.parse::<u64>().ok())
+            {
+                frame.compute_units = Some(consumed);
+                continue;
+            }
+            let status = if event == "success" {
+                InvocationStatus::Success
+            } else if let Some(error) = event.strip_prefix("failed: ") {
+                InvocationStatus::Failed(error.to_string())
+            } else {
+                continue;
+            };
+            frame.status = status;
+            close_frame(&mut stack, &mut roots);
+        }
+
+        // Invocations the logs never returned from stay Unfinished
+        while !stack.is_empty() {
+            close_frame(&mut stack, &mut roots);
+        }
+        Self { roots }
+    }
+
+    /// The top-level instructions that ran, in order
+    ///
+    /// Entry `i` is the `i`th instruction of the transaction, since instructions
+    /// after a failed one never run.
+    pub fn roots(&self) -> &[Invocation] {
+        &self.roots
+    }
+
+    /// Every invocation, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &Invocation> {
+        self.roots.iter().flat_map(Invocation::iter)
+    }
+
+    /// Every invocation of `program_id`, depth first
+    pub fn invocations_of<'a>(
+        &'a self,
+        program_id: &'a Pubkey,
+    ) -> impl Iterator<Item = &'a Invocation> {
+        self.iter()
+            .filter(move |invocation| invocation.program_id == *program_id)
+    }
+
+    /// The invocation that failed first, if any
+    ///
+    /// When a CPI fails, so do its callers; this is the innermost one.
+    pub fn failure(&self) -> Option<&Invocation> {
+        self.iter()
+            .filter(|invocation| matches!(invocation.status, InvocationStatus::Failed(_)))
+            .last()
+    }
+}
+
+/// Pop the innermost open invocation into its caller, or into the roots
+fn close_frame(stack: &mut Vec<Invocation>, roots: &mut Vec<Invocation>) {
+    let Some(invocation) = stack.pop() else {
+        return;
+    };
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(invocation),
+        None => roots.push(invocation),
+    }
+}
+
+/// A log line that belongs to an invocation frame rather than to the program's output
+enum FrameEvent {
+    Invoke(usize),
+    Return,
+    Other,
+}
+
+/// The program and event of a `Program <id> ...` line
+fn frame_event(log: &str) -> Option<(Pubkey, FrameEvent)> {
+    let (id, event) = log.strip_prefix("Program ")?.split_once(' ')?;
+    let program_id = id.parse().ok()?;
+    let event = if let Some(depth) = event
+        .strip_prefix("invoke [")
+        .and_then(|depth| depth.strip_suffix(']'))
+        .and_then(|depth| depth.parse().ok())
+    {
+        FrameEvent::Invoke(depth)
+    } else if event == "success" || event.starts_with("failed") {
+        FrameEvent::Return
+    } else {
+        FrameEvent::Other
+    };
+    Some((program_id, event))
+}
+
+/// Lay out logs for reading, indenting each line by its invocation depth
+///
+/// With `collapse`, a successful token program invocation is shortened to one line
+/// with its instruction and compute units; failed ones are kept in full. With
+/// `color`, program ids and failures are highlighted with ANSI colors.
+pub(crate) fn format_logs(logs: &[String], collapse: bool, color: bool) -> String {
+    let mut out = String::new();
+    let mut depth = 0;
+    // The depth and lines of the token program invocation being collapsed
+    let mut token_call: Option<(usize, Vec<String>)> = None;
+
+    for log in logs {
+        let event = frame_event(log);
+        let line_depth = match &event {
+            Some((_, FrameEvent::Invoke(invoked))) => {
+                depth = *invoked;
+                invoked.saturating_sub(1)
+            }
+            Some((_, FrameEvent::Return)) => {
+                depth = depth.saturating_sub(1);
+                depth
+            }
+            Some((_, FrameEvent::Other)) => depth.saturating_sub(1),
+            None => depth,
+        };
+
+        if let Some((call_depth, lines)) = &mut token_call {
+            lines.push(log.clone());
+            if matches!(event, Some((_, FrameEvent::Return))) && depth < *call_depth {
+                let (call_depth, lines) = token_call.take().unwrap_or_default();
+                let tree = LogTree::parse(&lines);
+                match tree.roots().first() {
+                    Some(call) if call.is_success() => push_line(
+                        &mut out,
+                        call_depth.saturating_sub(1),
+                        &invocation_label(call),
+                        color,
+                    ),
+                    _ => out.push_str(&format_logs(&lines, false, color)),
+                }
+            }
+            continue;
+        }
+        if let Some((program_id, FrameEvent::Invoke(invoked))) = &event {
+            if collapse && [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID].contains(program_id) {
+                token_call = Some((*invoked, vec![log.clone()]));
+                continue;
+            }
+        }
+        push_line(&mut out, line_depth, log, color);
+    }
+
+    // A token call the logs never returned from is shown in full
+    if let Some((_, lines)) = token_call {
+        out.push_str(&format_logs(&lines, false, color));
+    }
+    out
+}
+
+fn push_line(out: &mut String, depth: usize, line: &str, color: bool) {
+    out.push_str(&"  ".repeat(depth));
+    if color {
+        out.push_str(&paint(line));
+    } else {
+        out.push_str(line);
+    }
+    out.push('\n');
+}
+
+/// Highlight failures in red and program ids in cyan
+fn paint(line: &str) -> String {
+    const RED: &str = "\x1b[31m";
+    const CYAN: &str = "\x1b[36m";
+    const RESET: &str = "\x1b[0m";
+
+    let failed = line.contains(" failed")
+        || line.starts_with("Program log: Error")
+        || line.starts_with("Program log: AnchorError");
+    if failed {
+        return format!("{}{}{}", RED, line, RESET);
+    }
+    match frame_event(line).and_then(|_| line.strip_prefix("Program ")?.split_once(' ')) {
+        Some((id, rest)) => format!("Program {}{}{} {}", CYAN, id, RESET, rest),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_nested_invocations() {
+        let tree = LogTree::parse(&logs(&[
+            &format!("Program {} invoke [1]", PROGRAM),
+            "Program log: Instruction: Deposit",
+            &format!("Program {} invoke [2]", TOKEN),
+            "Program log: Instruction: Transfer",
+            &format!("Program {} consumed 4645 of 190000 compute units", TOKEN),
+            &format!("Program {} success", TOKEN),
+            "Program log: Deposited 100",
+            &format!("Program {} consumed 12000 of 200000 compute units", PROGRAM),
+            &format!("Program {} success", PROGRAM),
+            "Program 11111111111111111111111111111111 invoke [1]",
+            "Program 11111111111111111111111111111111 success",
+        ]));
+
+        let program: Pubkey = PROGRAM.parse().unwrap();
+        let token: Pubkey = TOKEN.parse().unwrap();
+        assert_eq!(tree.roots().len(), 2);
+
+        let deposit = &tree.roots()[0];
+        assert_eq!(deposit.program_id, program);
+        assert_eq!(deposit.depth, 1);
+        assert_eq!(deposit.instruction_name(), Some("Deposit"));
+        assert_eq!(deposit.messages, ["Instruction: Deposit", "Deposited 100"]);
+        assert_eq!(deposit.compute_units, Some(12000));
+        assert!(deposit.is_success());
+
+        let transfer = &deposit.children[0];
+        assert_eq!(transfer.program_id, token);
+        assert_eq!(transfer.depth, 2);
+        assert_eq!(transfer.instruction_name(), Some("Transfer"));
+        assert_eq!(transfer.compute_units, Some(4645));
+
+        let system = &tree.roots()[1];
+        assert_eq!(system.compute_units, None);
+        assert!(system.is_success());
+
+        let order: Vec<Pubkey> = tree
+            .iter()
+            .map(|invocation| invocation.program_id)
+            .collect();
+        assert_eq!(order, [program, token, solana_program::system_program::ID]);
+        assert_eq!(tree.invocations_of(&token).count(), 1);
+        assert!(tree.failure().is_none());
+    }
+
+    #[test]
+    fn test_parse_failed_and_unfinished_invocations() {
+        let tree = LogTree::parse(&logs(&[
+            &format!("Program {} invoke [1]", PROGRAM),
+            &format!("Program {} invoke [2]", TOKEN),
+            "Program log: Error: insufficient funds",
+            &format!("Program {} failed: custom program error: 0x1", TOKEN),
+            &format!("Program {} failed: custom program error: 0x1", PROGRAM),
+        ]));
+        let failure = tree.failure().unwrap();
+        assert_eq!(failure.program_id, TOKEN.parse().unwrap());
+        assert_eq!(
+            failure.status,
+            InvocationStatus::Failed("custom program error: 0x1".to_string())
+        );
+        assert!(!tree.roots()[0].is_success());
+
+        let truncated = LogTree::parse(&logs(&[
+            &format!("Program {} invoke [1]", PROGRAM),
+            &format!("Program {} invoke [2]", TOKEN),
+            "Log truncated",
+        ]));
+        let root = &truncated.roots()[0];
+        assert_eq!(root.status, InvocationStatus::Unfinished);
+        assert_eq!(root.children[0].status, InvocationStatus::Unfinished);
+    }
+
+    #[test]
+    fn test_format_logs() {
+        let lines = logs(&[
+            &format!("Program {} invoke [1]", PROGRAM),
+            "Program log: Instruction: Deposit",
+            &format!("Program {} invoke [2]", TOKEN),
+            "Program log: Instruction: Transfer",
+            &format!("Program {} consumed 4645 of 190000 compute units", TOKEN),
+            &format!("Program {} success", TOKEN),
+            &format!("Program {} consumed 12000 of 200000 compute units", PROGRAM),
+            &format!("Program {} success", PROGRAM),
+        ]);
+
+        let expected = [
+            format!("Program {} invoke [1]", PROGRAM),
+            "  Program log: Instruction: Deposit".to_string(),
+            "  Token Program: Transfer (4645 CU)".to_string(),
+            format!("Program {} consumed 12000 of 200000 compute units", PROGRAM),
+            format!("Program {} success\n", PROGRAM),
+        ];
+        assert_eq!(format_logs(&lines, true, false), expected.join("\n"));
+
+        // Without collapsing, the token program's lines are indented a level deeper
+        let full = format_logs(&lines, false, false);
+        assert!(full.contains(&format!("\n  Program {} invoke [2]\n", TOKEN)));
+        assert!(full.contains("\n    Program log: Instruction: Transfer\n"));
+
+        let painted = format_logs(&lines, true, true);
+        assert!(painted.contains(&format!("Program \x1b[36m{}\x1b[0m invoke [1]", PROGRAM)));
+    }
+
+    #[test]
+    fn test_format_logs_keeps_failed_token_calls() {
+        let lines = logs(&[
+            &format!("Program {} invoke [1]", PROGRAM),
+            &format!("Program {} invoke [2]", TOKEN),
+            "Program log: Error: insufficient funds",
+            &format!("Program {} failed: custom program error: 0x1", TOKEN),
+            &format!("Program {} failed: custom program error: 0x1", PROGRAM),
+        ]);
+        let formatted = format_logs(&lines, true, false);
+        assert!(formatted.contains("\n    Program log: Error: insufficient funds\n"));
+        assert!(formatted.contains(&format!("\n  Program {} failed", TOKEN)));
+        assert!(formatted.ends_with(&format!(
+            "\nProgram {} failed: custom program error: 0x1\n",
+            PROGRAM
+        )));
+    }
+}
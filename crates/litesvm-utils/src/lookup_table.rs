@@ -0,0 +1,264 @@
+//! Address lookup table fixtures and v0 transactions
+//!
+//! A legacy transaction can reference at most 35 or so accounts before it outgrows
+//! a packet. [`LookupTableHelpers`] creates and extends lookup tables through the
+//! real lookup table program, then "warms" them by advancing one slot, since the
+//! runtime only resolves addresses appended in an earlier slot. Instructions sent
+//! with [`send_instructions_with_lookup_tables`] compile to a v0 message that loads
+//! accounts through the tables.
+//!
+//! ```ignore
+//! let table = svm.create_lookup_table(&authority, &pool_accounts)?;
+//! let result = svm.send_instructions_with_lookup_tables(&[swap_ix], &[&user], &[table])?;
+//! result.assert_success();
+//! ```
+//!
+//! [`send_instructions_with_lookup_tables`]: LookupTableHelpers::send_instructions_with_lookup_tables
+
+use crate::transaction::{
+    with_default_compute_budget, TransactionError, TransactionHelpers, TransactionResult,
+};
+use litesvm::LiteSVM;
+use solana_address_lookup_table_interface::instruction::{
+    create_lookup_table, extend_lookup_table,
+};
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_program::clock::Clock;
+use solana_program::instruction::Instruction;
+use solana_program::pubkey::Pubkey;
+use solana_program::slot_hashes::SlotHashes;
+use solana_sdk::message::{v0, AddressLookupTableAccount, VersionedMessage};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use std::error::Error;
+
+/// Addresses appended per extend transaction, keeping each one under the packet limit
+const EXTEND_CHUNK_SIZE: usize = 20;
+
+/// Address lookup table helpers for LiteSVM
+pub trait LookupTableHelpers {
+    /// Create a lookup table holding `addresses`, ready to use in the next transaction
+    ///
+    /// `authority` owns the table and pays for it. The addresses are appended in
+    /// batches, then the table is warmed with [`warm_lookup_tables`].
+    ///
+    /// [`warm_lookup_tables`]: Self::warm_lookup_tables
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use litesvm_utils::{LookupTableHelpers, TestHelpers};
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// let authority = svm.create_funded_account(1_000_000_000).unwrap();
+    /// let addresses: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+    /// let table = svm.create_lookup_table(&authority, &addresses).unwrap();
+    /// ```
+    fn create_lookup_table(
+        &mut self,
+        authority: &Keypair,
+        addresses: &[Pubkey],
+    ) -> Result<Pubkey, Box<dyn Error>>;
+
+    /// Append `addresses` to an existing lookup table and warm it
+    ///
+    /// `authority` must be the table's authority; it also pays for the extra space.
+    fn extend_lookup_table(
+        &mut self,
+        table: &Pubkey,
+        authority: &Keypair,
+        addresses: &[Pubkey],
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Advance one slot, so addresses appended in the current slot can be resolved
+    fn warm_lookup_tables(&mut self);
+
+    /// Read a lookup table's addresses
+    ///
+    /// Returns `None` if the account doesn't exist or isn't a lookup table.
+    fn get_lookup_table(&self, table: &Pubkey) -> Option<AddressLookupTableAccount>;
+
+    /// Send instructions as a v0 transaction that loads accounts through `tables`
+    ///
+    /// The first signer pays, and the default compute budget is prepended as for
+    /// the other helpers. Accounts that aren't signers or invoked programs are
+    /// resolved through the tables where possible.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use litesvm_utils::LookupTableHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let user = Keypair::new();
+    /// # let table = solana_program::pubkey::Pubkey::new_unique();
+    /// let result = svm
+    ///     .send_instructions_with_lookup_tables(&[ix], &[&user], &[table])
+    ///     .unwrap();
+    /// result.assert_success();
+    /// ```
+    fn send_instructions_with_lookup_tables(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        tables: &[Pubkey],
+    ) -> Result<TransactionResult, TransactionError>;
+}
+
+impl LookupTableHelpers for LiteSVM {
+    fn create_lookup_table(
+        &mut self,
+        authority: &Keypair,
+        addresses: &[Pubkey],
+    ) -> Result<Pubkey, Box<dyn Error>> {
+        // The program derives the table from a slot that must be in SlotHashes,
+        // which LiteSVM only fills in for the slot it started at
+        let slot = self.get_sysvar::<Clock>().slot;
+        let mut slot_hashes = self.get_sysvar::<SlotHashes>();
+        if slot_hashes.get(&slot).is_none() {
+            slot_hashes.add(slot, self.latest_blockhash());
+            self.set_sysvar(&slot_hashes);
+        }
+
+        let (create_ix, table) = create_lookup_table(authority.pubkey(), authority.pubkey(), slot);
+        let result = self.send_instruction(create_ix, &[authority])?;
+        if let Some(error) = result.error() {
+            return Err(format!("Failed to create lookup table: {}", error).into());
+        }
+
+        self.extend_lookup_table(&table, authority, addresses)?;
+        Ok(table)
+    }
+
+    fn extend_lookup_table(
+        &mut self,
+        table: &Pubkey,
+        authority: &Keypair,
+        addresses: &[Pubkey],
+    ) -> Result<(), Box<dyn Error>> {
+        for chunk in addresses.chunks(EXTEND_CHUNK_SIZE) {
+            let extend_ix = extend_lookup_table(
+                *table,
+                authority.pubkey(),
+                Some(authority.pubkey()),
+                chunk.to_vec(),
+            );
+            let result = self.send_instruction(extend_ix, &[authority])?;
+            if let Some(error) = result.error() {
+                return Err(format!("Failed to extend lookup table {}: {}", table, error).into());
+            }
+        }
+        self.warm_lookup_tables();
+        Ok(())
+    }
+
+    fn warm_lookup_tables(&mut self) {
+        let slot = self.get_sysvar::<Clock>().slot;
+        self.warp_to_slot(slot + 1);
+    }
+
+    fn get_lookup_table(&self, table: &Pubkey) -> Option<AddressLookupTableAccount> {
+        let account = self.get_account(table)?;
+        if account.owner != solana_address_lookup_table_interface::program::id() {
+            return None;
+        }
+        let state = AddressLookupTable::deserialize(&account.data).ok()?;
+        Some(AddressLookupTableAccount {
+            key: *table,
+            addresses: state.addresses.to_vec(),
+        })
+    }
+
+    fn send_instructions_with_lookup_tables(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        tables: &[Pubkey],
+    ) -> Result<TransactionResult, TransactionError> {
+        let payer = signers
+            .first()
+            .ok_or_else(|| TransactionError::BuildError("No signers provided".to_string()))?;
+        let tables = tables
+            .iter()
+            .map(|table| {
+                self.get_lookup_table(table).ok_or_else(|| {
+                    TransactionError::BuildError(format!("Lookup table {} not found", table))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let all_instructions = with_default_compute_budget(self, instructions);
+        let message = v0::Message::try_compile(
+            &payer.pubkey(),
+            &all_instructions,
+            &tables,
+            self.latest_blockhash(),
+        )
+        .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+        let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), signers)
+            .map_err(|e| TransactionError::BuildError(e.to_string()))?;
+        let result = self.send_versioned_transaction_result(transaction)?;
+        Ok(result.with_prepended_instructions(all_instructions.len() - instructions.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use solana_program::system_instruction;
+
+    #[test]
+    fn test_lookup_table_transaction() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let recipients: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+
+        let table = svm.create_lookup_table(&authority, &recipients).unwrap();
+        assert_eq!(svm.get_lookup_table(&table).unwrap().addresses, recipients);
+
+        // 40 transfers don't fit in a legacy transaction
+        let transfers: Vec<Instruction> = recipients
+            .iter()
+            .map(|to| system_instruction::transfer(&authority.pubkey(), to, 1_000_000))
+            .collect();
+        assert!(crate::transaction::transaction_size(&transfers, &[&authority]).unwrap() > 1232);
+
+        let result = svm
+            .send_instructions_with_lookup_tables(&transfers, &[&authority], &[table])
+            .unwrap();
+        result.assert_success();
+        for recipient in &recipients {
+            assert_eq!(svm.get_balance(recipient), Some(1_000_000));
+        }
+
+        // Accounts loaded through the table are registered too
+        let tracked: Vec<Pubkey> = svm.iter_accounts(None).map(|(pubkey, _)| pubkey).collect();
+        assert!(recipients.iter().all(|recipient| tracked.contains(recipient)));
+    }
+
+    #[test]
+    fn test_extend_lookup_table() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        let table = svm.create_lookup_table(&authority, &[first]).unwrap();
+        svm.extend_lookup_table(&table, &authority, &[second])
+            .unwrap();
+        assert_eq!(
+            svm.get_lookup_table(&table).unwrap().addresses,
+            vec![first, second]
+        );
+
+        // A second table from the same authority lands at a different address
+        let other = svm.create_lookup_table(&authority, &[]).unwrap();
+        assert_ne!(other, table);
+        assert!(svm.get_lookup_table(&Pubkey::new_unique()).is_none());
+    }
+}
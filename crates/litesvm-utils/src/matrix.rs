@@ -0,0 +1,137 @@
+//! Parameterized test runner
+//!
+//! [`matrix`] runs one test body over every parameter set and reports which sets
+//! failed, replacing hand-rolled nested loops where a failure doesn't say which
+//! combination broke. The `product*` functions build the parameter sets.
+
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Run `test` once for every parameter set in `cases`
+///
+/// Every case runs even if an earlier one fails. Each failing case is reported on
+/// stderr with its parameters, and the runner then panics listing all of them.
+///
+/// # Panics
+///
+/// Panics if `test` panicked for at least one case
+///
+/// # Example
+///
+/// ```ignore
+/// matrix(product2([6u8, 9], [1u64, 1_000_000]), |&(decimals, amount)| {
+///     let mut svm = LiteSVM::new();
+///     let authority = svm.create_funded_account(10_000_000_000).unwrap();
+///     let mint = svm.create_token_mint(&authority, decimals).unwrap();
+///     // ...
+/// });
+/// ```
+pub fn matrix<P, F>(cases: impl IntoIterator<Item = P>, mut test: F)
+where
+    P: Debug,
+    F: FnMut(&P),
+{
+    let mut total = 0;
+    let mut failures = Vec::new();
+    for case in cases {
+        total += 1;
+        if panic::catch_unwind(AssertUnwindSafe(|| test(&case))).is_err() {
+            eprintln!("Matrix case failed: {:?}", case);
+            failures.push(format!("{:?}", case));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} matrix cases failed:\n  {}",
+        failures.len(),
+        total,
+        failures.join("\n  ")
+    );
+}
+
+/// All combinations of two parameter lists
+pub fn product2<A, B>(a: impl IntoIterator<Item = A>, b: impl IntoIterator<Item = B>) -> Vec<(A, B)>
+where
+    A: Clone,
+    B: Clone,
+{
+    let b: Vec<B> = b.into_iter().collect();
+    a.into_iter()
+        .flat_map(|a| b.iter().map(move |b| (a.clone(), b.clone())))
+        .collect()
+}
+
+/// All combinations of three parameter lists
+pub fn product3<A, B, C>(
+    a: impl IntoIterator<Item = A>,
+    b: impl IntoIterator<Item = B>,
+    c: impl IntoIterator<Item = C>,
+) -> Vec<(A, B, C)>
+where
+    A: Clone,
+    B: Clone,
+    C: Clone,
+{
+    let c: Vec<C> = c.into_iter().collect();
+    product2(a, b)
+        .into_iter()
+        .flat_map(|(a, b)| c.iter().map(move |c| (a.clone(), b.clone(), c.clone())))
+        .collect()
+}
+
+/// All combinations of four parameter lists
+pub fn product4<A, B, C, D>(
+    a: impl IntoIterator<Item = A>,
+    b: impl IntoIterator<Item = B>,
+    c: impl IntoIterator<Item = C>,
+    d: impl IntoIterator<Item = D>,
+) -> Vec<(A, B, C, D)>
+where
+    A: Clone,
+    B: Clone,
+    C: Clone,
+    D: Clone,
+{
+    let d: Vec<D> = d.into_iter().collect();
+    product3(a, b, c)
+        .into_iter()
+        .flat_map(|(a, b, c)| {
+            d.iter()
+                .map(move |d| (a.clone(), b.clone(), c.clone(), d.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product() {
+        assert_eq!(
+            product2([1, 2], ['a', 'b']),
+            vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]
+        );
+        assert_eq!(product3([1, 2], [true], [0u8, 1, 2]).len(), 6);
+        assert_eq!(product4([1, 2], [1, 2], [1, 2], [1, 2]).len(), 16);
+    }
+
+    #[test]
+    fn test_matrix_runs_every_case() {
+        let mut seen = Vec::new();
+        matrix(product2([6u8, 9], [1u64, 100]), |case| seen.push(*case));
+        assert_eq!(seen.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 of 3 matrix cases failed:\n  (9, \"large\")")]
+    fn test_matrix_labels_failures() {
+        matrix(
+            [(6u8, "small"), (9, "large"), (9, "small")],
+            |&(decimals, size)| {
+                assert!(!(decimals == 9 && size == "large"));
+            },
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Metaplex NFT fixtures
+//!
+//! [`NftHelpers::create_nft`] creates a 0-decimal mint, mints one token to the
+//! authority and writes the Token Metadata `Metadata` and `MasterEditionV2` accounts
+//! directly, in the layout the Metaplex program uses. Programs that read or CPI into
+//! NFT metadata can then be tested without hand-rolling Metaplex instructions.
+//!
+//! The Token Metadata program itself isn't vendored. Programs that CPI into it need
+//! its binary, e.g. dumped with
+//! `solana program dump metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s mpl_token_metadata.so`,
+//! and loaded with [`LiteSVMBuilder::with_token_metadata_program`] or
+//! [`NftHelpers::add_token_metadata_program`].
+//!
+//! ```ignore
+//! let nft = svm.create_nft(&artist, "Rock #1", "ROCK", "https://example.com/1.json")?;
+//! assert_eq!(svm.get_nft_metadata(&nft.mint).unwrap().name, "Rock #1");
+//! ```
+//!
+//! [`LiteSVMBuilder::with_token_metadata_program`]:
+//!     crate::LiteSVMBuilder::with_token_metadata_program
+
+use crate::surgery::StateSurgery;
+use crate::test_helpers::TestHelpers;
+use litesvm::LiteSVM;
+use solana_program::program_option::COption;
+use solana_program::pubkey::Pubkey;
+use solana_program_pack::Pack;
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_token::state::Mint;
+use std::error::Error;
+
+/// The Metaplex Token Metadata program ID
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Maximum name length, in bytes
+pub const MAX_NAME_LENGTH: usize = 32;
+/// Maximum symbol length, in bytes
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+/// Maximum URI length, in bytes
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// Allocated size of a metadata account
+const MAX_METADATA_LEN: usize = 679;
+/// Allocated size of a master edition account
+const MAX_MASTER_EDITION_LEN: usize = 282;
+
+/// `Key::MetadataV1` account tag
+const KEY_METADATA_V1: u8 = 4;
+/// `Key::MasterEditionV2` account tag
+const KEY_MASTER_EDITION_V2: u8 = 6;
+/// `TokenStandard::NonFungible`
+const TOKEN_STANDARD_NON_FUNGIBLE: u8 = 0;
+
+/// Accounts of an NFT created by [`NftHelpers::create_nft`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nft {
+    /// The NFT mint (0 decimals, supply 1)
+    pub mint: Pubkey,
+    /// The Token Metadata `Metadata` PDA
+    pub metadata: Pubkey,
+    /// The Token Metadata `MasterEditionV2` PDA, also mint and freeze authority
+    pub master_edition: Pubkey,
+    /// The authority's associated token account holding the NFT
+    pub token_account: Pubkey,
+}
+
+/// Fields read back from a metadata account by [`NftHelpers::get_nft_metadata`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftMetadata {
+    /// Update authority of the metadata
+    pub update_authority: Pubkey,
+    /// The NFT mint
+    pub mint: Pubkey,
+    /// Name, without Metaplex's trailing null padding
+    pub name: String,
+    /// Symbol, without padding
+    pub symbol: String,
+    /// URI, without padding
+    pub uri: String,
+}
+
+/// Derive the metadata PDA of `mint`
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Derive the master edition PDA of `mint`, with its bump
+pub fn find_master_edition_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+}
+
+/// Metaplex NFT helpers for LiteSVM
+pub trait NftHelpers {
+    /// Create an NFT owned by `authority`
+    ///
+    /// Creates the mint and the authority's associated token account, mints one
+    /// token, and writes the metadata and master edition accounts directly. As with
+    /// the Metaplex program, mint and freeze authority move to the master edition,
+    /// and `authority` becomes the update authority. The master edition has a max
+    /// supply of 0 (no prints).
+    ///
+    /// # Errors
+    ///
+    /// Fails if `name`, `symbol` or `uri` exceed Metaplex's length limits
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use litesvm_utils::{NftHelpers, TestHelpers};
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// # let artist = svm.create_funded_account(10_000_000_000).unwrap();
+    /// let nft = svm
+    ///     .create_nft(&artist, "Rock #1", "ROCK", "https://example.com/1.json")
+    ///     .unwrap();
+    /// ```
+    fn create_nft(
+        &mut self,
+        authority: &Keypair,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+    ) -> Result<Nft, Box<dyn Error>>;
+
+    /// Read the name, symbol, URI and authorities of `mint`'s metadata account
+    ///
+    /// Returns `None` if the metadata account doesn't exist or can't be parsed.
+    fn get_nft_metadata(&self, mint: &Pubkey) -> Option<NftMetadata>;
+
+    /// Deploy the Token Metadata program from its binary
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use litesvm_utils::NftHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// # let program_bytes = vec![];
+    /// svm.add_token_metadata_program(&program_bytes);
+    /// ```
+    fn add_token_metadata_program(&mut self, program_bytes: &[u8]);
+}
+
+impl NftHelpers for LiteSVM {
+    #[track_caller]
+    fn create_nft(
+        &mut self,
+        authority: &Keypair,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+    ) -> Result<Nft, Box<dyn Error>> {
+        check_length("name", name, MAX_NAME_LENGTH)?;
+        check_length("symbol", symbol, MAX_SYMBOL_LENGTH)?;
+        check_length("uri", uri, MAX_URI_LENGTH)?;
+
+        let mint = self.create_token_mint(authority, 0)?.pubkey();
+        let token_account = self.create_associated_token_account(&mint, authority)?;
+        self.mint_to(&mint, &token_account, authority, 1)?;
+
+        let metadata = find_metadata_address(&mint);
+        let (master_edition, edition_bump) = find_master_edition_address(&mint);
+
+        // Metaplex hands both authorities to the master edition
+        let mut mint_account = self
+            .get_account(&mint)
+            .ok_or_else(|| format!("Mint {} not found", mint))?;
+        let mut mint_state = Mint::unpack(&mint_account.data)?;
+        mint_state.mint_authority = COption::Some(master_edition);
+        mint_state.freeze_authority = COption::Some(master_edition);
+        Mint::pack(mint_state, &mut mint_account.data)?;
+        StateSurgery::new(self).write("create_nft (mint authorities)", &mint, mint_account)?;
+
+        let mut data = vec![KEY_METADATA_V1];
+        data.extend_from_slice(authority.pubkey().as_ref());
+        data.extend_from_slice(mint.as_ref());
+        write_padded_string(&mut data, name, MAX_NAME_LENGTH);
+        write_padded_string(&mut data, symbol, MAX_SYMBOL_LENGTH);
+        write_padded_string(&mut data, uri, MAX_URI_LENGTH);
+        data.extend_from_slice(&0u16.to_le_bytes()); // seller_fee_basis_points
+        data.push(0); // creators: None
+        data.push(0); // primary_sale_happened
+        data.push(1); // is_mutable
+        data.extend_from_slice(&[1, edition_bump]); // edition_nonce
+        data.extend_from_slice(&[1, TOKEN_STANDARD_NON_FUNGIBLE]); // token_standard
+        data.resize(MAX_METADATA_LEN, 0); // collection, uses, ... : None
+        write_metadata_account(self, "create_nft (metadata)", &metadata, data)?;
+
+        let mut data = vec![KEY_MASTER_EDITION_V2];
+        data.extend_from_slice(&0u64.to_le_bytes()); // supply
+        data.extend_from_slice(&[1]); // max_supply: Some(0)
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.resize(MAX_MASTER_EDITION_LEN, 0);
+        write_metadata_account(self, "create_nft (master edition)", &master_edition, data)?;
+
+        Ok(Nft {
+            mint,
+            metadata,
+            master_edition,
+            token_account,
+        })
+    }
+
+    fn get_nft_metadata(&self, mint: &Pubkey) -> Option<NftMetadata> {
+        let account = self.get_account(&find_metadata_address(mint))?;
+        if account.owner != TOKEN_METADATA_PROGRAM_ID {
+            return None;
+        }
+        let data = account.data;
+        if data.first() != Some(&KEY_METADATA_V1) {
+            return None;
+        }
+
+        let update_authority = Pubkey::try_from(data.get(1..33)?).ok()?;
+        let mint = Pubkey::try_from(data.get(33..65)?).ok()?;
+        let mut rest = data.get(65..)?;
+        let name = read_string(&mut rest)?;
+        let symbol = read_string(&mut rest)?;
+        let uri = read_string(&mut rest)?;
+        Some(NftMetadata {
+            update_authority,
+            mint,
+            name,
+            symbol,
+            uri,
+        })
+    }
+
+    fn add_token_metadata_program(&mut self, program_bytes: &[u8]) {
+        self.add_program(TOKEN_METADATA_PROGRAM_ID, program_bytes);
+    }
+}
+
+/// Reject a metadata field longer than Metaplex allows
+fn check_length(field: &str, value: &str, max: usize) -> Result<(), Box<dyn Error>> {
+    if value.len() > max {
+        return Err(format!(
+            "NFT {} is {} bytes, the maximum is {}",
+            field,
+            value.len(),
+            max
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Append a borsh string null-padded to `len` bytes, as Metaplex stores them
+fn write_padded_string(data: &mut Vec<u8>, value: &str, len: usize) {
+    data.extend_from_slice(&(len as u32).to_le_bytes());
+    data.extend_from_slice(value.as_bytes());
+    data.resize(data.len() + len - value.len(), 0);
+}
+
+/// Read a borsh string and strip its null padding
+fn read_string(data: &mut &[u8]) -> Option<String> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let bytes = data.get(4..4 + len)?;
+    *data = &data[4 + len..];
+    let value = std::str::from_utf8(bytes).ok()?;
+    Some(value.trim_end_matches('\0').to_string())
+}
+
+/// Write a rent-exempt account owned by the Token Metadata program
+#[track_caller]
+fn write_metadata_account(
+    svm: &mut LiteSVM,
+    operation: &str,
+    pubkey: &Pubkey,
+    data: Vec<u8>,
+) -> Result<(), Box<dyn Error>> {
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: TOKEN_METADATA_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    StateSurgery::new(svm).write(operation, pubkey, account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assertions::AssertionHelpers;
+
+    #[test]
+    fn test_create_nft() {
+        let mut svm = LiteSVM::new();
+        let artist = svm.create_funded_account(10_000_000_000).unwrap();
+        let nft = svm
+            .create_nft(&artist, "Rock #1", "ROCK", "https://example.com/1.json")
+            .unwrap();
+
+        svm.assert_token_balance(&nft.token_account, 1);
+        svm.assert_mint_supply(&nft.mint, 1);
+        let mint = Mint::unpack(&svm.get_account(&nft.mint).unwrap().data).unwrap();
+        assert_eq!(mint.decimals, 0);
+        assert_eq!(mint.mint_authority, COption::Some(nft.master_edition));
+
+        let metadata = svm.get_nft_metadata(&nft.mint).unwrap();
+        assert_eq!(metadata.name, "Rock #1");
+        assert_eq!(metadata.symbol, "ROCK");
+        assert_eq!(metadata.uri, "https://example.com/1.json");
+        assert_eq!(metadata.update_authority, artist.pubkey());
+        assert_eq!(metadata.mint, nft.mint);
+
+        let edition = svm.get_account(&nft.master_edition).unwrap();
+        assert_eq!(edition.owner, TOKEN_METADATA_PROGRAM_ID);
+        assert_eq!(edition.data.len(), MAX_MASTER_EDITION_LEN);
+        assert_eq!(edition.data[0], KEY_MASTER_EDITION_V2);
+    }
+
+    #[test]
+    fn test_create_nft_rejects_long_name() {
+        let mut svm = LiteSVM::new();
+        let artist = svm.create_funded_account(10_000_000_000).unwrap();
+        let name = "x".repeat(MAX_NAME_LENGTH + 1);
+        let err = svm.create_nft(&artist, &name, "X", "").unwrap_err();
+        assert!(err.to_string().contains("NFT name is 33 bytes"));
+    }
+}
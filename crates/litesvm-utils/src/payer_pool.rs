@@ -0,0 +1,182 @@
+//! Rotating fee payers for helper-executed transactions
+//!
+//! Relying on a single implicit payer hides bugs where a program accidentally
+//! depends on who pays the fees. A [`PayerPool`] hands out payers round-robin
+//! so consecutive transactions are paid by different accounts.
+
+use crate::account_registry::track_accounts;
+use crate::faucet::fund_account;
+use crate::keypairs::new_keypair;
+use crate::transaction::{TransactionError, TransactionHelpers, TransactionResult};
+use litesvm::LiteSVM;
+use solana_program::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use std::error::Error;
+
+/// A set of fee payers used in round-robin order
+///
+/// # Example
+///
+/// ```ignore
+/// let mut pool = PayerPool::create(&mut svm, 3, 10_000_000_000)?;
+///
+/// // Each call is paid by the next payer in the pool
+/// pool.send_instruction(&mut svm, ix1, &[&user])?.assert_success();
+/// pool.send_instruction(&mut svm, ix2, &[&user])?.assert_success();
+/// ```
+pub struct PayerPool {
+    payers: Vec<Keypair>,
+    next: usize,
+}
+
+impl Clone for PayerPool {
+    fn clone(&self) -> Self {
+        Self {
+            payers: self.payers.iter().map(|p| p.insecure_clone()).collect(),
+            next: self.next,
+        }
+    }
+}
+
+impl PayerPool {
+    /// Create a pool from existing keypairs
+    ///
+    /// # Panics
+    ///
+    /// Panics if `payers` is empty
+    pub fn new(payers: Vec<Keypair>) -> Self {
+        assert!(!payers.is_empty(), "PayerPool requires at least one payer");
+        Self { payers, next: 0 }
+    }
+
+    /// Create a pool of `count` freshly funded payers
+    #[track_caller]
+    pub fn create(svm: &mut LiteSVM, count: usize, lamports: u64) -> Result<Self, Box<dyn Error>> {
+        if count == 0 {
+            return Err("PayerPool requires at least one payer".into());
+        }
+
+        let mut payers = Vec::with_capacity(count);
+        for _ in 0..count {
+            let payer = new_keypair(svm);
+            fund_account(svm, &payer.pubkey(), lamports)?;
+            track_accounts(svm, [payer.pubkey()]);
+            payers.push(payer);
+        }
+        Ok(Self::new(payers))
+    }
+
+    /// Get the next payer in round-robin order
+    pub fn next_payer(&mut self) -> &Keypair {
+        let payer = &self.payers[self.next];
+        self.next = (self.next + 1) % self.payers.len();
+        payer
+    }
+
+    /// Get all payers in the pool
+    pub fn payers(&self) -> &[Keypair] {
+        &self.payers
+    }
+
+    /// Get the number of payers in the pool
+    pub fn len(&self) -> usize {
+        self.payers.len()
+    }
+
+    /// Check whether the pool is empty (never true for a constructed pool)
+    pub fn is_empty(&self) -> bool {
+        self.payers.is_empty()
+    }
+
+    /// Send a single instruction paid by the next payer
+    ///
+    /// `signers` only needs the instruction's own signers; the payer is added.
+    pub fn send_instruction(
+        &mut self,
+        svm: &mut LiteSVM,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError> {
+        self.send_instructions(svm, &[instruction], signers)
+    }
+
+    /// Send multiple instructions in one transaction paid by the next payer
+    pub fn send_instructions(
+        &mut self,
+        svm: &mut LiteSVM,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError> {
+        let payer = self.next_payer();
+        let all_signers = with_payer(payer, signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            svm.latest_blockhash(),
+        );
+
+        svm.send_transaction_result(tx)
+    }
+}
+
+/// Put `payer` first in the signer list, dropping any duplicate of it
+pub(crate) fn with_payer<'a>(payer: &'a Keypair, signers: &[&'a Keypair]) -> Vec<&'a Keypair> {
+    let mut all_signers = vec![payer];
+    all_signers.extend(
+        signers
+            .iter()
+            .copied()
+            .filter(|signer| signer.pubkey() != payer.pubkey()),
+    );
+    all_signers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use solana_program::system_instruction;
+
+    #[test]
+    fn test_payer_pool_round_robin() {
+        let mut svm = LiteSVM::new();
+        let mut pool = PayerPool::create(&mut svm, 3, 1_000_000_000).unwrap();
+        assert_eq!(pool.len(), 3);
+
+        let order: Vec<_> = (0..4).map(|_| pool.next_payer().pubkey()).collect();
+        assert_eq!(order[0], pool.payers()[0].pubkey());
+        assert_eq!(order[1], pool.payers()[1].pubkey());
+        assert_eq!(order[2], pool.payers()[2].pubkey());
+        assert_eq!(order[3], order[0]);
+    }
+
+    #[test]
+    fn test_payer_pool_send_instruction() {
+        let mut svm = LiteSVM::new();
+        let mut pool = PayerPool::create(&mut svm, 2, 1_000_000_000).unwrap();
+        let user = svm.create_funded_account(1_000_000_000).unwrap();
+        let recipient = Keypair::new().pubkey();
+
+        for _ in 0..2 {
+            let ix = system_instruction::transfer(&user.pubkey(), &recipient, 1_000_000);
+            pool.send_instruction(&mut svm, ix, &[&user])
+                .unwrap()
+                .assert_success();
+        }
+
+        // The user only paid for the transfers, each payer paid one fee
+        assert_eq!(svm.get_balance(&user.pubkey()).unwrap(), 998_000_000);
+        for payer in pool.payers() {
+            assert!(svm.get_balance(&payer.pubkey()).unwrap() < 1_000_000_000);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "PayerPool requires at least one payer")]
+    fn test_payer_pool_empty() {
+        PayerPool::new(vec![]);
+    }
+}
@@ -0,0 +1,29 @@
+//! Everything a typical test needs, in one import
+//!
+//! ```no_run
+//! use litesvm_utils::prelude::*;
+//!
+//! let mut svm = LiteSVM::new();
+//! let user = svm.create_funded_account(10_000_000_000).unwrap();
+//! svm.assert_sol_balance(&user.pubkey(), 10_000_000_000);
+//! ```
+
+#[cfg(feature = "spl")]
+pub use crate::actor::{Actor, ActorHelpers};
+pub use crate::assertions::AssertionHelpers;
+pub use crate::builder::{LiteSVMBuilder, ProgramTestExt};
+pub use crate::lookup_table::LookupTableHelpers;
+#[cfg(feature = "spl")]
+pub use crate::nft::NftHelpers;
+pub use crate::scenarios::ScenarioHelpers;
+pub use crate::soft_assertions::SoftAssertions;
+pub use crate::stake::StakeHelpers;
+pub use crate::test_helpers::TestHelpers;
+#[cfg(feature = "spl")]
+pub use crate::token_2022::Token2022Helpers;
+pub use crate::transaction::{TransactionHelpers, TransactionResult};
+
+pub use litesvm::LiteSVM;
+pub use solana_program::instruction::{AccountMeta, Instruction};
+pub use solana_program::pubkey::Pubkey;
+pub use solana_sdk::signature::{Keypair, Signer};
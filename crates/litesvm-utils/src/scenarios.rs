@@ -0,0 +1,371 @@
+//! Ready-made adversarial scenarios for security-focused tests
+//!
+//! This module packages common audit scenarios (account revival, front-running)
+//! into single calls so tests don't need manual state surgery to reproduce them.
+
+use crate::transaction::{TransactionError, TransactionHelpers, TransactionResult};
+use litesvm::LiteSVM;
+use solana_program::instruction::Instruction;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_instruction;
+use solana_sdk::signature::{Keypair, Signer};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// When the closed account is re-funded during a revival attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevivalTiming {
+    /// Close, re-fund and re-invoke within one atomic transaction
+    SameTransaction,
+    /// Close in one transaction, then re-fund and re-invoke in the next one
+    NextTransaction,
+}
+
+/// Outcome of an account revival attempt
+#[derive(Debug)]
+pub struct RevivalAttempt {
+    /// Result of the close transaction (only set for [`RevivalTiming::NextTransaction`])
+    pub close_result: Option<TransactionResult>,
+    /// Result of the transaction that re-funded the account and re-invoked the program
+    pub revival_result: TransactionResult,
+}
+
+impl RevivalAttempt {
+    /// Check whether the program rejected the revived account
+    pub fn is_defended(&self) -> bool {
+        !self.revival_result.is_success()
+    }
+
+    /// Assert that the program rejected the revived account
+    ///
+    /// # Panics
+    ///
+    /// Panics if the re-invocation succeeded on the revived account
+    ///
+    /// # Returns
+    ///
+    /// Returns self for chaining
+    pub fn assert_defended(&self) -> &Self {
+        assert!(
+            self.is_defended(),
+            "Program accepted a revived account: re-invocation after close succeeded.\nLogs:\n{}",
+            self.revival_result.logs().join("\n")
+        );
+        self
+    }
+}
+
+/// A prepared transaction submitted by one actor in an ordering scenario
+pub struct ActorTransaction<'a> {
+    /// Instructions executed atomically in one transaction
+    pub instructions: Vec<Instruction>,
+    /// Signers of the transaction; the first one pays the fees
+    pub signers: Vec<&'a Keypair>,
+}
+
+impl<'a> ActorTransaction<'a> {
+    /// Create a new actor transaction
+    pub fn new(instructions: Vec<Instruction>, signers: Vec<&'a Keypair>) -> Self {
+        Self {
+            instructions,
+            signers,
+        }
+    }
+}
+
+/// The order in which two actor transactions were executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionOrder {
+    /// Actor A's transaction landed first
+    AThenB,
+    /// Actor B's transaction landed first (B front-ran A)
+    BThenA,
+}
+
+impl fmt::Display for ExecutionOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionOrder::AThenB => write!(f, "A then B"),
+            ExecutionOrder::BThenA => write!(f, "B then A"),
+        }
+    }
+}
+
+/// One executed ordering of two actor transactions
+pub struct OrderingRun {
+    /// The order the transactions were executed in
+    pub order: ExecutionOrder,
+    /// The state after both transactions executed
+    pub svm: LiteSVM,
+    /// Result of actor A's transaction
+    pub a: TransactionResult,
+    /// Result of actor B's transaction
+    pub b: TransactionResult,
+}
+
+/// Adversarial scenario helpers for LiteSVM
+pub trait ScenarioHelpers {
+    /// Attempt a closed-account reinitialization (revival) attack
+    ///
+    /// Executes `close`, transfers enough lamports back into `account` to make its
+    /// original data length rent-exempt again, then executes `reinvoke`. A program
+    /// that properly closes accounts must reject the re-invocation.
+    ///
+    /// The first signer pays fees and funds the revival transfer.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let attempt = svm.attempt_account_revival(
+    ///     &escrow,
+    ///     &[close_ix],
+    ///     &[withdraw_ix],
+    ///     &[&maker],
+    ///     RevivalTiming::SameTransaction,
+    /// )?;
+    /// attempt.assert_defended();
+    /// ```
+    fn attempt_account_revival(
+        &mut self,
+        account: &Pubkey,
+        close: &[Instruction],
+        reinvoke: &[Instruction],
+        signers: &[&Keypair],
+        timing: RevivalTiming,
+    ) -> Result<RevivalAttempt, TransactionError>;
+
+    /// Execute two actors' transactions in both orders and check each outcome
+    ///
+    /// Each ordering runs against its own copy of the current state, which is left
+    /// untouched. Unless `same_slot` is set, the slot advances by one between the
+    /// two transactions. If `check` panics, the failing ordering is reported on
+    /// stderr before the panic propagates.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let victim = ActorTransaction::new(vec![swap_ix], vec![&alice]);
+    /// let attacker = ActorTransaction::new(vec![sandwich_ix], vec![&mallory]);
+    /// svm.run_both_orders(&victim, &attacker, true, |run| {
+    ///     if run.order == ExecutionOrder::BThenA {
+    ///         run.a.assert_error("SlippageExceeded");
+    ///     }
+    /// })?;
+    /// ```
+    fn run_both_orders<F>(
+        &self,
+        a: &ActorTransaction,
+        b: &ActorTransaction,
+        same_slot: bool,
+        check: F,
+    ) -> Result<(), TransactionError>
+    where
+        F: FnMut(&OrderingRun);
+}
+
+impl ScenarioHelpers for LiteSVM {
+    fn attempt_account_revival(
+        &mut self,
+        account: &Pubkey,
+        close: &[Instruction],
+        reinvoke: &[Instruction],
+        signers: &[&Keypair],
+        timing: RevivalTiming,
+    ) -> Result<RevivalAttempt, TransactionError> {
+        let funder = signers
+            .first()
+            .ok_or_else(|| TransactionError::BuildError("No signers provided".to_string()))?;
+
+        let data_len = self.get_account(account).map_or(0, |a| a.data.len());
+        let refund = self.minimum_balance_for_rent_exemption(data_len);
+        let refund_ix = system_instruction::transfer(&funder.pubkey(), account, refund);
+
+        match timing {
+            RevivalTiming::SameTransaction => {
+                let mut instructions = close.to_vec();
+                instructions.push(refund_ix);
+                instructions.extend_from_slice(reinvoke);
+
+                Ok(RevivalAttempt {
+                    close_result: None,
+                    revival_result: self.send_instructions(&instructions, signers)?,
+                })
+            }
+            RevivalTiming::NextTransaction => {
+                let close_result = self.send_instructions(close, signers)?;
+                if !close_result.is_success() {
+                    return Err(TransactionError::ExecutionFailed(format!(
+                        "Close transaction failed: {}",
+                        close_result.error().map_or("unknown error", |e| e.as_str())
+                    )));
+                }
+
+                let mut instructions = vec![refund_ix];
+                instructions.extend_from_slice(reinvoke);
+                let revival_result = self.send_instructions(&instructions, signers)?;
+
+                Ok(RevivalAttempt {
+                    close_result: Some(close_result),
+                    revival_result,
+                })
+            }
+        }
+    }
+
+    fn run_both_orders<F>(
+        &self,
+        a: &ActorTransaction,
+        b: &ActorTransaction,
+        same_slot: bool,
+        mut check: F,
+    ) -> Result<(), TransactionError>
+    where
+        F: FnMut(&OrderingRun),
+    {
+        for order in [ExecutionOrder::AThenB, ExecutionOrder::BThenA] {
+            let (first, second) = match order {
+                ExecutionOrder::AThenB => (a, b),
+                ExecutionOrder::BThenA => (b, a),
+            };
+
+            let mut svm = self.clone();
+            let first_result = svm.send_instructions(&first.instructions, &first.signers)?;
+            if !same_slot {
+                let slot = svm.get_sysvar::<solana_program::clock::Clock>().slot;
+                svm.warp_to_slot(slot + 1);
+            }
+            let second_result = svm.send_instructions(&second.instructions, &second.signers)?;
+
+            let (a_result, b_result) = match order {
+                ExecutionOrder::AThenB => (first_result, second_result),
+                ExecutionOrder::BThenA => (second_result, first_result),
+            };
+            let run = OrderingRun {
+                order,
+                svm,
+                a: a_result,
+                b: b_result,
+            };
+
+            if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| check(&run))) {
+                eprintln!("Ordering check failed for execution order: {}", order);
+                panic::resume_unwind(panic);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+
+    #[test]
+    fn test_revival_next_transaction_undefended() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let victim = svm.create_funded_account(1_000_000).unwrap();
+
+        // "Close" by draining all lamports, then "re-invoke" with another transfer.
+        // The system program happily accepts the revived account.
+        let close = system_instruction::transfer(&victim.pubkey(), &owner.pubkey(), 1_000_000);
+        let reinvoke = system_instruction::transfer(&victim.pubkey(), &owner.pubkey(), 1);
+
+        let attempt = svm
+            .attempt_account_revival(
+                &victim.pubkey(),
+                &[close],
+                &[reinvoke],
+                &[&owner, &victim],
+                RevivalTiming::NextTransaction,
+            )
+            .unwrap();
+
+        assert!(attempt.close_result.as_ref().unwrap().is_success());
+        assert!(!attempt.is_defended());
+    }
+
+    #[test]
+    fn test_revival_same_transaction_defended() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let victim = svm.create_funded_account(1_000_000).unwrap();
+
+        let close = system_instruction::transfer(&victim.pubkey(), &owner.pubkey(), 1_000_000);
+        // Re-invocation that can't succeed: spending more than the refund
+        let reinvoke = system_instruction::transfer(&victim.pubkey(), &owner.pubkey(), 10_000_000);
+
+        let attempt = svm
+            .attempt_account_revival(
+                &victim.pubkey(),
+                &[close],
+                &[reinvoke],
+                &[&owner, &victim],
+                RevivalTiming::SameTransaction,
+            )
+            .unwrap();
+
+        assert!(attempt.close_result.is_none());
+        attempt.assert_defended();
+    }
+
+    #[test]
+    fn test_run_both_orders() {
+        let mut svm = LiteSVM::new();
+        let alice = svm.create_funded_account(10_000_000_000).unwrap();
+        let mallory = svm.create_funded_account(10_000_000_000).unwrap();
+        let pool = svm.create_funded_account(1_000_000).unwrap();
+
+        // Both actors try to drain the same pool; only the first one can succeed
+        let a = ActorTransaction::new(
+            vec![system_instruction::transfer(&pool.pubkey(), &alice.pubkey(), 1_000_000)],
+            vec![&alice, &pool],
+        );
+        let b = ActorTransaction::new(
+            vec![system_instruction::transfer(&pool.pubkey(), &mallory.pubkey(), 1_000_000)],
+            vec![&mallory, &pool],
+        );
+
+        let mut orders = Vec::new();
+        svm.run_both_orders(&a, &b, false, |run| {
+            orders.push(run.order);
+            match run.order {
+                ExecutionOrder::AThenB => {
+                    assert!(run.a.is_success());
+                    assert!(!run.b.is_success());
+                }
+                ExecutionOrder::BThenA => {
+                    assert!(run.b.is_success());
+                    assert!(!run.a.is_success());
+                }
+            }
+        })
+        .unwrap();
+
+        assert_eq!(orders, vec![ExecutionOrder::AThenB, ExecutionOrder::BThenA]);
+        // The original state is untouched
+        assert_eq!(svm.get_balance(&pool.pubkey()), Some(1_000_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Program accepted a revived account")]
+    fn test_revival_assert_defended_fails() {
+        let mut svm = LiteSVM::new();
+        let owner = svm.create_funded_account(10_000_000_000).unwrap();
+        let victim = svm.create_funded_account(1_000_000).unwrap();
+
+        let close = system_instruction::transfer(&victim.pubkey(), &owner.pubkey(), 1_000_000);
+        let reinvoke = system_instruction::transfer(&victim.pubkey(), &owner.pubkey(), 1);
+
+        svm.attempt_account_revival(
+            &victim.pubkey(),
+            &[close],
+            &[reinvoke],
+            &[&owner, &victim],
+            RevivalTiming::SameTransaction,
+        )
+        .unwrap()
+        .assert_defended();
+    }
+}
@@ -0,0 +1,278 @@
+//! Collecting assertion failures instead of stopping at the first
+//!
+//! A test that checks a dozen balances after a complex instruction normally
+//! reports only the first mismatch. [`SoftAssertions`] runs every check through
+//! the non-panicking `check_*` methods of [`AssertionHelpers`] and reports all the
+//! failures together when [`verify`](SoftAssertions::verify) is called.
+//!
+//! ```ignore
+//! let mut soft = SoftAssertions::new(&svm);
+//! soft.assert_token_balance(&alice_ata, 900);
+//! soft.assert_token_balance(&bob_ata, 100);
+//! soft.assert_sol_balance(&vault, 0);
+//! soft.verify(); // panics listing every mismatch
+//! ```
+
+use crate::assertions::{AssertionError, AssertionHelpers, BalanceSnapshot};
+use litesvm::LiteSVM;
+use solana_program::clock::Clock;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::{Sysvar, SysvarId};
+
+/// Accumulates failed assertions against a VM
+///
+/// Each method mirrors the [`AssertionHelpers`] method of the same name but
+/// records a failure instead of panicking. Call [`verify`](Self::verify) at the
+/// end of the test, or the failures are lost.
+#[must_use = "call `verify` to report the collected failures"]
+pub struct SoftAssertions<'a> {
+    svm: &'a LiteSVM,
+    failures: Vec<AssertionError>,
+}
+
+impl<'a> SoftAssertions<'a> {
+    /// Start collecting assertions against `svm`
+    pub fn new(svm: &'a LiteSVM) -> Self {
+        Self {
+            svm,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Record the outcome of any check, such as a custom `check_*` helper
+    pub fn check(&mut self, result: Result<(), AssertionError>) -> &mut Self {
+        if let Err(failure) = result {
+            self.failures.push(failure);
+        }
+        self
+    }
+
+    /// Record a failure with `message` unless `condition` holds
+    pub fn assert(&mut self, condition: bool, message: impl Into<String>) -> &mut Self {
+        if !condition {
+            self.failures.push(AssertionError::new(message));
+        }
+        self
+    }
+
+    /// Failures recorded so far
+    pub fn failures(&self) -> &[AssertionError] {
+        &self.failures
+    }
+
+    /// Whether every check so far passed
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panic listing every recorded failure, if there are any
+    #[track_caller]
+    pub fn verify(self) {
+        if self.failures.is_empty() {
+            return;
+        }
+        let failures: Vec<String> = self
+            .failures
+            .iter()
+            .enumerate()
+            .map(|(index, failure)| format!("{}. {}", index + 1, failure))
+            .collect();
+        panic!(
+            "{} soft assertion(s) failed:\n{}",
+            self.failures.len(),
+            failures.join("\n")
+        );
+    }
+
+    /// See [`AssertionHelpers::assert_account_closed`]
+    pub fn assert_account_closed(&mut self, pubkey: &Pubkey) -> &mut Self {
+        let result = self.svm.check_account_closed(pubkey);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_account_exists`]
+    pub fn assert_account_exists(&mut self, pubkey: &Pubkey) -> &mut Self {
+        let result = self.svm.check_account_exists(pubkey);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_token_balance`]
+    #[cfg(feature = "spl")]
+    pub fn assert_token_balance(&mut self, token_account: &Pubkey, expected: u64) -> &mut Self {
+        let result = self.svm.check_token_balance(token_account, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_token_balances`]
+    #[cfg(feature = "spl")]
+    pub fn assert_token_balances(&mut self, expected: &[(&Pubkey, u64)]) -> &mut Self {
+        let result = self.svm.check_token_balances(expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_token_account_frozen`]
+    #[cfg(feature = "spl")]
+    pub fn assert_token_account_frozen(&mut self, token_account: &Pubkey) -> &mut Self {
+        let result = self.svm.check_token_account_frozen(token_account);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_sol_balance`]
+    pub fn assert_sol_balance(&mut self, pubkey: &Pubkey, expected: u64) -> &mut Self {
+        let result = self.svm.check_sol_balance(pubkey, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_sol_balance_change`]
+    pub fn assert_sol_balance_change(
+        &mut self,
+        snapshot: &BalanceSnapshot,
+        pubkey: &Pubkey,
+        expected: i128,
+    ) -> &mut Self {
+        let result = self
+            .svm
+            .check_sol_balance_change(snapshot, pubkey, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_token_balance_change`]
+    #[cfg(feature = "spl")]
+    pub fn assert_token_balance_change(
+        &mut self,
+        snapshot: &BalanceSnapshot,
+        token_account: &Pubkey,
+        expected: i128,
+    ) -> &mut Self {
+        let result = self
+            .svm
+            .check_token_balance_change(snapshot, token_account, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_mint_supply`]
+    #[cfg(feature = "spl")]
+    pub fn assert_mint_supply(&mut self, mint: &Pubkey, expected: u64) -> &mut Self {
+        let result = self.svm.check_mint_supply(mint, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_account_owner`]
+    pub fn assert_account_owner(&mut self, account: &Pubkey, expected_owner: &Pubkey) -> &mut Self {
+        let result = self.svm.check_account_owner(account, expected_owner);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_account_data_len`]
+    pub fn assert_account_data_len(&mut self, account: &Pubkey, expected_len: usize) -> &mut Self {
+        let result = self.svm.check_account_data_len(account, expected_len);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_account_bytes`]
+    pub fn assert_account_bytes(
+        &mut self,
+        account: &Pubkey,
+        offset: usize,
+        expected: &[u8],
+    ) -> &mut Self {
+        let result = self.svm.check_account_bytes(account, offset, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_ata_exists`]
+    #[cfg(feature = "spl")]
+    pub fn assert_ata_exists(&mut self, owner: &Pubkey, mint: &Pubkey) -> &mut Self {
+        let result = self.svm.check_ata_exists(owner, mint);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_ata_closed`]
+    #[cfg(feature = "spl")]
+    pub fn assert_ata_closed(&mut self, owner: &Pubkey, mint: &Pubkey) -> &mut Self {
+        let result = self.svm.check_ata_closed(owner, mint);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_upgrade_authority`]
+    pub fn assert_upgrade_authority(
+        &mut self,
+        program_id: &Pubkey,
+        expected: Option<&Pubkey>,
+    ) -> &mut Self {
+        let result = self.svm.check_upgrade_authority(program_id, expected);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_program_deployed_at`]
+    pub fn assert_program_deployed_at(
+        &mut self,
+        program_id: &Pubkey,
+        expected_slot: u64,
+    ) -> &mut Self {
+        let result = self
+            .svm
+            .check_program_deployed_at(program_id, expected_slot);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_sysvar`]
+    pub fn assert_sysvar<T, F>(&mut self, predicate: F) -> &mut Self
+    where
+        T: Sysvar + SysvarId + std::fmt::Debug,
+        F: FnOnce(&T) -> bool,
+    {
+        let result = self.svm.check_sysvar::<T, F>(predicate);
+        self.check(result)
+    }
+
+    /// See [`AssertionHelpers::assert_clock`]
+    pub fn assert_clock<F>(&mut self, predicate: F) -> &mut Self
+    where
+        F: FnOnce(&Clock) -> bool,
+    {
+        let result = self.svm.check_clock(predicate);
+        self.check(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_soft_assertions_collect_every_failure() {
+        let mut svm = LiteSVM::new();
+        let user = svm.create_funded_account(1_000_000).unwrap();
+        let missing = Pubkey::new_unique();
+
+        let mut soft = SoftAssertions::new(&svm);
+        soft.assert_sol_balance(&user.pubkey(), 1_000_000)
+            .assert_account_exists(&missing)
+            .assert_sol_balance(&user.pubkey(), 7)
+            .assert(1 + 1 == 3, "arithmetic is broken");
+        assert!(!soft.is_ok());
+        assert_eq!(soft.failures().len(), 3);
+
+        let message = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| soft.verify()))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert!(message.starts_with("3 soft assertion(s) failed:\n1. Expected account"));
+        assert!(message.contains("\n2. SOL balance mismatch"));
+        assert!(message.ends_with("\n3. arithmetic is broken"));
+    }
+
+    #[test]
+    fn test_soft_assertions_pass() {
+        let mut svm = LiteSVM::new();
+        let user = svm.create_funded_account(1_000_000).unwrap();
+
+        let mut soft = SoftAssertions::new(&svm);
+        soft.assert_sol_balance(&user.pubkey(), 1_000_000)
+            .assert_clock(|c| c.slot == 0);
+        soft.verify();
+    }
+}
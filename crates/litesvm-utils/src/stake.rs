@@ -0,0 +1,234 @@
+//! Stake account fixtures and simulated epoch rewards
+//!
+//! LiteSVM never pays inflation rewards, so programs that compute yields from stake
+//! accounts have nothing to accrue. [`StakeHelpers`] writes delegated stake accounts
+//! directly and credits them with rewards at each simulated epoch boundary, keeping
+//! the account lamports, the delegated stake and the `StakeHistory` sysvar consistent.
+//!
+//! ```ignore
+//! let stake = svm.create_stake_account(&staker, &voter, 100 * LAMPORTS_PER_SOL)?;
+//! for _ in 0..3 {
+//!     svm.advance_epoch_with_rewards(&[stake.pubkey()], 50)?; // 0.5% per epoch
+//! }
+//! ```
+
+use crate::surgery::StateSurgery;
+use crate::test_helpers::TestHelpers;
+use litesvm::LiteSVM;
+use solana_program::clock::Clock;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_stake_interface::stake_flags::StakeFlags;
+use solana_stake_interface::stake_history::{StakeHistory, StakeHistoryEntry};
+use solana_stake_interface::state::{Authorized, Delegation, Meta, Stake, StakeStateV2};
+use std::error::Error;
+
+/// Basis points in one whole (100%)
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Stake account helpers for LiteSVM
+pub trait StakeHelpers {
+    /// Create a stake account delegating `stake` lamports to `voter`
+    ///
+    /// The account is written directly, with `authority` as both staker and
+    /// withdrawer. The delegation is a bootstrap stake (`activation_epoch` is
+    /// `u64::MAX`), so it is fully effective from the current epoch without any
+    /// warmup. The account holds `stake` plus its rent-exempt reserve.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use litesvm_utils::StakeHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let authority = Pubkey::new_unique();
+    /// # let voter = Pubkey::new_unique();
+    /// let stake = svm.create_stake_account(&authority, &voter, 1_000_000_000).unwrap();
+    /// ```
+    fn create_stake_account(
+        &mut self,
+        authority: &Pubkey,
+        voter: &Pubkey,
+        stake: u64,
+    ) -> Result<Keypair, Box<dyn Error>>;
+
+    /// Read the delegated stake of a stake account
+    ///
+    /// Returns `None` if the account doesn't exist or isn't delegated.
+    fn get_delegated_stake(&self, stake_account: &Pubkey) -> Option<u64>;
+
+    /// End the current epoch, crediting each stake account with `rate_bps` of its stake
+    ///
+    /// Each account's reward (`stake * rate_bps / 10_000`, rounded down) is added to
+    /// both its lamports and its delegated stake, so rewards compound across epochs.
+    /// The total effective stake of `stake_accounts` is recorded in `StakeHistory` for
+    /// the finished epoch, and the clock moves to the first slot of the next epoch.
+    ///
+    /// Returns the reward credited to each account, in order.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use litesvm_utils::StakeHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let stake = Pubkey::new_unique();
+    /// let rewards = svm.advance_epoch_with_rewards(&[stake], 50).unwrap();
+    /// ```
+    fn advance_epoch_with_rewards(
+        &mut self,
+        stake_accounts: &[Pubkey],
+        rate_bps: u64,
+    ) -> Result<Vec<u64>, Box<dyn Error>>;
+}
+
+impl StakeHelpers for LiteSVM {
+    #[track_caller]
+    fn create_stake_account(
+        &mut self,
+        authority: &Pubkey,
+        voter: &Pubkey,
+        stake: u64,
+    ) -> Result<Keypair, Box<dyn Error>> {
+        let stake_account = Keypair::new();
+        let rent_exempt_reserve = self.minimum_balance_for_rent_exemption(StakeStateV2::size_of());
+        let state = StakeStateV2::Stake(
+            Meta {
+                rent_exempt_reserve,
+                authorized: Authorized::auto(authority),
+                ..Meta::default()
+            },
+            Stake {
+                delegation: Delegation::new(voter, stake, u64::MAX),
+                credits_observed: 0,
+            },
+            StakeFlags::empty(),
+        );
+
+        let lamports = rent_exempt_reserve
+            .checked_add(stake)
+            .ok_or("Stake account lamports overflow")?;
+        let pubkey = stake_account.pubkey();
+        write_stake_state(self, "create_stake_account", &pubkey, lamports, &state)?;
+        Ok(stake_account)
+    }
+
+    fn get_delegated_stake(&self, stake_account: &Pubkey) -> Option<u64> {
+        let account = self.get_account(stake_account)?;
+        let state: StakeStateV2 = bincode::deserialize(&account.data).ok()?;
+        state.delegation().map(|delegation| delegation.stake)
+    }
+
+    #[track_caller]
+    fn advance_epoch_with_rewards(
+        &mut self,
+        stake_accounts: &[Pubkey],
+        rate_bps: u64,
+    ) -> Result<Vec<u64>, Box<dyn Error>> {
+        let mut rewards = Vec::with_capacity(stake_accounts.len());
+        let mut effective: u64 = 0;
+        for pubkey in stake_accounts {
+            let account = self
+                .get_account(pubkey)
+                .ok_or_else(|| format!("Stake account {} not found", pubkey))?;
+            let mut state: StakeStateV2 = bincode::deserialize(&account.data)
+                .map_err(|e| format!("Failed to deserialize stake account {}: {}", pubkey, e))?;
+            let StakeStateV2::Stake(_, stake, _) = &mut state else {
+                return Err(format!("Stake account {} is not delegated", pubkey).into());
+            };
+
+            effective = effective.saturating_add(stake.delegation.stake);
+            let reward =
+                (stake.delegation.stake as u128 * rate_bps as u128 / BPS_DENOMINATOR) as u64;
+            stake.delegation.stake += reward;
+            let lamports = account.lamports + reward;
+            write_stake_state(self, "advance_epoch_with_rewards", pubkey, lamports, &state)?;
+            rewards.push(reward);
+        }
+
+        let epoch = self.get_sysvar::<Clock>().epoch;
+        let mut history = self.get_sysvar::<StakeHistory>();
+        history.add(epoch, StakeHistoryEntry::with_effective(effective));
+        self.set_sysvar(&history);
+        self.warp_to_epoch(epoch + 1);
+
+        Ok(rewards)
+    }
+}
+
+/// Write `state` as a stake-program-owned account holding `lamports`
+#[track_caller]
+fn write_stake_state(
+    svm: &mut LiteSVM,
+    operation: &str,
+    pubkey: &Pubkey,
+    lamports: u64,
+    state: &StakeStateV2,
+) -> Result<(), Box<dyn Error>> {
+    let mut data = vec![0u8; StakeStateV2::size_of()];
+    bincode::serialize_into(&mut data[..], state)?;
+    let account = Account {
+        lamports,
+        data,
+        owner: solana_stake_interface::program::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    StateSurgery::new(svm).write(operation, pubkey, account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::native_token::LAMPORTS_PER_SOL;
+    use solana_stake_interface::stake_history::StakeHistoryGetEntry;
+
+    #[test]
+    fn test_multi_epoch_rewards() {
+        let mut svm = LiteSVM::new();
+        let authority = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let stake = svm
+            .create_stake_account(&authority, &voter, 100 * LAMPORTS_PER_SOL)
+            .unwrap()
+            .pubkey();
+        let initial_lamports = svm.get_balance(&stake).unwrap();
+        let start_epoch = svm.get_sysvar::<Clock>().epoch;
+
+        let mut total = 0;
+        for _ in 0..3 {
+            let rewards = svm.advance_epoch_with_rewards(&[stake], 100).unwrap();
+            total += rewards[0];
+        }
+
+        // 1% per epoch, compounding
+        assert_eq!(total, 3_030_100_000);
+        assert_eq!(
+            svm.get_delegated_stake(&stake),
+            Some(100 * LAMPORTS_PER_SOL + total)
+        );
+        assert_eq!(svm.get_balance(&stake), Some(initial_lamports + total));
+
+        let clock = svm.get_sysvar::<Clock>();
+        assert_eq!(clock.epoch, start_epoch + 3);
+        let schedule = svm.get_sysvar::<solana_program::epoch_schedule::EpochSchedule>();
+        assert_eq!(clock.slot, schedule.get_first_slot_in_epoch(clock.epoch));
+
+        let history = svm.get_sysvar::<StakeHistory>();
+        assert_eq!(
+            history.get_entry(start_epoch + 1).unwrap().effective,
+            101 * LAMPORTS_PER_SOL
+        );
+    }
+
+    #[test]
+    fn test_rewards_require_delegated_stake() {
+        let mut svm = LiteSVM::new();
+        let missing = Pubkey::new_unique();
+        assert!(svm.advance_epoch_with_rewards(&[missing], 100).is_err());
+        assert_eq!(svm.get_delegated_stake(&missing), None);
+    }
+}
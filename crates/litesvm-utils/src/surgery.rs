@@ -0,0 +1,304 @@
+//! Recorded direct state surgery
+//!
+//! Writing accounts directly is often the quickest way to reach an edge case, but a
+//! reviewer reading the test can't tell hand-written state from state produced by
+//! executed instructions. [`TestHelpers::unsafe_state`] returns a [`StateSurgery`]
+//! handle whose writes are recorded in the VM's report, read back with
+//! [`surgery_report`]. The other helpers that write accounts directly (the account
+//! builder, actors, stake and NFT fixtures, and accounts loaded by
+//! [`LiteSVMBuilder`](crate::LiteSVMBuilder)) go through the same handle, so the
+//! report lists every hand-written account that didn't come from an executed
+//! instruction. Funding accounts with lamports, from a faucet or not, is routine
+//! setup and isn't recorded.
+//!
+//! ```ignore
+//! svm.unsafe_state().set_token_balance(&vault_ata, 0)?;
+//! for record in surgery_report(&svm) {
+//!     println!("{}", record); // "set_token_balance 7xKX... at tests/vault.rs:42 (slot 0)"
+//! }
+//! ```
+//!
+//! [`TestHelpers::unsafe_state`]: crate::TestHelpers::unsafe_state
+
+use crate::account_registry::track_accounts;
+use crate::vm_state::{clear_surgery_log, push_surgery, surgery_log};
+use litesvm::LiteSVM;
+use solana_program::clock::Clock;
+use solana_program::pubkey::Pubkey;
+#[cfg(feature = "spl")]
+use solana_program_pack::Pack;
+use solana_sdk::account::Account;
+#[cfg(feature = "spl")]
+use spl_token::state::{Account as TokenAccount, Mint};
+use std::error::Error;
+use std::fmt;
+use std::panic::Location;
+
+/// One direct account write made through [`StateSurgery`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurgeryRecord {
+    /// The surgery performed, e.g. `set_token_balance`
+    pub operation: String,
+    /// The account written
+    pub pubkey: Pubkey,
+    /// Slot of the VM when the account was written
+    pub slot: u64,
+    /// Where in the test the surgery was requested
+    pub location: &'static Location<'static>,
+}
+
+impl fmt::Display for SurgeryRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} at {} (slot {})",
+            self.operation, self.pubkey, self.location, self.slot
+        )
+    }
+}
+
+/// Get every surgery recorded on `svm`, in order
+pub fn surgery_report(svm: &LiteSVM) -> Vec<SurgeryRecord> {
+    surgery_log(svm)
+}
+
+/// Forget the surgery recorded on `svm` so far
+///
+/// Useful after fixture setup, so the report only shows the surgery of the test
+/// itself.
+pub fn clear_surgery_report(svm: &mut LiteSVM) {
+    clear_surgery_log(svm);
+}
+
+/// Direct account writes that bypass program execution
+///
+/// Created by [`TestHelpers::unsafe_state`]. Every write is appended to the
+/// [`surgery_report`] with the test location that requested it.
+///
+/// [`TestHelpers::unsafe_state`]: crate::TestHelpers::unsafe_state
+pub struct StateSurgery<'a> {
+    svm: &'a mut LiteSVM,
+}
+
+impl<'a> StateSurgery<'a> {
+    pub(crate) fn new(svm: &'a mut LiteSVM) -> Self {
+        Self { svm }
+    }
+
+    /// Get the VM the surgery is performed on
+    pub fn svm(&self) -> &LiteSVM {
+        self.svm
+    }
+
+    /// Write `account` at `pubkey`, recording it in the report as `operation`
+    ///
+    /// All other surgery methods go through this; extension crates use it to
+    /// record their own operations under a descriptive name.
+    #[track_caller]
+    pub fn write(
+        &mut self,
+        operation: &str,
+        pubkey: &Pubkey,
+        account: Account,
+    ) -> Result<(), Box<dyn Error>> {
+        let location = Location::caller();
+        self.svm
+            .set_account(*pubkey, account)
+            .map_err(|e| format!("Failed to write account {}: {:?}", pubkey, e))?;
+        track_accounts(self.svm, [*pubkey]);
+
+        let record = SurgeryRecord {
+            operation: operation.to_string(),
+            pubkey: *pubkey,
+            slot: self.svm.get_sysvar::<Clock>().slot,
+            location,
+        };
+        push_surgery(self.svm, record);
+        Ok(())
+    }
+
+    /// Overwrite an account wholesale
+    #[track_caller]
+    pub fn set_account(&mut self, pubkey: &Pubkey, account: Account) -> Result<(), Box<dyn Error>> {
+        self.write("set_account", pubkey, account)
+    }
+
+    /// Change the owner of an existing account, keeping its lamports and data
+    ///
+    /// See [`TestHelpers::reassign_owner`](crate::TestHelpers::reassign_owner).
+    #[track_caller]
+    pub fn reassign_owner(
+        &mut self,
+        pubkey: &Pubkey,
+        new_owner: &Pubkey,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut account = self
+            .svm
+            .get_account(pubkey)
+            .ok_or_else(|| format!("Account {} not found", pubkey))?;
+        account.owner = *new_owner;
+        self.write("reassign_owner", pubkey, account)
+    }
+
+    /// Overwrite the balance of an SPL token or Token-2022 account
+    ///
+    /// The mint's supply is adjusted by the same difference so supply assertions
+    /// stay meaningful. Wrapped SOL accounts gain or lose the matching lamports.
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    pub fn set_token_balance(
+        &mut self,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut account = self
+            .svm
+            .get_account(token_account)
+            .ok_or_else(|| format!("Token account {} not found", token_account))?;
+        let mut state = TokenAccount::unpack(account.data.get(..TokenAccount::LEN).unwrap_or(&[]))
+            .map_err(|e| format!("Failed to unpack token account {}: {:?}", token_account, e))?;
+        let previous = state.amount;
+        state.amount = amount;
+        TokenAccount::pack(state, &mut account.data[..TokenAccount::LEN])?;
+        if state.is_native.is_some() {
+            account.lamports = account
+                .lamports
+                .checked_sub(previous)
+                .and_then(|lamports| lamports.checked_add(amount))
+                .ok_or_else(|| {
+                    format!("Lamports out of range for wrapped SOL account {}", token_account)
+                })?;
+        }
+
+        if let Some(mut mint_account) = self.svm.get_account(&state.mint) {
+            if mint_account.data.len() >= Mint::LEN {
+                let mut mint = Mint::unpack(&mint_account.data[..Mint::LEN])
+                    .map_err(|e| format!("Failed to unpack mint {}: {:?}", state.mint, e))?;
+                mint.supply = (mint.supply as i128 + amount as i128 - previous as i128)
+                    .try_into()
+                    .map_err(|_| format!("Supply out of range for mint {}", state.mint))?;
+                Mint::pack(mint, &mut mint_account.data[..Mint::LEN])?;
+                self.write("set_token_balance (mint supply)", &state.mint, mint_account)?;
+            }
+        }
+        self.write("set_token_balance", token_account, account)
+    }
+
+    /// Overwrite the supply of an SPL token or Token-2022 mint
+    ///
+    /// No token account balances change, so the supply no longer matches the sum of
+    /// balances unless the test adjusts them too.
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    pub fn set_mint_supply(&mut self, mint: &Pubkey, supply: u64) -> Result<(), Box<dyn Error>> {
+        let mut account = self
+            .svm
+            .get_account(mint)
+            .ok_or_else(|| format!("Mint {} not found", mint))?;
+        let mut state = Mint::unpack(account.data.get(..Mint::LEN).unwrap_or(&[]))
+            .map_err(|e| format!("Failed to unpack mint {}: {:?}", mint, e))?;
+        state.supply = supply;
+        Mint::pack(state, &mut account.data[..Mint::LEN])?;
+        self.write("set_mint_supply", mint, account)
+    }
+}
+
+#[cfg(all(test, feature = "spl"))]
+mod tests {
+    use super::*;
+    use crate::assertions::AssertionHelpers;
+    use crate::test_helpers::TestHelpers;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    fn test_surgery_is_recorded() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let ata = svm
+            .create_associated_token_account(&mint, &authority)
+            .unwrap();
+        svm.mint_to(&mint, &ata, &authority, 1_000).unwrap();
+        assert!(surgery_report(&svm).is_empty());
+
+        svm.unsafe_state().set_token_balance(&ata, 250).unwrap();
+        svm.assert_token_balance(&ata, 250);
+        svm.assert_mint_supply(&mint, 250);
+
+        let target = Pubkey::new_unique();
+        svm.unsafe_state().reassign_owner(&ata, &target).unwrap();
+        svm.assert_account_owner(&ata, &target);
+
+        // The TestHelpers shorthands record the test as the call site too
+        svm.reassign_owner(&ata, &spl_token::id()).unwrap();
+        svm.set_token_balance(&ata, 300).unwrap();
+
+        let report = surgery_report(&svm);
+        let operations: Vec<&str> = report.iter().map(|r| r.operation.as_str()).collect();
+        assert_eq!(
+            operations,
+            [
+                "set_token_balance (mint supply)",
+                "set_token_balance",
+                "reassign_owner",
+                "reassign_owner",
+                "set_token_balance (mint supply)",
+                "set_token_balance",
+            ]
+        );
+        assert_eq!(report[1].pubkey, ata);
+        assert!(report.iter().all(|record| record.location.file() == file!()));
+        assert!(report[2].to_string().starts_with(&format!("reassign_owner {}", ata)));
+
+        // Each VM keeps its own report
+        assert!(surgery_report(&LiteSVM::new()).is_empty());
+        clear_surgery_report(&mut svm);
+        assert!(surgery_report(&svm).is_empty());
+    }
+
+    #[test]
+    fn test_set_token_balance_requires_token_account() {
+        let mut svm = LiteSVM::new();
+        let user = svm.create_funded_account(1_000_000_000).unwrap();
+        assert!(svm
+            .unsafe_state()
+            .set_token_balance(&user.pubkey(), 1)
+            .is_err());
+        assert!(surgery_report(&svm).is_empty());
+    }
+
+    #[test]
+    fn test_set_token_balance_rejects_inconsistent_wrapped_sol() {
+        use solana_program::program_option::COption;
+        use spl_token::state::AccountState;
+
+        let mut svm = LiteSVM::new();
+        let token_account = Pubkey::new_unique();
+        let state = TokenAccount {
+            mint: spl_token::native_mint::id(),
+            owner: Pubkey::new_unique(),
+            amount: 5_000_000,
+            state: AccountState::Initialized,
+            is_native: COption::Some(0),
+            ..TokenAccount::default()
+        };
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(state, &mut data).unwrap();
+        let account = Account {
+            lamports: 1_000_000,
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        svm.set_account(token_account, account.clone()).unwrap();
+
+        // The account holds fewer lamports than its recorded amount
+        assert!(svm
+            .unsafe_state()
+            .set_token_balance(&token_account, 1)
+            .is_err());
+        assert_eq!(svm.get_account(&token_account).unwrap(), account);
+        assert!(surgery_report(&svm).is_empty());
+    }
+}
@@ -3,13 +3,50 @@
 //! This module provides convenient methods for creating and managing test accounts,
 //! token mints, and associated token accounts.
 
+use crate::account_builder::AccountBuilder;
+use crate::account_registry::{track_accounts, AccountFilter, AccountIter};
+use crate::faucet::{draw_from_faucet, fund_account};
+use crate::keypairs::{keypair_from_name, new_keypair};
+use crate::surgery::StateSurgery;
+use crate::transaction::TransactionHelpers;
 use litesvm::LiteSVM;
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_program::epoch_schedule::EpochSchedule;
+use solana_program::hash::Hash;
+use solana_program::instruction::Instruction;
 use solana_program::pubkey::Pubkey;
+#[cfg(feature = "spl")]
+use solana_program_pack::{IsInitialized, Pack};
+use solana_sdk::account::Account;
 use solana_sdk::signature::{Keypair, Signer};
-use solana_sdk::transaction::Transaction;
+#[cfg(feature = "spl")]
 use spl_associated_token_account::get_associated_token_address;
 use std::error::Error;
 
+/// Deployment metadata of an upgradeable program, read from its programdata account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramData {
+    /// Address of the programdata account
+    pub address: Pubkey,
+    /// Slot the program was last deployed or upgraded at
+    pub slot: u64,
+    /// Upgrade authority, `None` if the program is immutable
+    pub upgrade_authority: Option<Pubkey>,
+}
+
+/// A saved copy of a VM's state, created by [`TestHelpers::snapshot`]
+///
+/// Holds every account, program and sysvar along with the transaction history, so
+/// a restored VM rejects exactly the transactions it had already processed when the
+/// snapshot was taken. The helpers' per-VM state (surgery report, tracked accounts,
+/// faucet and other settings) is saved by value with it, so a restore also forgets
+/// surgery and accounts recorded after the snapshot. A snapshot can be restored any
+/// number of times.
+#[derive(Clone)]
+pub struct SvmSnapshot {
+    svm: LiteSVM,
+}
+
 /// Test helper methods for LiteSVM
 pub trait TestHelpers {
     /// Create a new funded keypair
@@ -23,6 +60,112 @@ pub trait TestHelpers {
     /// ```
     fn create_funded_account(&mut self, lamports: u64) -> Result<Keypair, Box<dyn Error>>;
 
+    /// Create a funded keypair derived from `seed`, with the same pubkey on every run
+    ///
+    /// The keypair is [`keypair_from_name(seed)`](crate::keypairs::keypair_from_name),
+    /// so tests can name their actors and get stable addresses in logs and golden
+    /// files. Calling it again with the same seed funds the same account again.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// let alice = svm.create_funded_account_with_seed("alice", 1_000_000_000).unwrap();
+    /// ```
+    fn create_funded_account_with_seed(
+        &mut self,
+        seed: &str,
+        lamports: u64,
+    ) -> Result<Keypair, Box<dyn Error>>;
+
+    /// Create a funded system account at a fixed address
+    ///
+    /// Writes the account directly, so no keypair is needed. Use it when a program
+    /// hard-codes an admin or treasury address. An existing account at `pubkey` is
+    /// replaced. The lamports come out of the faucet when one is configured.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let treasury = Pubkey::new_unique();
+    /// svm.create_funded_account_at(&treasury, 1_000_000_000).unwrap();
+    /// ```
+    fn create_funded_account_at(
+        &mut self,
+        pubkey: &Pubkey,
+        lamports: u64,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Start building a raw account to write at `pubkey`
+    ///
+    /// Unset fields default to a rent-exempt balance, the system program as owner,
+    /// no data and not executable. See [`AccountBuilder`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let config = Pubkey::new_unique();
+    /// # let program_id = Pubkey::new_unique();
+    /// svm.account_builder(config)
+    ///     .lamports(1_000_000)
+    ///     .owner(&program_id)
+    ///     .data(vec![1, 0, 0, 0])
+    ///     .executable(false)
+    ///     .create()
+    ///     .unwrap();
+    /// ```
+    fn account_builder(&mut self, pubkey: Pubkey) -> AccountBuilder<'_>;
+
+    /// Change the owner of an existing account, keeping its lamports and data
+    ///
+    /// This is test-only state tampering: no instruction can do this on a real
+    /// cluster for an account the new owner didn't create. Use it to craft
+    /// "account owned by the wrong program" scenarios and check that a program
+    /// rejects them. The change is recorded in the surgery report like
+    /// [`StateSurgery::reassign_owner`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let vault = Pubkey::new_unique();
+    /// let attacker_program = Pubkey::new_unique();
+    /// svm.reassign_owner(&vault, &attacker_program).unwrap();
+    /// // Instructions taking `vault` should now fail their owner check
+    /// ```
+    fn reassign_owner(
+        &mut self,
+        pubkey: &Pubkey,
+        new_owner: &Pubkey,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Get a handle for direct account writes that bypass program execution
+    ///
+    /// Every write made through the handle is recorded, with the line of the test
+    /// that made it, in [`surgery_report`](crate::surgery_report). This keeps
+    /// hand-written state visible to reviewers.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let vault = Pubkey::new_unique();
+    /// let attacker_program = Pubkey::new_unique();
+    /// svm.unsafe_state().reassign_owner(&vault, &attacker_program).unwrap();
+    /// ```
+    fn unsafe_state(&mut self) -> StateSurgery<'_>;
+
     /// Create multiple funded keypairs
     ///
     /// # Example
@@ -39,6 +182,50 @@ pub trait TestHelpers {
         lamports: u64,
     ) -> Result<Vec<Keypair>, Box<dyn Error>>;
 
+    /// Create a rent-exempt account of `size` zeroed bytes owned by `owner`
+    ///
+    /// Sends a system `create_account` instruction funded by `payer`, as a client
+    /// would before calling a non-Anchor program that expects a pre-created account.
+    /// Returns the new account's keypair.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let program_id = Pubkey::new_unique();
+    /// let payer = svm.create_funded_account(1_000_000_000).unwrap();
+    /// let state = svm.create_rent_exempt_account(&payer, 128, &program_id).unwrap();
+    /// ```
+    fn create_rent_exempt_account(
+        &mut self,
+        payer: &Keypair,
+        size: usize,
+        owner: &Pubkey,
+    ) -> Result<Keypair, Box<dyn Error>>;
+
+    /// Transfer `lamports` from `from` to `to` with a system transfer instruction
+    ///
+    /// `from` signs and pays the fee. A missing `to` account is created.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let vault = Pubkey::new_unique();
+    /// let alice = svm.create_funded_account(1_000_000_000).unwrap();
+    /// svm.transfer_sol(&alice, &vault, 100_000_000).unwrap();
+    /// ```
+    fn transfer_sol(
+        &mut self,
+        from: &Keypair,
+        to: &Pubkey,
+        lamports: u64,
+    ) -> Result<(), Box<dyn Error>>;
+
     /// Create and initialize a token mint
     ///
     /// # Example
@@ -50,12 +237,34 @@ pub trait TestHelpers {
     /// # let authority = Keypair::new();
     /// let mint = svm.create_token_mint(&authority, 9).unwrap();
     /// ```
+    #[cfg(feature = "spl")]
     fn create_token_mint(
         &mut self,
         authority: &Keypair,
         decimals: u8,
     ) -> Result<Keypair, Box<dyn Error>>;
 
+    /// Create and initialize a token mint with `authority` as its freeze authority too
+    ///
+    /// Needed for [`freeze_token_account`](Self::freeze_token_account); mints from
+    /// [`create_token_mint`](Self::create_token_mint) can't be frozen.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let mut svm = LiteSVM::new();
+    /// # let authority = Keypair::new();
+    /// let mint = svm.create_freezable_token_mint(&authority, 6).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn create_freezable_token_mint(
+        &mut self,
+        authority: &Keypair,
+        decimals: u8,
+    ) -> Result<Keypair, Box<dyn Error>>;
+
     /// Create a token account for a mint
     ///
     /// # Example
@@ -68,6 +277,7 @@ pub trait TestHelpers {
     /// # let mint = Keypair::new();
     /// let token_account = svm.create_token_account(&mint.pubkey(), &owner).unwrap();
     /// ```
+    #[cfg(feature = "spl")]
     fn create_token_account(
         &mut self,
         mint: &Pubkey,
@@ -86,6 +296,7 @@ pub trait TestHelpers {
     /// # let mint = Keypair::new();
     /// let ata = svm.create_associated_token_account(&mint.pubkey(), &owner).unwrap();
     /// ```
+    #[cfg(feature = "spl")]
     fn create_associated_token_account(
         &mut self,
         mint: &Pubkey,
@@ -106,6 +317,7 @@ pub trait TestHelpers {
     /// # let authority = Keypair::new();
     /// svm.mint_to(&mint.pubkey(), &token_account, &authority, 1_000_000_000).unwrap();
     /// ```
+    #[cfg(feature = "spl")]
     fn mint_to(
         &mut self,
         mint: &Pubkey,
@@ -114,6 +326,180 @@ pub trait TestHelpers {
         amount: u64,
     ) -> Result<(), Box<dyn Error>>;
 
+    /// Overwrite a token account's balance without a mint authority or transaction
+    ///
+    /// A cheatcode for large or awkward balances. The mint's supply moves by the same
+    /// difference, and the write is recorded in the surgery report like
+    /// [`StateSurgery::set_token_balance`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ata = Pubkey::new_unique();
+    /// svm.set_token_balance(&ata, 1_000_000_000_000_000_000).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn set_token_balance(
+        &mut self,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Overwrite a mint's supply without touching any token account
+    ///
+    /// Recorded in the surgery report like [`StateSurgery::set_mint_supply`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let mint = Pubkey::new_unique();
+    /// svm.set_mint_supply(&mint, u64::MAX).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn set_mint_supply(&mut self, mint: &Pubkey, supply: u64) -> Result<(), Box<dyn Error>>;
+
+    /// Transfer tokens between two token accounts
+    ///
+    /// `owner` signs as the source account's owner (or delegate) and pays the fee.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::Keypair;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+    /// # let owner = Keypair::new();
+    /// svm.transfer_tokens(&source, &destination, &owner, 500).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn transfer_tokens(
+        &mut self,
+        source: &Pubkey,
+        destination: &Pubkey,
+        owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Burn tokens from a token account
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::Keypair;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let (token_account, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    /// # let owner = Keypair::new();
+    /// svm.burn_tokens(&token_account, &mint, &owner, 500).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn burn_tokens(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Approve a delegate to transfer or burn up to `amount` tokens from an account
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::Keypair;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let (token_account, delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+    /// # let owner = Keypair::new();
+    /// svm.approve_delegate(&token_account, &delegate, &owner, 500).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn approve_delegate(
+        &mut self,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Revoke the delegate of a token account
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::Keypair;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let token_account = Pubkey::new_unique();
+    /// # let owner = Keypair::new();
+    /// svm.revoke_delegate(&token_account, &owner).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn revoke_delegate(&mut self, account: &Pubkey, owner: &Keypair) -> Result<(), Box<dyn Error>>;
+
+    /// Close an empty token account, sending its rent to `destination`
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::Keypair;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let (token_account, rent_recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+    /// # let owner = Keypair::new();
+    /// svm.close_token_account(&token_account, &rent_recipient, &owner).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn close_token_account(
+        &mut self,
+        account: &Pubkey,
+        destination: &Pubkey,
+        owner: &Keypair,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Freeze a token account using the mint's freeze authority
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::signature::{Keypair, Signer};
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let authority = Keypair::new();
+    /// # let token_account = Pubkey::new_unique();
+    /// let mint = svm.create_freezable_token_mint(&authority, 6).unwrap();
+    /// svm.freeze_token_account(&token_account, &mint.pubkey(), &authority).unwrap();
+    /// ```
+    #[cfg(feature = "spl")]
+    fn freeze_token_account(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        freeze_authority: &Keypair,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Thaw a frozen token account using the mint's freeze authority
+    #[cfg(feature = "spl")]
+    fn thaw_token_account(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        freeze_authority: &Keypair,
+    ) -> Result<(), Box<dyn Error>>;
+
     /// Derive a program-derived address
     ///
     /// # Example
@@ -168,21 +554,335 @@ pub trait TestHelpers {
         self.derive_pda(seeds, program_id)
     }
 
+    /// Deploy a program through the upgradeable loader
+    ///
+    /// `LiteSVM::add_program` uses the non-upgradeable loader, so programs that
+    /// validate their own upgrade authority or deployment slot need this instead.
+    /// The deployment slot is the current slot.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let mut svm = LiteSVM::new();
+    /// # let program_id = Pubkey::new_unique();
+    /// # let authority = Pubkey::new_unique();
+    /// # let program_bytes = &[];
+    /// svm.deploy_upgradeable_program(program_id, program_bytes, Some(authority)).unwrap();
+    /// ```
+    fn deploy_upgradeable_program(
+        &mut self,
+        program_id: Pubkey,
+        program_bytes: &[u8],
+        upgrade_authority: Option<Pubkey>,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Get the programdata of an upgradeable program
+    ///
+    /// Returns `None` if the program doesn't exist or isn't owned by the upgradeable loader.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let program_id = Pubkey::new_unique();
+    /// let program_data = svm.get_program_data(&program_id).unwrap();
+    /// println!("Deployed at slot {}", program_data.slot);
+    /// ```
+    fn get_program_data(&self, program_id: &Pubkey) -> Option<ProgramData>;
+
+    /// Iterate over existing accounts, optionally only those owned by `owner`
+    ///
+    /// Accounts are yielded in ascending address order, so results can be paged
+    /// with [`AccountIter::starting_after`]. LiteSVM doesn't expose its account
+    /// store, so only accounts known to the helpers are visible: see
+    /// [`account_registry`](crate::account_registry) for what gets tracked.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::system_program;
+    /// # let svm = LiteSVM::new();
+    /// for (pubkey, account) in svm.iter_accounts(Some(&system_program::id())) {
+    ///     println!("{}: {} bytes", pubkey, account.data.len());
+    /// }
+    /// ```
+    fn iter_accounts(&self, owner: Option<&Pubkey>) -> AccountIter<'_>;
+
+    /// Get every account owned by `program_id`, like the `getProgramAccounts` RPC
+    ///
+    /// Accounts are returned in ascending address order. Only accounts known to
+    /// the helpers are visible, as with [`iter_accounts`](Self::iter_accounts).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let program_id = Pubkey::new_unique();
+    /// let accounts = svm.get_program_accounts(&program_id);
+    /// ```
+    fn get_program_accounts(&self, program_id: &Pubkey) -> Vec<(Pubkey, Account)> {
+        self.get_program_accounts_with_filters(program_id, &[])
+    }
+
+    /// Get the accounts owned by `program_id` that pass every filter
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::{AccountFilter, TestHelpers};
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let (program_id, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+    /// let positions = svm.get_program_accounts_with_filters(
+    ///     &program_id,
+    ///     &[AccountFilter::data_size(80), AccountFilter::memcmp(8, owner)],
+    /// );
+    /// assert_eq!(positions.len(), 3);
+    /// ```
+    fn get_program_accounts_with_filters(
+        &self,
+        program_id: &Pubkey,
+        filters: &[AccountFilter],
+    ) -> Vec<(Pubkey, Account)>;
+
+    /// Read a little-endian `u64` from an account's data at byte `offset`
+    ///
+    /// Returns `None` if the account doesn't exist or its data is too short.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let mint = Pubkey::new_unique();
+    /// // Supply of an SPL mint lives at byte 36
+    /// let supply = svm.read_u64_at(&mint, 36).unwrap();
+    /// ```
+    fn read_u64_at(&self, account: &Pubkey, offset: usize) -> Option<u64>;
+
+    /// Get the amount held by a token account
+    ///
+    /// Works for SPL Token and Token-2022 accounts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the account doesn't exist or isn't a token account
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let ata = Pubkey::new_unique();
+    /// let before = svm.get_token_balance(&ata);
+    /// ```
+    #[cfg(feature = "spl")]
+    fn get_token_balance(&self, token_account: &Pubkey) -> u64 {
+        self.get_token_account(token_account).amount
+    }
+
+    /// Get the decoded state of a token account
+    ///
+    /// Token-2022 extensions are ignored; only the base account is decoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the account doesn't exist or isn't a token account
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let ata = Pubkey::new_unique();
+    /// let account = svm.get_token_account(&ata);
+    /// assert!(account.delegate.is_none());
+    /// ```
+    #[cfg(feature = "spl")]
+    fn get_token_account(&self, token_account: &Pubkey) -> spl_token::state::Account;
+
+    /// Get the decoded state of a mint
+    ///
+    /// Token-2022 extensions are ignored; only the base mint is decoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the account doesn't exist or isn't a mint
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::pubkey::Pubkey;
+    /// # let svm = LiteSVM::new();
+    /// # let mint = Pubkey::new_unique();
+    /// let supply = svm.get_mint(&mint).supply;
+    /// ```
+    #[cfg(feature = "spl")]
+    fn get_mint(&self, mint: &Pubkey) -> spl_token::state::Mint;
+
     /// Get the current slot
     fn get_current_slot(&self) -> u64;
 
     /// Advance the slot by a specified amount
     fn advance_slot(&mut self, slots: u64);
+
+    /// Get the current epoch
+    fn get_current_epoch(&self) -> u64;
+
+    /// Move the clock to the first slot of `epoch`
+    ///
+    /// Updates the Clock sysvar's slot, epoch, `leader_schedule_epoch` and
+    /// `epoch_start_timestamp` together, following the `EpochSchedule` sysvar.
+    /// `unix_timestamp` is left unchanged. Moving to an earlier epoch is allowed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// svm.warp_to_epoch(10);
+    /// assert_eq!(svm.get_current_epoch(), 10);
+    /// ```
+    fn warp_to_epoch(&mut self, epoch: u64);
+
+    /// Move the clock to the first slot of the epoch `epochs` after the current one
+    ///
+    /// See [`warp_to_epoch`](Self::warp_to_epoch). Returns the new epoch.
+    fn advance_epochs(&mut self, epochs: u64) -> u64;
+
+    /// Get the Clock sysvar's `unix_timestamp`
+    fn get_unix_timestamp(&self) -> i64;
+
+    /// Set the Clock sysvar's `unix_timestamp`, leaving the slot unchanged
+    ///
+    /// Time-locked programs compare against `Clock::unix_timestamp`, which
+    /// [`advance_slot`](Self::advance_slot) doesn't move.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// svm.set_unix_timestamp(1_735_689_600); // 2025-01-01T00:00:00Z
+    /// ```
+    fn set_unix_timestamp(&mut self, timestamp: i64);
+
+    /// Move the Clock sysvar's `unix_timestamp` forward by `seconds`
+    ///
+    /// Negative values move it back. The timestamp saturates at `i64::MIN` and
+    /// `i64::MAX` instead of overflowing. Returns the new timestamp.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// // Skip past a one-day vesting cliff
+    /// svm.advance_clock_by_seconds(24 * 60 * 60);
+    /// ```
+    fn advance_clock_by_seconds(&mut self, seconds: i64) -> i64;
+
+    /// Move to a new blockhash `count` times, returning the latest one
+    ///
+    /// Only the latest blockhash is accepted, so every transaction signed before
+    /// the call now fails with `BlockhashNotFound`, as one would on a cluster once
+    /// its blockhash ages out. Use it to test client retry logic. `count` of 1 is
+    /// the same as [`LiteSVM::expire_blockhash`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// let stale = svm.latest_blockhash();
+    /// let fresh = svm.advance_blockhash(150);
+    /// assert_ne!(stale, fresh);
+    /// ```
+    fn advance_blockhash(&mut self, count: u64) -> Hash;
+
+    /// Save the full VM state so it can be restored with [`restore`](Self::restore)
+    ///
+    /// Set up an expensive baseline once, then restore it between scenarios instead
+    /// of rebuilding it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TestHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// let baseline = svm.snapshot();
+    /// // ... run one scenario ...
+    /// svm.restore(&baseline);
+    /// ```
+    fn snapshot(&self) -> SvmSnapshot;
+
+    /// Replace the VM state with a snapshot taken by [`snapshot`](Self::snapshot)
+    fn restore(&mut self, snapshot: &SvmSnapshot);
 }
 
 impl TestHelpers for LiteSVM {
+    #[track_caller]
     fn create_funded_account(&mut self, lamports: u64) -> Result<Keypair, Box<dyn Error>> {
-        let keypair = Keypair::new();
-        self.airdrop(&keypair.pubkey(), lamports)
-            .map_err(|e| format!("Failed to airdrop: {:?}", e))?;
+        let keypair = new_keypair(self);
+        fund_account(self, &keypair.pubkey(), lamports)?;
+        track_accounts(self, [keypair.pubkey()]);
+        Ok(keypair)
+    }
+
+    #[track_caller]
+    fn create_funded_account_with_seed(
+        &mut self,
+        seed: &str,
+        lamports: u64,
+    ) -> Result<Keypair, Box<dyn Error>> {
+        let keypair = keypair_from_name(seed);
+        fund_account(self, &keypair.pubkey(), lamports)?;
+        track_accounts(self, [keypair.pubkey()]);
         Ok(keypair)
     }
 
+    #[track_caller]
+    fn create_funded_account_at(
+        &mut self,
+        pubkey: &Pubkey,
+        lamports: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        draw_from_faucet(self, pubkey, lamports)?;
+        let account = Account::new(lamports, 0, &solana_program::system_program::id());
+        self.unsafe_state()
+            .write("create_funded_account_at", pubkey, account)
+    }
+
+    #[track_caller]
+    fn account_builder(&mut self, pubkey: Pubkey) -> AccountBuilder<'_> {
+        AccountBuilder::new(self, pubkey)
+    }
+
+    #[track_caller]
+    fn reassign_owner(
+        &mut self,
+        pubkey: &Pubkey,
+        new_owner: &Pubkey,
+    ) -> Result<(), Box<dyn Error>> {
+        self.unsafe_state().reassign_owner(pubkey, new_owner)
+    }
+
+    fn unsafe_state(&mut self) -> StateSurgery<'_> {
+        StateSurgery::new(self)
+    }
+
+    #[track_caller]
     fn create_funded_accounts(
         &mut self,
         count: usize,
@@ -195,53 +895,72 @@ impl TestHelpers for LiteSVM {
         Ok(accounts)
     }
 
-    fn create_token_mint(
+    fn create_rent_exempt_account(
         &mut self,
-        authority: &Keypair,
-        decimals: u8,
+        payer: &Keypair,
+        size: usize,
+        owner: &Pubkey,
     ) -> Result<Keypair, Box<dyn Error>> {
-        let mint = Keypair::new();
-
-        // Calculate rent for mint account
-        let rent = self.minimum_balance_for_rent_exemption(82);
-
-        // Create mint account
+        let account = new_keypair(self);
         let create_account_ix = solana_program::system_instruction::create_account(
-            &authority.pubkey(),
-            &mint.pubkey(),
-            rent,
-            82,
-            &spl_token::id(),
+            &payer.pubkey(),
+            &account.pubkey(),
+            self.minimum_balance_for_rent_exemption(size),
+            size as u64,
+            owner,
         );
 
-        // Initialize mint
-        let init_mint_ix = spl_token::instruction::initialize_mint(
-            &spl_token::id(),
-            &mint.pubkey(),
-            &authority.pubkey(),
-            None,
-            decimals,
+        send_helper_transaction(
+            self,
+            &[create_account_ix],
+            &[payer, &account],
+            "create account",
         )?;
+        Ok(account)
+    }
 
-        // Send transaction
-        let tx = Transaction::new_signed_with_payer(
-            &[create_account_ix, init_mint_ix],
-            Some(&authority.pubkey()),
-            &[authority, &mint],
-            self.latest_blockhash(),
-        );
+    fn transfer_sol(
+        &mut self,
+        from: &Keypair,
+        to: &Pubkey,
+        lamports: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let transfer_ix =
+            solana_program::system_instruction::transfer(&from.pubkey(), to, lamports);
+        send_helper_transaction(
+            self,
+            &[transfer_ix],
+            &[from],
+            "transfer SOL",
+        )?;
+        Ok(())
+    }
 
-        self.send_transaction(tx)
-            .map_err(|e| format!("Failed to create mint: {:?}", e.err))?;
-        Ok(mint)
+    #[cfg(feature = "spl")]
+    fn create_token_mint(
+        &mut self,
+        authority: &Keypair,
+        decimals: u8,
+    ) -> Result<Keypair, Box<dyn Error>> {
+        create_mint(self, authority, decimals, None)
     }
 
+    #[cfg(feature = "spl")]
+    fn create_freezable_token_mint(
+        &mut self,
+        authority: &Keypair,
+        decimals: u8,
+    ) -> Result<Keypair, Box<dyn Error>> {
+        create_mint(self, authority, decimals, Some(&authority.pubkey()))
+    }
+
+    #[cfg(feature = "spl")]
     fn create_token_account(
         &mut self,
         mint: &Pubkey,
         owner: &Keypair,
     ) -> Result<Keypair, Box<dyn Error>> {
-        let token_account = Keypair::new();
+        let token_account = new_keypair(self);
 
         // Calculate rent for token account
         let rent = self.minimum_balance_for_rent_exemption(165);
@@ -263,19 +982,16 @@ impl TestHelpers for LiteSVM {
             &owner.pubkey(),
         )?;
 
-        // Send transaction
-        let tx = Transaction::new_signed_with_payer(
+        send_helper_transaction(
+            self,
             &[create_account_ix, init_account_ix],
-            Some(&owner.pubkey()),
             &[owner, &token_account],
-            self.latest_blockhash(),
-        );
-
-        self.send_transaction(tx)
-            .map_err(|e| format!("Failed to create token account: {:?}", e.err))?;
+            "create token account",
+        )?;
         Ok(token_account)
     }
 
+    #[cfg(feature = "spl")]
     fn create_associated_token_account(
         &mut self,
         mint: &Pubkey,
@@ -291,19 +1007,16 @@ impl TestHelpers for LiteSVM {
             &spl_token::id(),
         );
 
-        // Send transaction
-        let tx = Transaction::new_signed_with_payer(
-            &[create_ata_ix],
-            Some(&owner.pubkey()),
-            &[owner],
-            self.latest_blockhash(),
-        );
-
-        self.send_transaction(tx)
-            .map_err(|e| format!("Failed to create ATA: {:?}", e.err))?;
+        send_helper_transaction(
+            self,
+            &[create_ata_ix],
+            &[owner],
+            "create ATA",
+        )?;
         Ok(ata)
     }
 
+    #[cfg(feature = "spl")]
     fn mint_to(
         &mut self,
         mint: &Pubkey,
@@ -321,23 +1034,247 @@ impl TestHelpers for LiteSVM {
             amount,
         )?;
 
-        // Send transaction
-        let tx = Transaction::new_signed_with_payer(
+        send_helper_transaction(
+            self,
             &[mint_to_ix],
-            Some(&authority.pubkey()),
             &[authority],
-            self.latest_blockhash(),
-        );
-
-        self.send_transaction(tx)
-            .map_err(|e| format!("Failed to mint tokens: {:?}", e.err))?;
+            "mint tokens",
+        )?;
         Ok(())
     }
 
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    fn set_token_balance(
+        &mut self,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        self.unsafe_state().set_token_balance(token_account, amount)
+    }
+
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    fn set_mint_supply(&mut self, mint: &Pubkey, supply: u64) -> Result<(), Box<dyn Error>> {
+        self.unsafe_state().set_mint_supply(mint, supply)
+    }
+
+    #[cfg(feature = "spl")]
+    fn transfer_tokens(
+        &mut self,
+        source: &Pubkey,
+        destination: &Pubkey,
+        owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::transfer(
+            &spl_token::id(),
+            source,
+            destination,
+            &owner.pubkey(),
+            &[],
+            amount,
+        )?;
+        send_token_instruction(self, ix, owner, "transfer tokens")
+    }
+
+    #[cfg(feature = "spl")]
+    fn burn_tokens(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::burn(
+            &spl_token::id(),
+            account,
+            mint,
+            &owner.pubkey(),
+            &[],
+            amount,
+        )?;
+        send_token_instruction(self, ix, owner, "burn tokens")
+    }
+
+    #[cfg(feature = "spl")]
+    fn approve_delegate(
+        &mut self,
+        account: &Pubkey,
+        delegate: &Pubkey,
+        owner: &Keypair,
+        amount: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::approve(
+            &spl_token::id(),
+            account,
+            delegate,
+            &owner.pubkey(),
+            &[],
+            amount,
+        )?;
+        send_token_instruction(self, ix, owner, "approve delegate")
+    }
+
+    #[cfg(feature = "spl")]
+    fn revoke_delegate(&mut self, account: &Pubkey, owner: &Keypair) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::revoke(&spl_token::id(), account, &owner.pubkey(), &[])?;
+        send_token_instruction(self, ix, owner, "revoke delegate")
+    }
+
+    #[cfg(feature = "spl")]
+    fn close_token_account(
+        &mut self,
+        account: &Pubkey,
+        destination: &Pubkey,
+        owner: &Keypair,
+    ) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::close_account(
+            &spl_token::id(),
+            account,
+            destination,
+            &owner.pubkey(),
+            &[],
+        )?;
+        send_token_instruction(self, ix, owner, "close token account")
+    }
+
+    #[cfg(feature = "spl")]
+    fn freeze_token_account(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        freeze_authority: &Keypair,
+    ) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::freeze_account(
+            &spl_token::id(),
+            account,
+            mint,
+            &freeze_authority.pubkey(),
+            &[],
+        )?;
+        send_token_instruction(self, ix, freeze_authority, "freeze token account")
+    }
+
+    #[cfg(feature = "spl")]
+    fn thaw_token_account(
+        &mut self,
+        account: &Pubkey,
+        mint: &Pubkey,
+        freeze_authority: &Keypair,
+    ) -> Result<(), Box<dyn Error>> {
+        let ix = spl_token::instruction::thaw_account(
+            &spl_token::id(),
+            account,
+            mint,
+            &freeze_authority.pubkey(),
+            &[],
+        )?;
+        send_token_instruction(self, ix, freeze_authority, "thaw token account")
+    }
+
     fn derive_pda(&self, seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(seeds, program_id)
     }
 
+    #[track_caller]
+    fn deploy_upgradeable_program(
+        &mut self,
+        program_id: Pubkey,
+        program_bytes: &[u8],
+        upgrade_authority: Option<Pubkey>,
+    ) -> Result<(), Box<dyn Error>> {
+        let (programdata_address, _) =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+
+        // The programdata account must exist before the program account is loaded
+        let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+        let programdata_len = metadata_len + program_bytes.len();
+        let mut programdata = Account::new_data_with_space(
+            self.minimum_balance_for_rent_exemption(programdata_len),
+            &UpgradeableLoaderState::ProgramData {
+                slot: self.get_current_slot(),
+                upgrade_authority_address: upgrade_authority,
+            },
+            programdata_len,
+            &bpf_loader_upgradeable::id(),
+        )?;
+        programdata.data[metadata_len..].copy_from_slice(program_bytes);
+        self.unsafe_state()
+            .write("deploy_upgradeable_program", &programdata_address, programdata)?;
+
+        let program_len = UpgradeableLoaderState::size_of_program();
+        let mut program = Account::new_data_with_space(
+            self.minimum_balance_for_rent_exemption(program_len),
+            &UpgradeableLoaderState::Program {
+                programdata_address,
+            },
+            program_len,
+            &bpf_loader_upgradeable::id(),
+        )?;
+        program.executable = true;
+        self.unsafe_state()
+            .write("deploy_upgradeable_program", &program_id, program)
+    }
+
+    fn get_program_data(&self, program_id: &Pubkey) -> Option<ProgramData> {
+        let program = self.get_account(program_id)?;
+        if program.owner != bpf_loader_upgradeable::id() {
+            return None;
+        }
+        let UpgradeableLoaderState::Program {
+            programdata_address,
+        } = program.deserialize_data().ok()?
+        else {
+            return None;
+        };
+
+        let programdata = self.get_account(&programdata_address)?;
+        match programdata.deserialize_data().ok()? {
+            UpgradeableLoaderState::ProgramData {
+                slot,
+                upgrade_authority_address,
+            } => Some(ProgramData {
+                address: programdata_address,
+                slot,
+                upgrade_authority: upgrade_authority_address,
+            }),
+            _ => None,
+        }
+    }
+
+    fn iter_accounts(&self, owner: Option<&Pubkey>) -> AccountIter<'_> {
+        AccountIter::new(self, owner)
+    }
+
+    fn get_program_accounts_with_filters(
+        &self,
+        program_id: &Pubkey,
+        filters: &[AccountFilter],
+    ) -> Vec<(Pubkey, Account)> {
+        self.iter_accounts(Some(program_id))
+            .filter(|(_, account)| filters.iter().all(|filter| filter.matches(&account.data)))
+            .collect()
+    }
+
+    fn read_u64_at(&self, account: &Pubkey, offset: usize) -> Option<u64> {
+        let data = self.get_account(account)?.data;
+        let bytes = data.get(offset..offset.checked_add(8)?)?;
+        Some(u64::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    fn get_token_account(&self, token_account: &Pubkey) -> spl_token::state::Account {
+        unpack_token_state(self, token_account, "token account")
+    }
+
+    #[cfg(feature = "spl")]
+    #[track_caller]
+    fn get_mint(&self, mint: &Pubkey) -> spl_token::state::Mint {
+        unpack_token_state(self, mint, "mint")
+    }
+
     fn get_current_slot(&self) -> u64 {
         // LiteSVM doesn't have get_clock, use slot directly
         self.get_sysvar::<solana_program::clock::Clock>().slot
@@ -349,13 +1286,153 @@ impl TestHelpers for LiteSVM {
             self.warp_to_slot(current_slot + i + 1);
         }
     }
+
+    fn get_current_epoch(&self) -> u64 {
+        self.get_sysvar::<solana_program::clock::Clock>().epoch
+    }
+
+    fn warp_to_epoch(&mut self, epoch: u64) {
+        let schedule = self.get_sysvar::<EpochSchedule>();
+        let mut clock = self.get_sysvar::<solana_program::clock::Clock>();
+        clock.epoch = epoch;
+        clock.slot = schedule.get_first_slot_in_epoch(epoch);
+        clock.epoch_start_timestamp = clock.unix_timestamp;
+        clock.leader_schedule_epoch = schedule.get_leader_schedule_epoch(clock.slot);
+        self.set_sysvar(&clock);
+    }
+
+    fn advance_epochs(&mut self, epochs: u64) -> u64 {
+        let epoch = self.get_current_epoch() + epochs;
+        self.warp_to_epoch(epoch);
+        epoch
+    }
+
+    fn get_unix_timestamp(&self) -> i64 {
+        self.get_sysvar::<solana_program::clock::Clock>().unix_timestamp
+    }
+
+    fn set_unix_timestamp(&mut self, timestamp: i64) {
+        let mut clock = self.get_sysvar::<solana_program::clock::Clock>();
+        clock.unix_timestamp = timestamp;
+        self.set_sysvar(&clock);
+    }
+
+    fn advance_clock_by_seconds(&mut self, seconds: i64) -> i64 {
+        let timestamp = self.get_unix_timestamp().saturating_add(seconds);
+        self.set_unix_timestamp(timestamp);
+        timestamp
+    }
+
+    fn advance_blockhash(&mut self, count: u64) -> Hash {
+        for _ in 0..count {
+            self.expire_blockhash();
+        }
+        self.latest_blockhash()
+    }
+
+    fn snapshot(&self) -> SvmSnapshot {
+        SvmSnapshot { svm: self.clone() }
+    }
+
+    fn restore(&mut self, snapshot: &SvmSnapshot) {
+        *self = snapshot.svm.clone();
+    }
+}
+
+/// Create and initialize an SPL token mint, optionally with a freeze authority
+#[cfg(feature = "spl")]
+fn create_mint(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    decimals: u8,
+    freeze_authority: Option<&Pubkey>,
+) -> Result<Keypair, Box<dyn Error>> {
+    let mint = new_keypair(svm);
+
+    // Calculate rent for mint account
+    let rent = svm.minimum_balance_for_rent_exemption(82);
+
+    // Create mint account
+    let create_account_ix = solana_program::system_instruction::create_account(
+        &authority.pubkey(),
+        &mint.pubkey(),
+        rent,
+        82,
+        &spl_token::id(),
+    );
+
+    // Initialize mint
+    let init_mint_ix = spl_token::instruction::initialize_mint(
+        &spl_token::id(),
+        &mint.pubkey(),
+        &authority.pubkey(),
+        freeze_authority,
+        decimals,
+    )?;
+
+    send_helper_transaction(
+        svm,
+        &[create_account_ix, init_mint_ix],
+        &[authority, &mint],
+        "create mint",
+    )?;
+    Ok(mint)
+}
+
+/// Decode the base state of an SPL Token or Token-2022 account, panicking if it isn't one
+#[cfg(feature = "spl")]
+#[track_caller]
+fn unpack_token_state<T: Pack + IsInitialized>(svm: &LiteSVM, pubkey: &Pubkey, kind: &str) -> T {
+    let account = svm
+        .get_account(pubkey)
+        .unwrap_or_else(|| panic!("Account {} not found", pubkey));
+    if account.owner != spl_token::id() && account.owner != spl_token_2022::id() {
+        panic!("Account {} is not a {}: owned by {}", pubkey, kind, account.owner);
+    }
+    account
+        .data
+        .get(..T::LEN)
+        .and_then(|data| T::unpack(data).ok())
+        .unwrap_or_else(|| panic!("Account {} is not a {}", pubkey, kind))
+}
+
+/// Send a token instruction signed and paid for by `authority`
+#[cfg(feature = "spl")]
+fn send_token_instruction(
+    svm: &mut LiteSVM,
+    instruction: Instruction,
+    authority: &Keypair,
+    action: &str,
+) -> Result<(), Box<dyn Error>> {
+    send_helper_transaction(svm, &[instruction], &[authority], action)
+}
+
+/// Send a helper's transaction, paid for by the first signer
+///
+/// Goes through [`TransactionHelpers::send_instructions`] like a test's own
+/// transactions, so it gets the default compute budget, account tracking, diffs,
+/// log capture and tracing. A failed transaction becomes an error naming `action`.
+pub(crate) fn send_helper_transaction(
+    svm: &mut LiteSVM,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+    action: &str,
+) -> Result<(), Box<dyn Error>> {
+    let result = svm.send_instructions(instructions, signers)?;
+    match result.error() {
+        Some(error) => Err(format!("Failed to {}: {}", action, error).into()),
+        None => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "spl")]
     use solana_program_pack::Pack;
     use solana_sdk::signature::Signer;
+    #[cfg(feature = "spl")]
+    use solana_sdk::transaction::Transaction;
 
     #[test]
     fn test_create_funded_account() {
@@ -369,6 +1446,48 @@ mod tests {
         assert_eq!(balance, lamports);
     }
 
+    #[test]
+    fn test_create_funded_account_at() {
+        let mut svm = LiteSVM::new();
+        let treasury = Pubkey::new_unique();
+
+        svm.create_funded_account_at(&treasury, 1_000_000_000)
+            .unwrap();
+
+        let account = svm.get_account(&treasury).unwrap();
+        assert_eq!(account.lamports, 1_000_000_000);
+        assert_eq!(account.owner, solana_program::system_program::id());
+        assert!(account.data.is_empty());
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_reassign_owner() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 6).unwrap();
+        let token_account = svm
+            .create_associated_token_account(&mint.pubkey(), &authority)
+            .unwrap();
+        let before = svm.get_account(&token_account).unwrap();
+
+        let fake_program = Pubkey::new_unique();
+        svm.reassign_owner(&token_account, &fake_program).unwrap();
+
+        let after = svm.get_account(&token_account).unwrap();
+        assert_eq!(after.owner, fake_program);
+        assert_eq!(after.data, before.data);
+        assert_eq!(after.lamports, before.lamports);
+
+        // The token program now rejects the account
+        assert!(svm
+            .mint_to(&mint.pubkey(), &token_account, &authority, 1_000)
+            .is_err());
+        assert!(svm
+            .reassign_owner(&Pubkey::new_unique(), &fake_program)
+            .is_err());
+    }
+
     #[test]
     fn test_create_funded_accounts() {
         let mut svm = LiteSVM::new();
@@ -393,6 +1512,66 @@ mod tests {
         assert_eq!(pubkeys.len(), count);
     }
 
+    #[test]
+    fn test_create_rent_exempt_account() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(1_000_000_000).unwrap();
+        let program_id = Pubkey::new_unique();
+
+        let state = svm
+            .create_rent_exempt_account(&payer, 128, &program_id)
+            .unwrap();
+
+        let account = svm.get_account(&state.pubkey()).unwrap();
+        assert_eq!(account.owner, program_id);
+        assert_eq!(account.data, vec![0; 128]);
+        assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(128));
+
+        // The payer must be able to cover the rent
+        let broke = svm.create_funded_account(1).unwrap();
+        assert!(svm
+            .create_rent_exempt_account(&broke, 128, &program_id)
+            .is_err());
+    }
+
+    #[test]
+    fn test_transfer_sol() {
+        let mut svm = LiteSVM::new();
+        let alice = svm.create_funded_account(1_000_000_000).unwrap();
+        let vault = Pubkey::new_unique();
+
+        svm.transfer_sol(&alice, &vault, 100_000_000).unwrap();
+        svm.transfer_sol(&alice, &vault, 50_000_000).unwrap();
+
+        assert_eq!(svm.get_balance(&vault), Some(150_000_000));
+        // Two transfers plus one signature fee each
+        assert_eq!(svm.get_balance(&alice.pubkey()), Some(849_990_000));
+        assert!(svm.transfer_sol(&alice, &vault, 1_000_000_000).is_err());
+    }
+
+    #[test]
+    fn test_helper_transactions_use_the_send_pipeline() {
+        use crate::transaction::{set_default_compute_budget, ComputeBudget};
+
+        let mut svm = LiteSVM::new();
+        let alice = keypair_from_name("alice");
+        svm.airdrop(&alice.pubkey(), 1_000_000_000).unwrap();
+        let vault = Pubkey::new_unique();
+        svm.transfer_sol(&alice, &vault, 100_000_000).unwrap();
+
+        // Both sides of the transfer are tracked
+        let tracked: Vec<Pubkey> = svm.iter_accounts(None).map(|(pubkey, _)| pubkey).collect();
+        assert!(tracked.contains(&alice.pubkey()));
+        assert!(tracked.contains(&vault));
+
+        // The VM's default compute budget applies to helper transactions too
+        let budget = ComputeBudget::new().with_compute_unit_limit(10);
+        set_default_compute_budget(&mut svm, Some(budget));
+        let error = svm.transfer_sol(&alice, &vault, 1).unwrap_err();
+        assert!(error.to_string().starts_with("Failed to transfer SOL"), "{}", error);
+    }
+
+    #[cfg(feature = "spl")]
     #[test]
     fn test_create_token_mint() {
         let mut svm = LiteSVM::new();
@@ -412,6 +1591,7 @@ mod tests {
         assert_eq!(mint_data.supply, 0);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_create_token_account() {
         let mut svm = LiteSVM::new();
@@ -431,6 +1611,7 @@ mod tests {
         assert_eq!(token_data.amount, 0);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_create_associated_token_account() {
         let mut svm = LiteSVM::new();
@@ -456,6 +1637,7 @@ mod tests {
         assert_eq!(token_data.amount, 0);
     }
 
+    #[cfg(feature = "spl")]
     #[test]
     fn test_mint_to() {
         let mut svm = LiteSVM::new();
@@ -480,6 +1662,36 @@ mod tests {
         assert_eq!(mint_data.supply, amount);
     }
 
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_token_state_getters() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let ata = svm
+            .create_associated_token_account(&mint, &authority)
+            .unwrap();
+        svm.mint_to(&mint, &ata, &authority, 2_500).unwrap();
+
+        assert_eq!(svm.get_token_balance(&ata), 2_500);
+        let account = svm.get_token_account(&ata);
+        assert_eq!(account.mint, mint);
+        assert_eq!(account.owner, authority.pubkey());
+        let mint_state = svm.get_mint(&mint);
+        assert_eq!(mint_state.decimals, 6);
+        assert_eq!(mint_state.supply, 2_500);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    #[should_panic(expected = "is not a mint")]
+    fn test_get_mint_rejects_other_accounts() {
+        let mut svm = LiteSVM::new();
+        let wallet = svm.create_funded_account(1_000_000_000).unwrap();
+        svm.get_mint(&wallet.pubkey());
+    }
+
+    #[cfg(feature = "spl")]
     #[test]
     fn test_mint_to_multiple_times() {
         let mut svm = LiteSVM::new();
@@ -503,6 +1715,103 @@ mod tests {
         assert_eq!(token_data.amount, 600_000);
     }
 
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_token_cheatcodes() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 9).unwrap().pubkey();
+        let ata = svm
+            .create_associated_token_account(&mint, &authority)
+            .unwrap();
+
+        let billion_tokens = 1_000_000_000 * 10u64.pow(9);
+        svm.set_token_balance(&ata, billion_tokens).unwrap();
+        let account = spl_token::state::Account::unpack(&svm.get_account(&ata).unwrap().data);
+        assert_eq!(account.unwrap().amount, billion_tokens);
+        let supply = |svm: &LiteSVM| {
+            spl_token::state::Mint::unpack(&svm.get_account(&mint).unwrap().data)
+                .unwrap()
+                .supply
+        };
+        assert_eq!(supply(&svm), billion_tokens);
+
+        svm.set_mint_supply(&mint, u64::MAX).unwrap();
+        assert_eq!(supply(&svm), u64::MAX);
+        assert!(svm.set_mint_supply(&ata, 0).is_err());
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_token_lifecycle() {
+        let mut svm = LiteSVM::new();
+        let alice = svm.create_funded_account(10_000_000_000).unwrap();
+        let bob = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&alice, 6).unwrap().pubkey();
+        let alice_ata = svm.create_associated_token_account(&mint, &alice).unwrap();
+        let bob_ata = svm.create_associated_token_account(&mint, &bob).unwrap();
+        svm.mint_to(&mint, &alice_ata, &alice, 1_000).unwrap();
+        let balance = |svm: &LiteSVM, account: &Pubkey| {
+            spl_token::state::Account::unpack(&svm.get_account(account).unwrap().data).unwrap()
+        };
+
+        svm.transfer_tokens(&alice_ata, &bob_ata, &alice, 400).unwrap();
+        svm.burn_tokens(&alice_ata, &mint, &alice, 100).unwrap();
+        assert_eq!(balance(&svm, &alice_ata).amount, 500);
+        assert_eq!(balance(&svm, &bob_ata).amount, 400);
+
+        // Bob spends part of his allowance as delegate
+        svm.approve_delegate(&alice_ata, &bob.pubkey(), &alice, 200).unwrap();
+        svm.transfer_tokens(&alice_ata, &bob_ata, &bob, 150).unwrap();
+        assert_eq!(balance(&svm, &alice_ata).delegated_amount, 50);
+        svm.revoke_delegate(&alice_ata, &alice).unwrap();
+        assert!(svm.transfer_tokens(&alice_ata, &bob_ata, &bob, 10).is_err());
+
+        // Only empty accounts can be closed
+        assert!(svm.close_token_account(&bob_ata, &bob.pubkey(), &bob).is_err());
+        svm.transfer_tokens(&bob_ata, &alice_ata, &bob, 550).unwrap();
+        // A fresh blockhash keeps the retry from being rejected as a duplicate
+        svm.expire_blockhash();
+        svm.close_token_account(&bob_ata, &bob.pubkey(), &bob).unwrap();
+        assert!(svm.get_account(&bob_ata).is_none_or(|account| account.data.is_empty()));
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_freeze_and_thaw_token_account() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_freezable_token_mint(&authority, 6).unwrap().pubkey();
+        let ata = svm.create_associated_token_account(&mint, &authority).unwrap();
+        svm.mint_to(&mint, &ata, &authority, 1_000).unwrap();
+
+        svm.freeze_token_account(&ata, &mint, &authority).unwrap();
+        assert!(svm.burn_tokens(&ata, &mint, &authority, 1).is_err());
+
+        svm.thaw_token_account(&ata, &mint, &authority).unwrap();
+        svm.burn_tokens(&ata, &mint, &authority, 2).unwrap();
+
+        // Plain mints have no freeze authority
+        let plain = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let plain_ata = svm.create_associated_token_account(&plain, &authority).unwrap();
+        assert!(svm.freeze_token_account(&plain_ata, &plain, &authority).is_err());
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_read_u64_at() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&authority, 6).unwrap().pubkey();
+        let ata = svm.create_associated_token_account(&mint, &authority).unwrap();
+        svm.mint_to(&mint, &ata, &authority, 1_234).unwrap();
+
+        assert_eq!(svm.read_u64_at(&mint, 36), Some(1_234));
+        assert_eq!(svm.read_u64_at(&ata, 64), Some(1_234));
+        assert_eq!(svm.read_u64_at(&mint, 80), None);
+        assert_eq!(svm.read_u64_at(&Pubkey::new_unique(), 0), None);
+    }
+
     #[test]
     fn test_derive_pda() {
         let svm = LiteSVM::new();
@@ -583,4 +1892,139 @@ mod tests {
         svm.advance_slot(5);
         assert_eq!(svm.get_current_slot(), 40);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_warp_to_epoch() {
+        use solana_program::clock::Clock;
+
+        let mut svm = LiteSVM::new();
+        let schedule = svm.get_sysvar::<EpochSchedule>();
+        svm.set_unix_timestamp(1_700_000_000);
+
+        svm.warp_to_epoch(20);
+        let clock = svm.get_sysvar::<Clock>();
+        assert_eq!(clock.epoch, 20);
+        assert_eq!(clock.slot, schedule.get_first_slot_in_epoch(20));
+        assert_eq!(schedule.get_epoch(clock.slot), 20);
+        assert_eq!(clock.leader_schedule_epoch, 21);
+        assert_eq!(clock.epoch_start_timestamp, 1_700_000_000);
+
+        assert_eq!(svm.advance_epochs(2), 22);
+        assert_eq!(svm.get_current_epoch(), 22);
+        assert_eq!(svm.get_current_slot(), schedule.get_first_slot_in_epoch(22));
+    }
+
+    #[test]
+    fn test_unix_timestamp() {
+        let mut svm = LiteSVM::new();
+        svm.advance_slot(5);
+
+        svm.set_unix_timestamp(1_700_000_000);
+        assert_eq!(svm.get_unix_timestamp(), 1_700_000_000);
+        assert_eq!(svm.advance_clock_by_seconds(3_600), 1_700_003_600);
+        assert_eq!(svm.advance_clock_by_seconds(-600), 1_700_003_000);
+        assert_eq!(svm.get_unix_timestamp(), 1_700_003_000);
+        assert_eq!(svm.get_current_slot(), 5);
+
+        // Saturates instead of overflowing
+        assert_eq!(svm.advance_clock_by_seconds(i64::MAX), i64::MAX);
+        svm.set_unix_timestamp(-1);
+        assert_eq!(svm.advance_clock_by_seconds(i64::MIN), i64::MIN);
+    }
+
+    #[test]
+    fn test_advance_blockhash() {
+        use crate::transaction::TransactionHelpers;
+
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = solana_program::system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000_000,
+        );
+        let signed = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            std::slice::from_ref(&ix),
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+
+        let stale = svm.latest_blockhash();
+        let fresh = svm.advance_blockhash(3);
+        assert_ne!(stale, fresh);
+        assert_eq!(svm.advance_blockhash(0), fresh);
+
+        let result = svm.send_transaction_result(signed).unwrap();
+        result.assert_error("BlockhashNotFound");
+
+        // A retry re-signed against the new blockhash goes through
+        svm.send_instruction(ix, &[&payer]).unwrap().assert_success();
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut svm = LiteSVM::new();
+        let user = svm.create_funded_account(1_000_000_000).unwrap();
+        svm.advance_slot(5);
+        let baseline = svm.snapshot();
+
+        let report = crate::surgery::surgery_report(&svm);
+        let tracked = svm.iter_accounts(None).count();
+
+        let other = svm.create_funded_account(1_000_000_000).unwrap();
+        svm.airdrop(&user.pubkey(), 500).unwrap();
+        svm.advance_slot(10);
+        let written = Pubkey::new_unique();
+        svm.unsafe_state()
+            .set_account(&written, Account::new(1_000_000, 0, &Pubkey::default()))
+            .unwrap();
+        assert_eq!(crate::surgery::surgery_report(&svm).len(), report.len() + 1);
+
+        for _ in 0..2 {
+            svm.restore(&baseline);
+            assert_eq!(svm.get_balance(&user.pubkey()), Some(1_000_000_000));
+            assert!(svm.get_account(&other.pubkey()).is_none());
+            assert_eq!(svm.get_current_slot(), 5);
+
+            // Helper state rolls back with the accounts
+            assert_eq!(crate::surgery::surgery_report(&svm), report);
+            assert_eq!(svm.iter_accounts(None).count(), tracked);
+            assert!(svm.get_account(&written).is_none());
+
+            // The restored VM doesn't remember transactions sent after the snapshot
+            svm.airdrop(&user.pubkey(), 500).unwrap();
+        }
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_deploy_upgradeable_program() {
+        let mut svm = LiteSVM::new();
+        let authority = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        svm.advance_slot(5);
+
+        // Redeploy the bundled memo program through the upgradeable loader
+        let memo_id = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+        let elf = svm.get_account(&memo_id).unwrap().data;
+        svm.deploy_upgradeable_program(program_id, &elf, Some(authority))
+            .unwrap();
+
+        let program_data = svm.get_program_data(&program_id).unwrap();
+        assert_eq!(program_data.slot, 5);
+        assert_eq!(program_data.upgrade_authority, Some(authority));
+        assert!(svm.get_program_data(&memo_id).is_none());
+
+        // The program is executable
+        let payer = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = solana_program::instruction::Instruction::new_with_bytes(program_id, b"hello", vec![]);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+//! Token-2022 extension helpers
+//!
+//! Helpers for Token-2022 mints whose behaviour depends on extension state, such as
+//! interest-bearing mints whose UI amount grows with the clock.
+
+use crate::keypairs::new_keypair;
+use crate::test_helpers::send_helper_transaction;
+use litesvm::LiteSVM;
+use solana_program::clock::Clock;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_token_2022::extension::interest_bearing_mint::{self, InterestBearingConfig};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::state::Mint;
+use std::error::Error;
+
+/// Token-2022 helper methods for LiteSVM
+pub trait Token2022Helpers {
+    /// Create a Token-2022 mint with the interest-bearing extension
+    ///
+    /// `authority` becomes both the mint authority and the rate authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `authority` - Mint and rate authority; pays for the mint account
+    /// * `decimals` - Mint decimals
+    /// * `rate_bps` - Annual interest rate in basis points
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mint = svm.create_interest_bearing_mint(&authority, 6, 500)?; // 5% APR
+    /// ```
+    fn create_interest_bearing_mint(
+        &mut self,
+        authority: &Keypair,
+        decimals: u8,
+        rate_bps: i16,
+    ) -> Result<Keypair, Box<dyn Error>>;
+
+    /// Compute the UI amount of `amount` for an interest-bearing mint at the current clock
+    ///
+    /// Uses the protocol's own `amount_to_ui_amount`, so the result matches what
+    /// Token-2022 reports for the same raw amount.
+    fn interest_bearing_ui_amount(
+        &self,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<String, Box<dyn Error>>;
+
+    /// Advance the clock by `seconds` and compute the accrued UI amount of `amount`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mint = svm.create_interest_bearing_mint(&authority, 6, 500)?;
+    /// let one_year = 365 * 24 * 60 * 60;
+    /// let expected = svm.advance_and_accrue(&mint.pubkey(), 1_000_000, one_year)?;
+    /// // `expected` is ~"1.0512" (5% compounded continuously)
+    /// ```
+    fn advance_and_accrue(
+        &mut self,
+        mint: &Pubkey,
+        amount: u64,
+        seconds: i64,
+    ) -> Result<String, Box<dyn Error>>;
+}
+
+impl Token2022Helpers for LiteSVM {
+    fn create_interest_bearing_mint(
+        &mut self,
+        authority: &Keypair,
+        decimals: u8,
+        rate_bps: i16,
+    ) -> Result<Keypair, Box<dyn Error>> {
+        let mint = new_keypair(self);
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+            ExtensionType::InterestBearingConfig,
+        ])?;
+        let rent = self.minimum_balance_for_rent_exemption(space);
+
+        let create_account_ix = solana_program::system_instruction::create_account(
+            &authority.pubkey(),
+            &mint.pubkey(),
+            rent,
+            space as u64,
+            &spl_token_2022::id(),
+        );
+
+        // Extensions must be initialized before the mint itself
+        let init_interest_ix = interest_bearing_mint::instruction::initialize(
+            &spl_token_2022::id(),
+            &mint.pubkey(),
+            Some(authority.pubkey()),
+            rate_bps,
+        )?;
+
+        let init_mint_ix = spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::id(),
+            &mint.pubkey(),
+            &authority.pubkey(),
+            None,
+            decimals,
+        )?;
+
+        send_helper_transaction(
+            self,
+            &[create_account_ix, init_interest_ix, init_mint_ix],
+            &[authority, &mint],
+            "create interest-bearing mint",
+        )?;
+        Ok(mint)
+    }
+
+    fn interest_bearing_ui_amount(
+        &self,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<String, Box<dyn Error>> {
+        let account = self
+            .get_account(mint)
+            .ok_or_else(|| format!("Mint {} not found", mint))?;
+        let state = StateWithExtensions::<Mint>::unpack(&account.data)?;
+        let config = state.get_extension::<InterestBearingConfig>()?;
+        let now = self.get_sysvar::<Clock>().unix_timestamp;
+
+        config
+            .amount_to_ui_amount(amount, state.base.decimals, now)
+            .ok_or_else(|| format!("Failed to compute UI amount for mint {}", mint).into())
+    }
+
+    fn advance_and_accrue(
+        &mut self,
+        mint: &Pubkey,
+        amount: u64,
+        seconds: i64,
+    ) -> Result<String, Box<dyn Error>> {
+        let mut clock = self.get_sysvar::<Clock>();
+        clock.unix_timestamp += seconds;
+        self.set_sysvar(&clock);
+
+        self.interest_bearing_ui_amount(mint, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestHelpers;
+
+    #[test]
+    fn test_interest_bearing_mint_accrues() {
+        let mut svm = LiteSVM::new();
+        let authority = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm
+            .create_interest_bearing_mint(&authority, 6, 500)
+            .unwrap();
+
+        let initial: f64 = svm
+            .interest_bearing_ui_amount(&mint.pubkey(), 1_000_000)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(initial, 1.0);
+
+        let one_year = 365 * 24 * 60 * 60;
+        let accrued: f64 = svm
+            .advance_and_accrue(&mint.pubkey(), 1_000_000, one_year)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(
+            (accrued - 0.05_f64.exp()).abs() < 1e-3,
+            "accrued {}",
+            accrued
+        );
+    }
+}
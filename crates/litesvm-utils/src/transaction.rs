@@ -3,14 +3,39 @@
 //! This module provides convenient wrappers for executing transactions
 //! and handling their results in tests.
 
+use crate::account_diff::{capture_writable_accounts, diff_accounts, AccountDiff};
+use crate::account_registry::{message_account_keys, track_accounts};
+use crate::cu_tracker::track_compute_units;
+use crate::log_capture::capture_transaction;
+use crate::log_tree::{format_logs, LogTree};
+use crate::transaction_builder::TransactionBuilder;
+use crate::vm_state::{vm_state, vm_state_mut};
 use litesvm::types::TransactionMetadata;
 use litesvm::LiteSVM;
-use solana_program::instruction::Instruction;
-use solana_sdk::signature::{Keypair, Signer};
-use solana_sdk::transaction::Transaction;
+use solana_program::hash::Hash;
+use solana_program::instruction::{CompiledInstruction, Instruction};
+use solana_program::message::Message;
+use solana_program::message::inner_instruction::InnerInstructionsList;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use std::collections::HashMap;
 use std::fmt;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Lamports LiteSVM charges per signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Precompiles whose signatures are charged like transaction signatures
+const PRECOMPILE_PROGRAMS: [Pubkey; 3] = [
+    solana_sdk::ed25519_program::ID,
+    solana_sdk::secp256k1_program::ID,
+    Pubkey::from_str_const("Secp256r1SigVerify1111111111111111111111111"),
+];
+
 #[derive(Error, Debug)]
 pub enum TransactionError {
     #[error("Transaction execution failed: {0}")]
@@ -36,10 +61,19 @@ pub enum TransactionError {
 /// assert!(result.has_log("Transfer complete"));
 /// println!("Used {} compute units", result.compute_units());
 /// ```
+#[derive(Clone)]
 pub struct TransactionResult {
     inner: TransactionMetadata,
     instruction_name: Option<String>,
     error: Option<String>,
+    signature: Signature,
+    blockhash: Option<Hash>,
+    fee_payer: Option<Pubkey>,
+    transaction_bytes: Option<Vec<u8>>,
+    account_keys: Vec<Pubkey>,
+    account_diffs: Vec<AccountDiff>,
+    error_names: HashMap<(Pubkey, u32), String>,
+    prepended_instructions: usize,
 }
 
 impl TransactionResult {
@@ -51,9 +85,17 @@ impl TransactionResult {
     /// * `instruction_name` - Optional name of the instruction for debugging
     pub fn new(result: TransactionMetadata, instruction_name: Option<String>) -> Self {
         Self {
+            signature: result.signature,
             inner: result,
             instruction_name,
             error: None,
+            blockhash: None,
+            fee_payer: None,
+            transaction_bytes: None,
+            account_keys: Vec::new(),
+            account_diffs: Vec::new(),
+            error_names: HashMap::new(),
+            prepended_instructions: 0,
         }
     }
 
@@ -66,10 +108,144 @@ impl TransactionResult {
     /// * `instruction_name` - Optional name of the instruction for debugging
     pub fn new_failed(error: String, result: TransactionMetadata, instruction_name: Option<String>) -> Self {
         Self {
+            signature: result.signature,
             inner: result,
             instruction_name,
             error: Some(error),
+            blockhash: None,
+            fee_payer: None,
+            transaction_bytes: None,
+            account_keys: Vec::new(),
+            account_diffs: Vec::new(),
+            error_names: HashMap::new(),
+            prepended_instructions: 0,
+        }
+    }
+
+    /// Record the transaction this result came from
+    ///
+    /// Fills in [`signature`](Self::signature), [`blockhash`](Self::blockhash),
+    /// [`fee_payer`](Self::fee_payer) and [`transaction_bytes`](Self::transaction_bytes),
+    /// and the account keys [`cpi_programs`](Self::cpi_programs) resolves against.
+    /// The helpers call this for every transaction they send.
+    pub fn with_transaction(self, transaction: &Transaction) -> Self {
+        self.with_versioned_transaction(&VersionedTransaction::from(transaction.clone()))
+    }
+
+    /// Record the versioned transaction this result came from
+    ///
+    /// See [`with_transaction`](Self::with_transaction).
+    pub fn with_versioned_transaction(mut self, transaction: &VersionedTransaction) -> Self {
+        if let Some(signature) = transaction.signatures.first() {
+            self.signature = *signature;
         }
+        self.blockhash = Some(*transaction.message.recent_blockhash());
+        self.fee_payer = transaction.message.static_account_keys().first().copied();
+        self.transaction_bytes = bincode::serialize(transaction).ok();
+        self.account_keys = transaction.message.static_account_keys().to_vec();
+        self
+    }
+
+    /// Name the instruction this result belongs to, replacing any earlier name
+    pub fn with_instruction_name(mut self, name: impl Into<String>) -> Self {
+        self.instruction_name = Some(name.into());
+        self
+    }
+
+    /// Record that the helpers put `count` instructions in front of the caller's
+    ///
+    /// Compute budget and ATA creation instructions are prepended this way.
+    /// [`failed_instruction_index`](Self::failed_instruction_index) and
+    /// [`assert_error_at`](Self::assert_error_at) skip them, so indexes match the
+    /// caller's own instruction list.
+    pub fn with_prepended_instructions(mut self, count: usize) -> Self {
+        self.prepended_instructions = count;
+        self
+    }
+
+    /// Get the number of instructions the helpers prepended to the caller's
+    ///
+    /// See [`with_prepended_instructions`](Self::with_prepended_instructions).
+    pub fn prepended_instructions(&self) -> usize {
+        self.prepended_instructions
+    }
+
+    /// Attach the account changes recorded around this transaction
+    ///
+    /// The helpers call this when [`record_account_diffs`](crate::record_account_diffs)
+    /// is on.
+    pub fn with_account_diffs(mut self, account_diffs: Vec<AccountDiff>) -> Self {
+        self.account_diffs = account_diffs;
+        self
+    }
+
+    /// Name the custom error codes of `program_id`, so failures report them by name
+    ///
+    /// Once a code is named, a `custom program error: 0x1771` raised by `program_id`
+    /// reads as e.g. `InvalidAmount (6001)` in assertion failures and reports, and
+    /// [`assert_anchor_error`](Self::assert_anchor_error) matches the name even when
+    /// the program didn't log it. `AnchorContext` names the errors of every loaded IDL.
+    pub fn with_error_names(
+        mut self,
+        program_id: Pubkey,
+        names: impl IntoIterator<Item = (u32, String)>,
+    ) -> Self {
+        self.error_names
+            .extend(names.into_iter().map(|(code, name)| ((program_id, code), name)));
+        self
+    }
+
+    /// Get the program and code of the custom error the transaction failed with
+    ///
+    /// Read from the `Program <id> failed: custom program error: 0x..` log line.
+    pub fn custom_error(&self) -> Option<(Pubkey, u32)> {
+        self.error.as_ref()?;
+        self.logs().iter().rev().find_map(|log| {
+            let (program, code) = log
+                .strip_prefix("Program ")?
+                .split_once(" failed: custom program error: 0x")?;
+            Some((program.parse().ok()?, u32::from_str_radix(code.trim(), 16).ok()?))
+        })
+    }
+
+    /// Get the name of the custom error the transaction failed with, if it was named
+    ///
+    /// See [`with_error_names`](Self::with_error_names).
+    pub fn custom_error_name(&self) -> Option<&str> {
+        self.custom_error()
+            .and_then(|key| self.error_names.get(&key))
+            .map(String::as_str)
+    }
+
+    /// Rewrite the custom error code in `text` as its name, if it was named
+    pub(crate) fn name_custom_error(&self, text: &str) -> String {
+        let Some(((_, code), name)) = self
+            .custom_error()
+            .and_then(|key| Some((key, self.error_names.get(&key)?)))
+        else {
+            return text.to_string();
+        };
+        let named = format!("{} ({})", name, code);
+        text.replace(&format!("custom program error: 0x{:x}", code), &named)
+            .replace(&format!("Custom({})", code), &named)
+    }
+
+    /// The error message, with a named custom error code rewritten
+    pub(crate) fn error_report(&self) -> Option<String> {
+        self.error.as_deref().map(|error| self.name_custom_error(error))
+    }
+
+    /// The logs joined by newlines, with a named custom error code rewritten
+    fn log_report(&self) -> String {
+        self.name_custom_error(&self.logs().join("\n"))
+    }
+
+    /// Get the before/after state of every writable account of the transaction
+    ///
+    /// Empty unless [`record_account_diffs`](crate::record_account_diffs) was on when
+    /// the transaction was sent. Unchanged accounts are included.
+    pub fn account_diffs(&self) -> &[AccountDiff] {
+        &self.account_diffs
     }
 
     /// Assert that the transaction succeeded, panic with logs if it failed
@@ -87,8 +263,8 @@ impl TransactionResult {
         assert!(
             self.error.is_none(),
             "Transaction failed: {}\nLogs:\n{}",
-            self.error.as_ref().unwrap_or(&"Unknown error".to_string()),
-            self.logs().join("\n")
+            self.error_report().unwrap_or_else(|| "Unknown error".to_string()),
+            self.log_report()
         );
         self
     }
@@ -146,6 +322,177 @@ impl TransactionResult {
         self.inner.logs.iter().find(|log| log.contains(pattern))
     }
 
+    /// Get the log entries emitted while `program_id` was the executing program
+    ///
+    /// Log lines from programs it invokes via CPI are excluded, as are lines from
+    /// other top-level instructions, so assertions aren't polluted by token or
+    /// system program output.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let own_logs = result.logs_for(&program_id);
+    /// assert!(own_logs.iter().any(|log| log.contains("Deposited")));
+    /// ```
+    pub fn logs_for(&self, program_id: &Pubkey) -> Vec<&String> {
+        let program_id = program_id.to_string();
+        let mut frames: Vec<&str> = Vec::new();
+        let mut logs = Vec::new();
+
+        for log in &self.inner.logs {
+            if let Some(invoked) = log
+                .strip_prefix("Program ")
+                .and_then(|rest| rest.split_once(" invoke ["))
+                .map(|(id, _)| id)
+            {
+                frames.push(invoked);
+            }
+
+            if frames.last() == Some(&program_id.as_str()) {
+                logs.push(log);
+            }
+
+            let returned = match (frames.last(), log.strip_prefix("Program ")) {
+                (Some(id), Some(rest)) => rest
+                    .strip_prefix(id)
+                    .is_some_and(|outcome| outcome == " success" || outcome.starts_with(" failed")),
+                _ => false,
+            };
+            if returned {
+                frames.pop();
+            }
+        }
+        logs
+    }
+
+    /// Parse the logs into a tree of program invocations
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tree = result.log_tree();
+    /// assert_eq!(tree.roots()[0].children[0].program_id, spl_token::id());
+    /// ```
+    pub fn log_tree(&self) -> LogTree {
+        LogTree::parse(&self.inner.logs)
+    }
+
+    /// Get the inner instructions (CPIs) of each top-level instruction
+    ///
+    /// Program and account indexes refer to the transaction's account keys.
+    pub fn inner_instructions(&self) -> &InnerInstructionsList {
+        &self.inner.inner_instructions
+    }
+
+    /// Get the program invoked by every CPI, in execution order
+    ///
+    /// Empty if the transaction isn't known, i.e. the result wasn't created by the
+    /// helpers or [`with_transaction`](Self::with_transaction). Programs loaded
+    /// from address lookup tables aren't resolved.
+    pub fn cpi_programs(&self) -> Vec<Pubkey> {
+        self.inner
+            .inner_instructions
+            .iter()
+            .flatten()
+            .filter_map(|inner| {
+                self.account_keys
+                    .get(inner.instruction.program_id_index as usize)
+                    .copied()
+            })
+            .collect()
+    }
+
+    /// Count the CPIs into `program_id`
+    pub fn cpi_count(&self, program_id: &Pubkey) -> usize {
+        self.cpi_programs()
+            .iter()
+            .filter(|program| *program == program_id)
+            .count()
+    }
+
+    /// Assert that the transaction made at least one CPI into `program_id`
+    ///
+    /// # Returns
+    ///
+    /// Returns self for chaining
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_cpi_to(&spl_token::id());
+    /// ```
+    pub fn assert_cpi_to(&self, program_id: &Pubkey) -> &Self {
+        assert!(
+            self.cpi_count(program_id) > 0,
+            "Expected a CPI to {}, but none happened. CPIs: {:?}",
+            program_id,
+            self.cpi_programs()
+        );
+        self
+    }
+
+    /// Assert that the transaction made exactly `expected` CPIs into `program_id`
+    ///
+    /// # Returns
+    ///
+    /// Returns self for chaining
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // One token transfer per recipient
+    /// result.assert_cpi_count(&spl_token::id(), 3);
+    /// ```
+    pub fn assert_cpi_count(&self, program_id: &Pubkey, expected: usize) -> &Self {
+        let actual = self.cpi_count(program_id);
+        assert_eq!(
+            actual, expected,
+            "Expected {} CPI(s) to {}, found {}. CPIs: {:?}",
+            expected,
+            program_id,
+            actual,
+            self.cpi_programs()
+        );
+        self
+    }
+
+    /// Count the occurrences of a substring across all log entries
+    pub fn log_count(&self, pattern: &str) -> usize {
+        self.inner
+            .logs
+            .iter()
+            .map(|log| log.matches(pattern).count())
+            .sum()
+    }
+
+    /// Assert that a substring occurs exactly `expected` times in the logs
+    ///
+    /// Useful for loops and batched instructions, where the number of repetitions
+    /// matters and [`has_log`](Self::has_log) can't tell.
+    ///
+    /// # Returns
+    ///
+    /// Returns self for chaining
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_log_count("Instruction: Transfer", 2);
+    /// ```
+    pub fn assert_log_count(&self, pattern: &str, expected: usize) -> &Self {
+        let actual = self.log_count(pattern);
+        assert_eq!(
+            actual,
+            expected,
+            "Expected '{}' to appear {} time(s) in the logs, found {}.\nLogs:\n{}",
+            pattern,
+            expected,
+            actual,
+            self.log_report()
+        );
+        self
+    }
+
     /// Get the compute units consumed
     ///
     /// # Returns
@@ -155,20 +502,294 @@ impl TransactionResult {
         self.inner.compute_units_consumed
     }
 
+    /// Break the compute units down by instruction, from the program logs
+    ///
+    /// Returns one entry per program invocation, in execution order: each
+    /// top-level instruction (`depth` 1) followed by its CPIs (`depth` 2 and up).
+    /// A program's consumption includes that of the CPIs it made. Builtin
+    /// programs such as the system program don't log their usage and have `None`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for entry in result.compute_units_per_instruction() {
+    ///     let indent = "  ".repeat(entry.depth - 1);
+    ///     println!("{}{}: {:?}", indent, entry.program_id, entry.compute_units);
+    /// }
+    /// ```
+    pub fn compute_units_per_instruction(&self) -> Vec<InstructionComputeUnits> {
+        let mut entries: Vec<InstructionComputeUnits> = Vec::new();
+        let mut frames: Vec<usize> = Vec::new();
+        let mut instruction_index = 0;
+
+        for log in &self.inner.logs {
+            let Some(rest) = log.strip_prefix("Program ") else {
+                continue;
+            };
+            let Some((id, event)) = rest.split_once(' ') else {
+                continue;
+            };
+
+            if let Some(depth) = event
+                .strip_prefix("invoke [")
+                .and_then(|depth| depth.strip_suffix(']'))
+                .and_then(|depth| depth.parse::<usize>().ok())
+            {
+                let Ok(program_id) = id.parse() else {
+                    continue;
+                };
+                if depth == 1 && !entries.is_empty() {
+                    instruction_index += 1;
+                }
+                frames.push(entries.len());
+                entries.push(InstructionComputeUnits {
+                    instruction_index,
+                    program_id,
+                    depth,
+                    compute_units: None,
+                });
+                continue;
+            }
+
+            let Some(&frame) = frames.last() else {
+                continue;
+            };
+            if entries[frame].program_id.to_string() != id {
+                continue;
+            }
+            if let Some(consumed) = event
+                .strip_prefix("consumed ")
+                .and_then(|consumed| consumed.split_once(' '))
+                .and_then(|(units, _)| units.parse::<u64>().ok())
+            {
+                entries[frame].compute_units = Some(consumed);
+            } else if event == "success" || event.starts_with("failed") {
+                frames.pop();
+            }
+        }
+        entries
+    }
+
+    /// Assert that the transaction consumed fewer than `max` compute units
+    ///
+    /// Like [`assert_cu_under`](Self::assert_cu_under), labelled with the
+    /// instruction name the result was created with.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_success().assert_compute_units_under(50_000);
+    /// ```
+    pub fn assert_compute_units_under(&self, max: u64) -> &Self {
+        self.assert_cu_under(self.instruction_name.as_deref().unwrap_or("transaction"), max)
+    }
+
+    /// Assert that the transaction consumed between `min` and `max` compute units,
+    /// inclusive
+    ///
+    /// A lower bound catches instructions that silently stopped doing work, such as
+    /// an early return that skips a transfer.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_compute_units_between(4_000, 6_000);
+    /// ```
+    pub fn assert_compute_units_between(&self, min: u64, max: u64) -> &Self {
+        let used = self.compute_units();
+        assert!(
+            (min..=max).contains(&used),
+            "Instruction '{}' used {} CU, expected between {} and {} CU",
+            self.instruction_name.as_deref().unwrap_or("transaction"),
+            used,
+            min,
+            max
+        );
+        self
+    }
+
+    /// Assert that the transaction consumed fewer than `max` compute units
+    ///
+    /// The failure message names the instruction and reports the actual usage.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A label for the instruction, used in the failure message
+    /// * `max` - The exclusive compute unit ceiling
+    ///
+    /// # Returns
+    ///
+    /// Returns self for chaining
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_cu_under("take", 180_000);
+    /// ```
+    pub fn assert_cu_under(&self, name: &str, max: u64) -> &Self {
+        let used = self.compute_units();
+        assert!(
+            used < max,
+            "Instruction '{}' exceeded its compute budget: used {} CU, budget {} CU (over by {})",
+            name,
+            used,
+            max,
+            used - max + 1
+        );
+        self
+    }
+
+    /// Assert that the transaction stays under the budget registered for `name`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the budget is exceeded or no budget is registered for `name`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let budgets = CuBudgets::new().with("make", 60_000).with("take", 180_000);
+    /// result.assert_cu_budget(&budgets, "take");
+    /// ```
+    pub fn assert_cu_budget(&self, budgets: &CuBudgets, name: &str) -> &Self {
+        let max = budgets
+            .get(name)
+            .unwrap_or_else(|| panic!("No compute budget registered for '{}'", name));
+        self.assert_cu_under(name, max)
+    }
+
+    /// Assert that the transaction's compute units haven't regressed past the
+    /// baseline recorded for `name`
+    ///
+    /// `tolerance` is the allowed growth as a fraction of the baseline. The first
+    /// call for a name records its baseline; see [`cu_tracker`](crate::cu_tracker)
+    /// for where the file lives and how to update it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transaction failed, usage exceeds the baseline by more than
+    /// `tolerance`, or the baseline file can't be read or written
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// result.assert_cu_within_baseline("make", 0.05);
+    /// ```
+    pub fn assert_cu_within_baseline(&self, name: &str, tolerance: f64) -> &Self {
+        if let Some(error) = &self.error {
+            panic!(
+                "Instruction '{}' failed, so its compute units can't be compared with the baseline: {}",
+                name, error
+            );
+        }
+        if let Err(e) = track_compute_units(name, self.compute_units(), tolerance) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// Get the number of events emitted (`Program data:` log lines)
+    pub fn event_count(&self) -> usize {
+        self.logs()
+            .iter()
+            .filter(|log| log.starts_with("Program data: "))
+            .count()
+    }
+
     /// Print the transaction logs
+    ///
+    /// With the `color` feature, program ids and failures are highlighted unless the
+    /// `NO_COLOR` environment variable is set.
     pub fn print_logs(&self) {
-        println!("=== Transaction Logs ===");
+        let color = cfg!(feature = "color") && std::env::var_os("NO_COLOR").is_none();
+        print!("{}", self.pretty_report(color));
+    }
+
+    /// Format the full report printed by [`print_logs`](Self::print_logs)
+    ///
+    /// Log lines are indented by CPI depth, and successful token program calls are
+    /// collapsed to one line; [`logs`](Self::logs) has them in full. Use
+    /// [`Display`](fmt::Display) instead for a one-line summary.
+    pub fn to_pretty_string(&self) -> String {
+        self.pretty_report(false)
+    }
+
+    fn pretty_report(&self, color: bool) -> String {
+        let mut out = String::from("=== Transaction Logs ===\n");
         if let Some(name) = &self.instruction_name {
-            println!("Instruction: {}", name);
+            out.push_str(&format!("Instruction: {}\n", name));
         }
-        for log in &self.inner.logs {
-            println!("{}", log);
+        let logs: Vec<String> = self
+            .inner
+            .logs
+            .iter()
+            .map(|log| self.name_custom_error(log))
+            .collect();
+        out.push_str(&format_logs(&logs, true, color));
+        if let Some(err) = self.error_report() {
+            out.push_str(&format!("Error: {}\n", err));
         }
-        if let Some(err) = &self.error {
-            println!("Error: {}", err);
+        out.push_str(&format!("Compute Units: {}\n", self.compute_units()));
+        out.push_str(&format!("Events: {}\n", self.event_count()));
+        let changed: Vec<&AccountDiff> = self
+            .account_diffs
+            .iter()
+            .filter(|diff| diff.is_changed())
+            .collect();
+        if !changed.is_empty() {
+            out.push_str("Account Changes:\n");
+            for diff in changed {
+                out.push_str(&format!("  {}\n", diff));
+            }
         }
-        println!("Compute Units: {}", self.compute_units());
-        println!("========================");
+        out.push_str("========================\n");
+        out
+    }
+
+    /// Get the name of the instruction this result belongs to, if known
+    pub fn instruction_name(&self) -> Option<&str> {
+        self.instruction_name.as_deref()
+    }
+
+    /// Get the transaction signature
+    ///
+    /// Results are recorded in the VM's history under this signature, so it can
+    /// be used to correlate and deduplicate them.
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    /// Get the recent blockhash the transaction was built with, if known
+    pub fn blockhash(&self) -> Option<Hash> {
+        self.blockhash
+    }
+
+    /// Get the fee payer of the transaction, if known
+    pub fn fee_payer(&self) -> Option<Pubkey> {
+        self.fee_payer
+    }
+
+    /// Get the wire-format bytes of the transaction, if known
+    ///
+    /// The bytes can be fed back into
+    /// [`send_serialized_transaction`](TransactionHelpers::send_serialized_transaction)
+    /// or handed to other tooling, such as a JS SDK fixture.
+    pub fn transaction_bytes(&self) -> Option<&[u8]> {
+        self.transaction_bytes.as_deref()
+    }
+
+    /// Get the data the program set with `set_return_data`, empty if none
+    ///
+    /// Decode it with the program's own format, e.g.
+    /// `u64::from_le_bytes(result.return_data().try_into()?)`.
+    pub fn return_data(&self) -> &[u8] {
+        &self.inner.return_data.data
+    }
+
+    /// Get the program that set the return data, if any did
+    pub fn return_data_program(&self) -> Option<Pubkey> {
+        let program_id = self.inner.return_data.program_id;
+        (program_id != Pubkey::default()).then_some(program_id)
     }
 
     /// Get the inner TransactionMetadata for direct access
@@ -195,7 +816,7 @@ impl TransactionResult {
         assert!(
             self.error.is_some(),
             "Expected transaction to fail, but it succeeded.\nLogs:\n{}",
-            self.logs().join("\n")
+            self.log_report()
         );
         self
     }
@@ -222,12 +843,13 @@ impl TransactionResult {
     pub fn assert_error(&self, expected_error: &str) -> &Self {
         match &self.error {
             Some(error) => {
+                let named = self.name_custom_error(error);
                 assert!(
-                    error.contains(expected_error),
+                    error.contains(expected_error) || named.contains(expected_error),
                     "Transaction failed with unexpected error.\nExpected substring: {}\nActual error: {}\nLogs:\n{}",
                     expected_error,
-                    error,
-                    self.logs().join("\n")
+                    named,
+                    self.log_report()
                 );
             }
             None => {
@@ -302,12 +924,87 @@ impl TransactionResult {
             .map(|e| e.contains(error_name))
             .unwrap_or(false);
 
+        // And the name registered for the custom error code, bare or qualified
+        let found_in_names = self.custom_error_name().is_some_and(|name| {
+            error_name == name || error_name.ends_with(&format!("::{}", name))
+        });
+
         assert!(
-            found_in_logs || found_in_error,
+            found_in_logs || found_in_error || found_in_names,
             "Expected Anchor error '{}' not found in transaction logs or error message.\nError: {:?}\nLogs:\n{}",
             error_name,
-            self.error,
-            self.logs().join("\n")
+            self.error_report(),
+            self.log_report()
+        );
+        self
+    }
+
+    /// Get the index of the instruction that failed, if the failure was an instruction error
+    ///
+    /// The index is in the caller's numbering: instructions the helpers prepended,
+    /// such as a default compute budget, aren't counted, so 0 is the first
+    /// instruction passed in. The raw error message keeps the runtime's numbering.
+    /// Transaction-level failures (e.g. an unknown blockhash) and failures of a
+    /// prepended instruction have no index.
+    pub fn failed_instruction_index(&self) -> Option<u8> {
+        let rest = self.error.as_ref()?.strip_prefix("InstructionError(")?;
+        let index: u8 = rest.split(',').next()?.trim().parse().ok()?;
+        let index = (index as usize).checked_sub(self.prepended_instructions)?;
+        u8::try_from(index).ok()
+    }
+
+    /// Assert that the instruction at `index` failed with a specific error
+    ///
+    /// For batched transactions this pins the failure to one instruction. The error
+    /// is matched like [`assert_anchor_error`](Self::assert_anchor_error), against
+    /// both the error message and the logs.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Position of the failing instruction among the caller's
+    ///   instructions, see [`failed_instruction_index`](Self::failed_instruction_index)
+    /// * `expected_error` - The expected error name or message (substring match)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transaction succeeded, a different instruction failed, or the
+    /// error doesn't match
+    ///
+    /// # Returns
+    ///
+    /// Returns self for chaining
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let result = ctx.execute_instructions(vec![init_ix, deposit_ix], &[&user])?;
+    /// result.assert_error_at(1, "ConstraintOwner");
+    /// ```
+    pub fn assert_error_at(&self, index: u8, expected_error: &str) -> &Self {
+        self.assert_failure();
+
+        let actual_index = self.failed_instruction_index();
+        assert_eq!(
+            actual_index,
+            Some(index),
+            "Expected instruction {} to fail, but the failing instruction was {:?}.\nError: {:?}\nLogs:\n{}",
+            index,
+            actual_index,
+            self.error_report(),
+            self.log_report()
+        );
+
+        let found_in_error = self
+            .error
+            .as_ref()
+            .is_some_and(|e| e.contains(expected_error));
+        assert!(
+            found_in_error || self.has_log(expected_error),
+            "Instruction {} failed, but not with '{}'.\nError: {:?}\nLogs:\n{}",
+            index,
+            expected_error,
+            self.error_report(),
+            self.log_report()
         );
         self
     }
@@ -338,7 +1035,7 @@ impl TransactionResult {
             self.has_log(error_message),
             "Expected error message '{}' not found in logs.\nLogs:\n{}",
             error_message,
-            self.logs().join("\n")
+            self.log_report()
         );
         self
     }
@@ -356,15 +1053,362 @@ impl fmt::Debug for TransactionResult {
     }
 }
 
-/// Transaction helper methods for LiteSVM
-pub trait TransactionHelpers {
-    /// Send a single instruction and return a wrapped result
-    ///
-    /// # Example
-    /// ```no_run
-    /// # use litesvm_utils::TransactionHelpers;
-    /// # use litesvm::LiteSVM;
-    /// # use solana_program::instruction::Instruction;
+/// One-line summary, e.g. `make: success, 5231 CU, 1 event(s)`
+impl fmt::Display for TransactionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.instruction_name.as_deref().unwrap_or("transaction"))?;
+        match &self.error {
+            None => write!(f, "success")?,
+            Some(error) => write!(f, "failed ({})", error)?,
+        }
+        write!(
+            f,
+            ", {} CU, {} event(s)",
+            self.compute_units(),
+            self.event_count()
+        )
+    }
+}
+
+/// Named compute unit ceilings for a test suite
+///
+/// Keeps every CU budget in one place instead of scattering magic numbers
+/// across tests.
+///
+/// # Example
+///
+/// ```ignore
+/// fn budgets() -> CuBudgets {
+///     CuBudgets::new().with("make", 60_000).with("take", 180_000)
+/// }
+///
+/// result.assert_cu_budget(&budgets(), "take");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CuBudgets {
+    budgets: HashMap<String, u64>,
+}
+
+impl CuBudgets {
+    /// Create an empty budget table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the compute unit ceiling for an instruction name
+    pub fn with(mut self, name: &str, max: u64) -> Self {
+        self.budgets.insert(name.to_string(), max);
+        self
+    }
+
+    /// Get the ceiling registered for an instruction name
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.budgets.get(name).copied()
+    }
+}
+
+/// Compute budget instructions to prepend to a transaction
+///
+/// Transactions get 200k compute units per instruction by default; programs that
+/// need more fail with `ComputationalBudgetExceeded` unless they request it.
+///
+/// # Example
+///
+/// ```ignore
+/// let budget = ComputeBudget::new()
+///     .with_compute_unit_limit(400_000)
+///     .with_compute_unit_price(1_000);
+/// svm.send_instruction_with_budget(ix, &[&user], &budget)?;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComputeBudget {
+    unit_limit: Option<u32>,
+    unit_price: Option<u64>,
+}
+
+impl ComputeBudget {
+    /// Create a budget that requests nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request a compute unit limit for the whole transaction
+    pub fn with_compute_unit_limit(mut self, units: u32) -> Self {
+        self.unit_limit = Some(units);
+        self
+    }
+
+    /// Set the priority fee, in micro-lamports per compute unit
+    ///
+    /// LiteSVM doesn't charge priority fees, but the instruction is still
+    /// processed, so tests see the same instruction layout as on a cluster.
+    pub fn with_compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// The ComputeBudget program instructions for this budget
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let limit = self
+            .unit_limit
+            .map(ComputeBudgetInstruction::set_compute_unit_limit);
+        let price = self
+            .unit_price
+            .map(ComputeBudgetInstruction::set_compute_unit_price);
+        limit.into_iter().chain(price).collect()
+    }
+
+    /// `instructions` preceded by this budget's instructions
+    pub fn prepend_to(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let mut all = self.instructions();
+        all.extend_from_slice(instructions);
+        all
+    }
+}
+
+/// Set the compute budget the helpers prepend to every transaction they build on `svm`
+///
+/// Applies to this VM only. Transactions that already contain a ComputeBudget
+/// instruction, such as those sent with
+/// [`send_instruction_with_budget`](TransactionHelpers::send_instruction_with_budget),
+/// are left as they are. `None` turns it off again.
+pub fn set_default_compute_budget(svm: &mut LiteSVM, budget: Option<ComputeBudget>) {
+    vm_state_mut(svm, |state| state.default_compute_budget = budget);
+}
+
+/// The default compute budget of `svm`, if any
+pub fn default_compute_budget(svm: &LiteSVM) -> Option<ComputeBudget> {
+    vm_state(svm, |state| state.default_compute_budget)
+}
+
+/// `instructions` preceded by `budget`, unless they set their own
+///
+/// A transaction may only hold one instruction of each ComputeBudget kind, so a
+/// caller's own ComputeBudget instruction always wins over `budget`.
+pub fn with_compute_budget(
+    budget: Option<ComputeBudget>,
+    instructions: &[Instruction],
+) -> Vec<Instruction> {
+    let requests_budget = instructions
+        .iter()
+        .any(|ix| ix.program_id == solana_sdk::compute_budget::ID);
+    match budget {
+        Some(budget) if !requests_budget => budget.prepend_to(instructions),
+        _ => instructions.to_vec(),
+    }
+}
+
+/// `instructions` preceded by the default compute budget of `svm`, unless they set
+/// their own
+pub(crate) fn with_default_compute_budget(
+    svm: &LiteSVM,
+    instructions: &[Instruction],
+) -> Vec<Instruction> {
+    with_compute_budget(default_compute_budget(svm), instructions)
+}
+
+/// Sign `transaction` with `signers`, keeping the signatures it already has
+///
+/// # Errors
+///
+/// Fails if a keypair isn't one of the transaction's required signers
+pub fn add_signatures(
+    transaction: &mut Transaction,
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let blockhash = transaction.message.recent_blockhash;
+    transaction
+        .try_partial_sign(signers, blockhash)
+        .map_err(|e| TransactionError::BuildError(format!("Failed to sign transaction: {}", e)))
+}
+
+/// Required signers of `transaction` that haven't signed it yet, in message order
+pub fn missing_signers(transaction: &Transaction) -> Vec<Pubkey> {
+    let required = transaction.message.header.num_required_signatures as usize;
+    transaction.message.account_keys[..required]
+        .iter()
+        .zip(&transaction.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(pubkey, _)| *pubkey)
+        .collect()
+}
+
+/// Largest serialized transaction a cluster accepts, in bytes
+pub const MAX_TRANSACTION_SIZE: usize = solana_sdk::packet::PACKET_DATA_SIZE;
+
+/// Serialized size of the transaction the helpers would send for `instructions`
+///
+/// The first signer pays, as in [`TransactionHelpers::send_instructions`]. A VM's
+/// default compute budget isn't known here; pass `budget.prepend_to(instructions)`
+/// to count it. Signatures are fixed-size, so the size doesn't depend on the
+/// blockhash or on signing.
+///
+/// # Errors
+///
+/// Fails if no signers are provided or the transaction can't be serialized
+pub fn transaction_size(
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<usize, TransactionError> {
+    let payer = signers
+        .first()
+        .ok_or_else(|| TransactionError::BuildError("No signers provided".to_string()))?;
+    let tx = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+    bincode::serialized_size(&tx)
+        .map(|size| size as usize)
+        .map_err(|e| TransactionError::BuildError(e.to_string()))
+}
+
+/// Assert that the transaction built from `instructions` fits in a packet
+///
+/// Returns the serialized size, so tests can also keep an eye on the headroom.
+///
+/// # Panics
+///
+/// Panics if the transaction is larger than [`MAX_TRANSACTION_SIZE`], reporting
+/// its size and what it's made of
+///
+/// # Example
+///
+/// ```ignore
+/// let size = assert_tx_fits(&[create_ix, deposit_ix], &[&user]);
+/// println!("{} bytes to spare", MAX_TRANSACTION_SIZE - size);
+/// ```
+#[track_caller]
+pub fn assert_tx_fits(instructions: &[Instruction], signers: &[&Keypair]) -> usize {
+    let size = transaction_size(instructions, signers)
+        .unwrap_or_else(|e| panic!("Failed to build transaction: {}", e));
+    if size > MAX_TRANSACTION_SIZE {
+        let payer = signers[0].pubkey();
+        let message = Message::new(instructions, Some(&payer));
+        panic!(
+            "Transaction is {} bytes, {} over the {}-byte packet limit ({} instructions, {} accounts, {} signatures)",
+            size,
+            size - MAX_TRANSACTION_SIZE,
+            MAX_TRANSACTION_SIZE,
+            message.instructions.len(),
+            message.account_keys.len(),
+            message.header.num_required_signatures
+        );
+    }
+    size
+}
+
+/// Compute units used by one program invocation
+///
+/// Returned by [`TransactionResult::compute_units_per_instruction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionComputeUnits {
+    /// Index of the top-level instruction this invocation belongs to
+    pub instruction_index: usize,
+    /// The invoked program
+    pub program_id: Pubkey,
+    /// Invocation depth: 1 for a top-level instruction, 2 and up for CPIs
+    pub depth: usize,
+    /// Compute units consumed, including nested CPIs; `None` for builtins
+    pub compute_units: Option<u64>,
+}
+
+/// Results of a batch sent with [`TransactionHelpers::send_transactions`]
+///
+/// Displays as a one-line summary, e.g. `10 transactions: 9 succeeded, 1 failed, 52310 CU`.
+#[derive(Debug, Clone, Default)]
+pub struct BatchResult {
+    results: Vec<TransactionResult>,
+}
+
+impl BatchResult {
+    /// Every transaction's result, in the order they were sent
+    pub fn results(&self) -> &[TransactionResult] {
+        &self.results
+    }
+
+    /// Take the results out of the batch
+    pub fn into_results(self) -> Vec<TransactionResult> {
+        self.results
+    }
+
+    /// Number of transactions in the batch
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether the batch was empty
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Number of transactions that succeeded
+    pub fn success_count(&self) -> usize {
+        self.results.iter().filter(|r| r.is_success()).count()
+    }
+
+    /// Number of transactions that failed
+    pub fn failure_count(&self) -> usize {
+        self.len() - self.success_count()
+    }
+
+    /// The failed transactions with their position in the batch
+    pub fn failures(&self) -> Vec<(usize, &TransactionResult)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter(|(_, result)| !result.is_success())
+            .collect()
+    }
+
+    /// Compute units consumed by the whole batch
+    pub fn total_compute_units(&self) -> u64 {
+        self.results.iter().map(|r| r.compute_units()).sum()
+    }
+
+    /// Assert that every transaction in the batch succeeded
+    ///
+    /// # Panics
+    ///
+    /// Panics listing the position and error of each failed transaction
+    pub fn assert_all_success(&self) -> &Self {
+        let failures = self.failures();
+        if !failures.is_empty() {
+            let lines: Vec<String> = failures
+                .iter()
+                .map(|(index, result)| {
+                    format!("[{}] {}", index, result.error().map_or("", String::as_str))
+                })
+                .collect();
+            panic!(
+                "{} of {} transactions failed:\n  {}",
+                failures.len(),
+                self.len(),
+                lines.join("\n  ")
+            );
+        }
+        self
+    }
+}
+
+impl fmt::Display for BatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} transactions: {} succeeded, {} failed, {} CU",
+            self.len(),
+            self.success_count(),
+            self.failure_count(),
+            self.total_compute_units()
+        )
+    }
+}
+
+/// Transaction helper methods for LiteSVM
+pub trait TransactionHelpers {
+    /// Send a single instruction and return a wrapped result
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
     /// # use solana_sdk::signature::Keypair;
     /// # let mut svm = LiteSVM::new();
     /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
@@ -399,6 +1443,180 @@ pub trait TransactionHelpers {
         signers: &[&Keypair],
     ) -> Result<TransactionResult, TransactionError>;
 
+    /// Send a single instruction with `payer` paying the fees
+    ///
+    /// [`send_instruction`](Self::send_instruction) always charges `signers[0]`.
+    /// Use this for sponsored-fee flows, where the payer is not the signer the
+    /// instruction naturally names first. `payer` signs as well and may also
+    /// appear in `signers`, which may be empty.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let (sponsor, user) = (Keypair::new(), Keypair::new());
+    /// let result = svm.send_instruction_with_payer(ix, &sponsor, &[&user]).unwrap();
+    /// result.assert_success();
+    /// ```
+    fn send_instruction_with_payer(
+        &mut self,
+        instruction: Instruction,
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError> {
+        self.send_instructions_with_payer(&[instruction], payer, signers)
+    }
+
+    /// Send multiple instructions with `payer` paying the fees
+    ///
+    /// See [`send_instruction_with_payer`](Self::send_instruction_with_payer).
+    fn send_instructions_with_payer(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Send many transactions one after another
+    ///
+    /// Each entry is a transaction's instructions and signers, the first signer
+    /// paying. A failed transaction doesn't stop the batch; check the outcome with
+    /// the returned [`BatchResult`]. A fresh blockhash is issued between
+    /// transactions, so a batch that repeats an identical transaction runs it every
+    /// time instead of failing the repeats as `AlreadyProcessed`.
+    ///
+    /// # Errors
+    ///
+    /// Fails at the first transaction that can't be built, e.g. one without signers
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let users = [Keypair::new(), Keypair::new()];
+    /// let batch = svm
+    ///     .send_transactions(users.iter().map(|user| (vec![ix.clone()], vec![user])).collect())
+    ///     .unwrap();
+    /// batch.assert_all_success();
+    /// println!("{}", batch);
+    /// ```
+    fn send_transactions(
+        &mut self,
+        transactions: Vec<(Vec<Instruction>, Vec<&Keypair>)>,
+    ) -> Result<BatchResult, TransactionError>;
+
+    /// Start a [`TransactionBuilder`] for a multi-instruction transaction
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix1 = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let ix2 = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let (payer, user) = (Keypair::new(), Keypair::new());
+    /// let result = svm
+    ///     .transaction()
+    ///     .add(ix1)
+    ///     .add(ix2)
+    ///     .payer(&payer)
+    ///     .signers(&[&user])
+    ///     .send()
+    ///     .unwrap();
+    /// result.assert_success();
+    /// ```
+    fn transaction(&mut self) -> TransactionBuilder<'_, Self> {
+        TransactionBuilder::new(self)
+    }
+
+    /// Send a single instruction with compute budget instructions prepended
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::{ComputeBudget, TransactionHelpers};
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let signer = Keypair::new();
+    /// let budget = ComputeBudget::new().with_compute_unit_limit(1_000_000);
+    /// svm.send_instruction_with_budget(ix, &[&signer], &budget).unwrap();
+    /// ```
+    fn send_instruction_with_budget(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+        budget: &ComputeBudget,
+    ) -> Result<TransactionResult, TransactionError> {
+        self.send_instructions_with_budget(&[instruction], signers, budget)
+    }
+
+    /// Send multiple instructions with compute budget instructions prepended
+    ///
+    /// See [`send_instruction_with_budget`](Self::send_instruction_with_budget).
+    fn send_instructions_with_budget(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+        budget: &ComputeBudget,
+    ) -> Result<TransactionResult, TransactionError> {
+        let result = self.send_instructions(&budget.prepend_to(instructions), signers)?;
+        let prepended = result.prepended_instructions() + budget.instructions().len();
+        Ok(result.with_prepended_instructions(prepended))
+    }
+
+    /// Execute a single instruction without committing any state changes
+    ///
+    /// The result reports logs, compute units, return data and errors exactly as a
+    /// real send would, but accounts, balances and transaction history are left
+    /// untouched. Use it to preview CU usage or failures before sending.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::Keypair;
+    /// # let svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let signer = Keypair::new();
+    /// let preview = svm.simulate_instruction(ix, &[&signer]).unwrap();
+    /// println!("Would use {} compute units", preview.compute_units());
+    /// ```
+    fn simulate_instruction(
+        &self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Execute multiple instructions in one transaction without committing anything
+    ///
+    /// See [`simulate_instruction`](Self::simulate_instruction).
+    fn simulate_instructions(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Execute a signed transaction without committing anything
+    ///
+    /// See [`simulate_instruction`](Self::simulate_instruction).
+    fn simulate_transaction_result(
+        &self,
+        transaction: Transaction,
+    ) -> Result<TransactionResult, TransactionError>;
+
     /// Send a transaction and return a wrapped result
     ///
     /// # Example
@@ -424,6 +1642,143 @@ pub trait TransactionHelpers {
         &mut self,
         transaction: Transaction,
     ) -> Result<TransactionResult, TransactionError>;
+
+    /// Send a versioned transaction and return a wrapped result
+    ///
+    /// Use it for v0 transactions that load accounts through address lookup tables.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::message::{v0, VersionedMessage};
+    /// # use solana_sdk::signature::{Keypair, Signer};
+    /// # use solana_sdk::transaction::VersionedTransaction;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let signer = Keypair::new();
+    /// let message = v0::Message::try_compile(&signer.pubkey(), &[ix], &[], svm.latest_blockhash())
+    ///     .unwrap();
+    /// let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[&signer]).unwrap();
+    /// svm.send_versioned_transaction_result(tx).unwrap().assert_success();
+    /// ```
+    fn send_versioned_transaction_result(
+        &mut self,
+        transaction: VersionedTransaction,
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Deserialize and send a wire-format transaction
+    ///
+    /// Accepts both legacy and versioned transactions, e.g. ones built by the JS SDK
+    /// or a wallet simulator, and returns the same wrapped result as the other helpers.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # let mut svm = LiteSVM::new();
+    /// let bytes = std::fs::read("fixtures/swap_tx.bin").unwrap();
+    /// svm.send_serialized_transaction(&bytes).unwrap().assert_success();
+    /// ```
+    fn send_serialized_transaction(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Send a transaction with signature verification disabled for it
+    ///
+    /// Missing signatures are filled with unique placeholders, so the transaction
+    /// may declare signers (e.g. PDAs) whose keypairs don't exist. The VM's
+    /// sigverify setting is restored afterwards.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_sdk::transaction::Transaction;
+    /// # use solana_program::instruction::Instruction;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let payer = solana_program::pubkey::Pubkey::new_unique();
+    /// let tx = Transaction::new_with_payer(&[ix], Some(&payer));
+    /// svm.send_unchecked(tx).unwrap().assert_success();
+    /// ```
+    fn send_unchecked(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Send a single instruction without any signatures
+    ///
+    /// Useful for isolating program logic from signing, e.g. for instructions whose
+    /// signers are program-derived addresses. `payer` still pays the fees.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let payer = solana_program::pubkey::Pubkey::new_unique();
+    /// svm.send_instruction_unsigned(ix, &payer).unwrap().assert_success();
+    /// ```
+    fn send_instruction_unsigned(
+        &mut self,
+        instruction: Instruction,
+        payer: &Pubkey,
+    ) -> Result<TransactionResult, TransactionError>;
+
+    /// Build a transaction signed only by `signers`, for others to sign later
+    ///
+    /// `payer` pays the fees and needn't be among `signers`. The transaction uses
+    /// the latest blockhash and the default compute budget, like the other helpers.
+    /// Complete it with [`add_signatures`] and send it with
+    /// [`send_transaction_result`](Self::send_transaction_result).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::{add_signatures, missing_signers, TransactionHelpers};
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_sdk::signature::{Keypair, Signer};
+    /// # let mut svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let (maker, taker) = (Keypair::new(), Keypair::new());
+    /// let mut tx = svm
+    ///     .partially_signed_transaction(&[ix], &taker.pubkey(), &[&maker])
+    ///     .unwrap();
+    /// assert_eq!(missing_signers(&tx), vec![taker.pubkey()]);
+    /// add_signatures(&mut tx, &[&taker]).unwrap();
+    /// svm.send_transaction_result(tx).unwrap().assert_success();
+    /// ```
+    fn partially_signed_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &[&Keypair],
+    ) -> Result<Transaction, TransactionError>;
+
+    /// Estimate the fee LiteSVM will charge the fee payer for `message`
+    ///
+    /// Every transaction signature and every precompile (ed25519, secp256k1,
+    /// secp256r1) signature costs [`LAMPORTS_PER_SIGNATURE`]. LiteSVM charges no
+    /// prioritization fee, so compute budget prices don't change the result.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use litesvm_utils::TransactionHelpers;
+    /// # use litesvm::LiteSVM;
+    /// # use solana_program::instruction::Instruction;
+    /// # use solana_program::message::Message;
+    /// # let svm = LiteSVM::new();
+    /// # let ix = Instruction::new_with_bytes(solana_program::pubkey::Pubkey::new_unique(), &[], vec![]);
+    /// # let payer = solana_program::pubkey::Pubkey::new_unique();
+    /// let message = Message::new(&[ix], Some(&payer));
+    /// let fee = svm.estimate_fee(&message);
+    /// ```
+    fn estimate_fee(&self, message: &Message) -> u64;
 }
 
 impl TransactionHelpers for LiteSVM {
@@ -436,18 +1791,84 @@ impl TransactionHelpers for LiteSVM {
             return Err(TransactionError::BuildError("No signers provided".to_string()));
         }
 
+        let instructions = with_default_compute_budget(self, &[instruction]);
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.latest_blockhash(),
+        );
+
+        let result = self.send_transaction_result(tx)?;
+        Ok(result.with_prepended_instructions(instructions.len() - 1))
+    }
+
+    fn send_instructions(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError> {
+        if signers.is_empty() {
+            return Err(TransactionError::BuildError("No signers provided".to_string()));
+        }
+
+        let all_instructions = with_default_compute_budget(self, instructions);
         let tx = Transaction::new_signed_with_payer(
-            &[instruction],
+            &all_instructions,
             Some(&signers[0].pubkey()),
             signers,
             self.latest_blockhash(),
         );
 
-        self.send_transaction_result(tx)
+        let result = self.send_transaction_result(tx)?;
+        Ok(result.with_prepended_instructions(all_instructions.len() - instructions.len()))
     }
 
-    fn send_instructions(
+    fn send_instructions_with_payer(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError> {
+        let mut all_signers = vec![payer];
+        all_signers.extend(signers.iter().filter(|s| s.pubkey() != payer.pubkey()));
+
+        let all_instructions = with_default_compute_budget(self, instructions);
+        let tx = Transaction::new_signed_with_payer(
+            &all_instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.latest_blockhash(),
+        );
+
+        let result = self.send_transaction_result(tx)?;
+        Ok(result.with_prepended_instructions(all_instructions.len() - instructions.len()))
+    }
+
+    fn send_transactions(
         &mut self,
+        transactions: Vec<(Vec<Instruction>, Vec<&Keypair>)>,
+    ) -> Result<BatchResult, TransactionError> {
+        let mut results = Vec::with_capacity(transactions.len());
+        for (i, (instructions, signers)) in transactions.into_iter().enumerate() {
+            if i > 0 {
+                self.expire_blockhash();
+            }
+            results.push(self.send_instructions(&instructions, &signers)?);
+        }
+        Ok(BatchResult { results })
+    }
+
+    fn simulate_instruction(
+        &self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionResult, TransactionError> {
+        self.simulate_instructions(&[instruction], signers)
+    }
+
+    fn simulate_instructions(
+        &self,
         instructions: &[Instruction],
         signers: &[&Keypair],
     ) -> Result<TransactionResult, TransactionError> {
@@ -455,32 +1876,147 @@ impl TransactionHelpers for LiteSVM {
             return Err(TransactionError::BuildError("No signers provided".to_string()));
         }
 
+        let all_instructions = with_default_compute_budget(self, instructions);
         let tx = Transaction::new_signed_with_payer(
-            instructions,
+            &all_instructions,
             Some(&signers[0].pubkey()),
             signers,
             self.latest_blockhash(),
         );
 
-        self.send_transaction_result(tx)
+        let result = self.simulate_transaction_result(tx)?;
+        Ok(result.with_prepended_instructions(all_instructions.len() - instructions.len()))
+    }
+
+    fn simulate_transaction_result(
+        &self,
+        transaction: Transaction,
+    ) -> Result<TransactionResult, TransactionError> {
+        let result = match self.simulate_transaction(transaction.clone()) {
+            Ok(simulated) => TransactionResult::new(simulated.meta, None),
+            Err(failed) => {
+                TransactionResult::new_failed(format!("{:?}", failed.err), failed.meta, None)
+            }
+        };
+        Ok(result.with_transaction(&transaction))
     }
 
     fn send_transaction_result(
         &mut self,
         transaction: Transaction,
     ) -> Result<TransactionResult, TransactionError> {
-        match self.send_transaction(transaction) {
-            Ok(result) => Ok(TransactionResult::new(result, None)),
+        track_accounts(self, transaction.message.account_keys.iter().copied());
+        let message = VersionedMessage::Legacy(transaction.message.clone());
+        let before = capture_writable_accounts(self, &message);
+        let result = match self.send_transaction(transaction.clone()) {
+            Ok(result) => TransactionResult::new(result, None),
             Err(failed) => {
                 // Return a failed transaction result with metadata
-                Ok(TransactionResult::new_failed(
-                    format!("{:?}", failed.err),
-                    failed.meta,
-                    None,
-                ))
+                TransactionResult::new_failed(format!("{:?}", failed.err), failed.meta, None)
+            }
+        }
+        .with_transaction(&transaction)
+        .with_account_diffs(diff_accounts(self, before));
+        capture_transaction(&result);
+        Ok(result)
+    }
+
+    fn send_serialized_transaction(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<TransactionResult, TransactionError> {
+        let transaction: VersionedTransaction = bincode::deserialize(bytes).map_err(|e| {
+            TransactionError::BuildError(format!("Failed to deserialize transaction: {}", e))
+        })?;
+        self.send_versioned_transaction_result(transaction)
+    }
+
+    fn send_versioned_transaction_result(
+        &mut self,
+        transaction: VersionedTransaction,
+    ) -> Result<TransactionResult, TransactionError> {
+        let keys = message_account_keys(self, &transaction.message);
+        track_accounts(self, keys);
+        let before = capture_writable_accounts(self, &transaction.message);
+        let result = match self.send_transaction(transaction.clone()) {
+            Ok(result) => TransactionResult::new(result, None),
+            Err(failed) => {
+                TransactionResult::new_failed(format!("{:?}", failed.err), failed.meta, None)
             }
         }
+        .with_versioned_transaction(&transaction)
+        .with_account_diffs(diff_accounts(self, before));
+        capture_transaction(&result);
+        Ok(result)
+    }
+
+    fn send_unchecked(
+        &mut self,
+        mut transaction: Transaction,
+    ) -> Result<TransactionResult, TransactionError> {
+        // Placeholders must be unique, otherwise the history rejects repeats
+        for signature in transaction.signatures.iter_mut() {
+            if *signature == Signature::default() {
+                *signature = Signature::new_unique();
+            }
+        }
+
+        let sigverify = self.get_sigverify();
+        *self = std::mem::take(self).with_sigverify(false);
+        let result = self.send_transaction_result(transaction);
+        *self = std::mem::take(self).with_sigverify(sigverify);
+        result
+    }
+
+    fn send_instruction_unsigned(
+        &mut self,
+        instruction: Instruction,
+        payer: &Pubkey,
+    ) -> Result<TransactionResult, TransactionError> {
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(payer));
+        tx.message.recent_blockhash = self.latest_blockhash();
+        self.send_unchecked(tx)
+    }
+
+    fn partially_signed_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &[&Keypair],
+    ) -> Result<Transaction, TransactionError> {
+        let instructions = with_default_compute_budget(self, instructions);
+        let mut tx = Transaction::new_with_payer(&instructions, Some(payer));
+        tx.message.recent_blockhash = self.latest_blockhash();
+        add_signatures(&mut tx, signers)?;
+        Ok(tx)
     }
+
+    fn estimate_fee(&self, message: &Message) -> u64 {
+        signature_fee(
+            message.header.num_required_signatures,
+            &message.account_keys,
+            &message.instructions,
+        )
+    }
+}
+
+/// The fee LiteSVM charges for a message's transaction and precompile signatures
+pub(crate) fn signature_fee(
+    num_required_signatures: u8,
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+) -> u64 {
+    let precompile_signatures: u64 = instructions
+        .iter()
+        .filter(|ix| {
+            account_keys
+                .get(ix.program_id_index as usize)
+                .is_some_and(|program_id| PRECOMPILE_PROGRAMS.contains(program_id))
+        })
+        .map(|ix| u64::from(ix.data.first().copied().unwrap_or(0)))
+        .sum();
+
+    (u64::from(num_required_signatures) + precompile_signatures) * LAMPORTS_PER_SIGNATURE
 }
 
 #[cfg(test)]
@@ -532,6 +2068,35 @@ mod tests {
         assert!(log.is_some());
     }
 
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_transaction_result_logs_for() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&payer, 6).unwrap();
+
+        // The ATA program invokes the token and system programs via CPI
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            &mint.pubkey(),
+            &spl_token::id(),
+        );
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+        result.assert_success();
+
+        let ata_logs = result.logs_for(&spl_associated_token_account::id());
+        assert!(ata_logs.iter().any(|log| log.contains("Program log: Create")));
+        assert!(!ata_logs.iter().any(|log| log.contains("GetAccountDataSize")));
+        assert!(ata_logs.last().unwrap().ends_with("success"));
+
+        let token_logs = result.logs_for(&spl_token::id());
+        assert!(token_logs.iter().any(|log| log.contains("GetAccountDataSize")));
+        assert!(!token_logs.iter().any(|log| log.contains("Program log: Create")));
+
+        assert!(result.logs_for(&Pubkey::new_unique()).is_empty());
+    }
+
     #[test]
     fn test_transaction_result_compute_units() {
         let mut svm = LiteSVM::new();
@@ -547,6 +2112,71 @@ mod tests {
         assert!(cu < 1_000_000); // Should be reasonable
     }
 
+    #[test]
+    fn test_transaction_result_assert_cu_under() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+
+        result.assert_cu_under("transfer", 200_000);
+
+        let budgets = CuBudgets::new().with("transfer", 200_000);
+        result.assert_cu_budget(&budgets, "transfer");
+    }
+
+    #[test]
+    #[should_panic(expected = "Instruction 'transfer' exceeded its compute budget")]
+    fn test_transaction_result_assert_cu_under_fails() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+
+        result.assert_cu_under("transfer", 1);
+    }
+
+    #[test]
+    fn test_transaction_result_assert_compute_units_range() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+        let used = result.compute_units();
+
+        result
+            .assert_compute_units_under(used + 1)
+            .assert_compute_units_between(used, used)
+            .assert_compute_units_between(1, 200_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected between 1000000 and 2000000 CU")]
+    fn test_transaction_result_assert_compute_units_between_fails() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+        // No transaction can use this much, whatever a transfer costs
+        result.assert_compute_units_between(1_000_000, 2_000_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "No compute budget registered for 'take'")]
+    fn test_transaction_result_assert_cu_budget_missing() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Keypair::new().pubkey(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+
+        result.assert_cu_budget(&CuBudgets::new(), "take");
+    }
+
     #[test]
     fn test_transaction_result_logs() {
         let mut svm = LiteSVM::new();
@@ -574,6 +2204,104 @@ mod tests {
         assert!(_inner.compute_units_consumed > 0);
     }
 
+    #[test]
+    fn test_transaction_result_return_data() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+        assert!(result.return_data().is_empty());
+        assert_eq!(result.return_data_program(), None);
+
+        let program_id = Pubkey::new_unique();
+        let meta = TransactionMetadata {
+            return_data: solana_sdk::transaction_context::TransactionReturnData {
+                program_id,
+                data: 42u64.to_le_bytes().to_vec(),
+            },
+            ..Default::default()
+        };
+        let result = TransactionResult::new(meta, None);
+        assert_eq!(result.return_data(), 42u64.to_le_bytes());
+        assert_eq!(result.return_data_program(), Some(program_id));
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    fn test_transaction_result_cpis() {
+        use spl_associated_token_account::instruction::create_associated_token_account;
+
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let mint = svm.create_token_mint(&payer, 6).unwrap();
+        let ix = create_associated_token_account(
+            &payer.pubkey(),
+            &payer.pubkey(),
+            &mint.pubkey(),
+            &spl_token::id(),
+        );
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+
+        assert_eq!(result.inner_instructions().len(), 1);
+        result
+            .assert_cpi_to(&spl_token::id())
+            .assert_cpi_to(&solana_program::system_program::id())
+            .assert_cpi_count(&solana_program::system_program::id(), 1);
+        assert_eq!(result.cpi_count(&Pubkey::new_unique()), 0);
+    }
+
+    #[cfg(feature = "spl")]
+    #[test]
+    #[should_panic(expected = "Expected a CPI to")]
+    fn test_transaction_result_assert_cpi_to_fails() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000_000);
+        let result = svm.send_instruction(ix, &[&payer]).unwrap();
+        result.assert_cpi_to(&spl_token::id());
+    }
+
+    #[test]
+    fn test_compute_units_per_instruction() {
+        let program = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        let system = solana_program::system_program::id();
+        let logs = [
+            format!("Program {} invoke [1]", program),
+            "Program log: Instruction: Deposit".to_string(),
+            format!("Program {} invoke [2]", token),
+            format!("Program {} consumed 4645 of 190000 compute units", token),
+            format!("Program {} success", token),
+            format!("Program {} invoke [2]", system),
+            format!("Program {} success", system),
+            format!("Program {} consumed 15000 of 200000 compute units", program),
+            format!("Program {} success", program),
+            format!("Program {} invoke [1]", program),
+            format!("Program {} consumed 900 of 185000 compute units", program),
+            format!("Program {} failed: custom program error: 0x1", program),
+        ];
+        let meta = TransactionMetadata {
+            logs: logs.to_vec(),
+            ..Default::default()
+        };
+        let result = TransactionResult::new(meta, None);
+
+        let entries = result.compute_units_per_instruction();
+        let summary: Vec<(usize, Pubkey, usize, Option<u64>)> = entries
+            .iter()
+            .map(|e| (e.instruction_index, e.program_id, e.depth, e.compute_units))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, program, 1, Some(15000)),
+                (0, token, 2, Some(4645)),
+                (0, system, 2, None),
+                (1, program, 1, Some(900)),
+            ]
+        );
+    }
+
     #[test]
     fn test_transaction_result_failure() {
         let mut svm = LiteSVM::new();
@@ -658,6 +2386,173 @@ mod tests {
         assert_eq!(balance2, 2_000_000);
     }
 
+    #[test]
+    fn test_simulate_instruction() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let recipient = Pubkey::new_unique();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000);
+        let preview = svm.simulate_instruction(ix.clone(), &[&payer]).unwrap();
+        preview.assert_success();
+        assert!(preview.compute_units() > 0);
+        assert_eq!(svm.get_balance(&recipient), None);
+        assert_eq!(svm.get_balance(&payer.pubkey()), Some(10_000_000_000));
+
+        let too_much = system_instruction::transfer(&payer.pubkey(), &recipient, u64::MAX);
+        svm.simulate_instructions(&[too_much], &[&payer])
+            .unwrap()
+            .assert_failure();
+
+        // Simulating doesn't record the transaction, so it can still be sent
+        svm.send_instruction(ix, &[&payer]).unwrap().assert_success();
+        assert_eq!(svm.get_balance(&recipient), Some(1_000_000));
+    }
+
+    #[test]
+    fn test_send_with_compute_budget() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let recipient = Pubkey::new_unique();
+        let ix = system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000);
+
+        let budget = ComputeBudget::new()
+            .with_compute_unit_limit(100_000)
+            .with_compute_unit_price(5);
+        assert_eq!(budget.instructions().len(), 2);
+        let result = svm
+            .send_instruction_with_budget(ix.clone(), &[&payer], &budget)
+            .unwrap();
+        result.assert_success();
+        assert_eq!(
+            result.log_count("ComputeBudget111111111111111111111111111111 invoke [1]"),
+            2
+        );
+
+        let starved = ComputeBudget::new().with_compute_unit_limit(10);
+        svm.send_instructions_with_budget(&[ix], &[&payer], &starved)
+            .unwrap()
+            .assert_failure();
+        assert_eq!(svm.get_balance(&recipient), Some(1_000_000));
+    }
+
+    #[test]
+    fn test_partially_signed_transaction() {
+        let mut svm = LiteSVM::new();
+        let maker = svm.create_funded_account(1_000_000_000).unwrap();
+        let taker = svm.create_funded_account(1_000_000_000).unwrap();
+        let ixs = [
+            system_instruction::transfer(&maker.pubkey(), &taker.pubkey(), 100_000_000),
+            system_instruction::transfer(&taker.pubkey(), &maker.pubkey(), 50_000_000),
+        ];
+
+        // The maker signs first and hands the transaction over as bytes
+        let tx = svm
+            .partially_signed_transaction(&ixs, &taker.pubkey(), &[&maker])
+            .unwrap();
+        let bytes = bincode::serialize(&tx).unwrap();
+        let mut tx: Transaction = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(missing_signers(&tx), vec![taker.pubkey()]);
+        svm.send_transaction_result(tx.clone()).unwrap().assert_failure();
+
+        assert!(add_signatures(&mut tx, &[&Keypair::new()]).is_err());
+        add_signatures(&mut tx, &[&taker]).unwrap();
+        assert!(missing_signers(&tx).is_empty());
+        svm.send_transaction_result(tx).unwrap().assert_success();
+        assert_eq!(svm.get_balance(&maker.pubkey()), Some(950_000_000));
+    }
+
+    #[test]
+    fn test_send_instruction_with_payer() {
+        let mut svm = LiteSVM::new();
+        let sponsor = svm.create_funded_account(1_000_000_000).unwrap();
+        let user = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&user.pubkey(), &Pubkey::new_unique(), 1_000_000);
+
+        let result = svm
+            .send_instruction_with_payer(ix, &sponsor, &[&user])
+            .unwrap();
+        result.assert_success();
+        assert_eq!(result.fee_payer(), Some(sponsor.pubkey()));
+        assert_eq!(svm.get_balance(&user.pubkey()), Some(999_000_000));
+        assert_eq!(svm.get_balance(&sponsor.pubkey()), Some(1_000_000_000 - 10_000));
+
+        // The payer may also be listed among the signers
+        let ix = system_instruction::transfer(&sponsor.pubkey(), &Pubkey::new_unique(), 1_000_000);
+        svm.send_instructions_with_payer(&[ix], &sponsor, &[&sponsor])
+            .unwrap()
+            .assert_success();
+    }
+
+    #[test]
+    fn test_send_transactions() {
+        let mut svm = LiteSVM::new();
+        let alice = svm.create_funded_account(1_000_000_000).unwrap();
+        let bob = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        let transfer = |from: &Keypair, lamports| {
+            vec![system_instruction::transfer(&from.pubkey(), &to, lamports)]
+        };
+
+        let batch = svm
+            .send_transactions(vec![
+                (transfer(&alice, 1_000_000), vec![&alice]),
+                (transfer(&bob, 2_000_000_000), vec![&bob]),
+                (transfer(&bob, 2_000_000), vec![&bob]),
+            ])
+            .unwrap();
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.success_count(), 2);
+        assert_eq!(batch.failures()[0].0, 1);
+        let total: u64 = batch.results().iter().map(|r| r.compute_units()).sum();
+        assert_eq!(batch.total_compute_units(), total);
+        assert_eq!(
+            batch.to_string(),
+            format!("3 transactions: 2 succeeded, 1 failed, {} CU", total)
+        );
+        assert_eq!(svm.get_balance(&to), Some(3_000_000));
+
+        // Identical transactions all run
+        let repeated = svm
+            .send_transactions(vec![(transfer(&alice, 1_000), vec![&alice]); 3])
+            .unwrap();
+        repeated.assert_all_success();
+        assert_eq!(svm.get_balance(&to), Some(3_003_000));
+
+        let panic = std::panic::catch_unwind(|| {
+            batch.assert_all_success();
+        });
+        assert!(panic.is_err());
+        assert!(svm.send_transactions(vec![(vec![], vec![])]).is_err());
+    }
+
+    #[test]
+    fn test_assert_tx_fits() {
+        let payer = Keypair::new();
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        let size = assert_tx_fits(std::slice::from_ref(&ix), &[&payer]);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        );
+        assert_eq!(size, bincode::serialize(&tx).unwrap().len());
+        assert!(transaction_size(&[], &[]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "over the 1232-byte packet limit (30 instructions, 32 accounts")]
+    fn test_assert_tx_fits_fails() {
+        let payer = Keypair::new();
+        let instructions: Vec<Instruction> = (0..30)
+            .map(|_| system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1))
+            .collect();
+        assert_tx_fits(&instructions, &[&payer]);
+    }
+
     #[test]
     fn test_send_instruction_no_signers() {
         let mut svm = LiteSVM::new();
@@ -734,4 +2629,256 @@ mod tests {
         let result = svm.send_transaction_result(tx).unwrap();
         result.assert_success();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_transaction_result_origin() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        let signature = tx.signatures[0];
+
+        let result = svm.send_transaction_result(tx).unwrap();
+        assert_eq!(result.signature(), signature);
+        assert_eq!(result.blockhash(), Some(svm.latest_blockhash()));
+        assert_eq!(result.fee_payer(), Some(payer.pubkey()));
+        assert!(svm.get_transaction(&result.signature()).is_some());
+    }
+
+    #[test]
+    fn test_send_serialized_transaction() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(10_000_000_000).unwrap();
+        let recipient = Pubkey::new_unique();
+
+        let ix = system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        let bytes = bincode::serialize(&tx).unwrap();
+
+        let result = svm.send_serialized_transaction(&bytes).unwrap();
+        result.assert_success();
+        assert_eq!(result.transaction_bytes(), Some(bytes.as_slice()));
+        assert_eq!(result.signature(), tx.signatures[0]);
+        assert_eq!(svm.get_balance(&recipient), Some(1_000_000));
+
+        assert!(matches!(
+            svm.send_serialized_transaction(&[1, 2, 3]),
+            Err(TransactionError::BuildError(_))
+        ));
+    }
+
+    #[test]
+    fn test_send_instruction_unsigned() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let recipient = Pubkey::new_unique();
+
+        // Nobody signs, yet the transfer goes through
+        for _ in 0..2 {
+            let ix = system_instruction::transfer(&from.pubkey(), &recipient, 1_000_000);
+            svm.send_instruction_unsigned(ix, &from.pubkey())
+                .unwrap()
+                .assert_success();
+        }
+
+        assert_eq!(svm.get_balance(&recipient), Some(2_000_000));
+        assert!(svm.get_sigverify());
+    }
+
+    #[test]
+    fn test_estimate_fee() {
+        let mut svm = LiteSVM::new();
+        let payer = svm.create_funded_account(1_000_000_000).unwrap();
+        let co_signer = svm.create_funded_account(1_000_000_000).unwrap();
+        let recipient = Pubkey::new_unique();
+
+        let ixs = [
+            system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000),
+            system_instruction::transfer(&co_signer.pubkey(), &recipient, 1_000_000),
+        ];
+        let message = Message::new(&ixs, Some(&payer.pubkey()));
+        let fee = svm.estimate_fee(&message);
+        assert_eq!(fee, 2 * LAMPORTS_PER_SIGNATURE);
+
+        let before = svm.get_balance(&payer.pubkey()).unwrap();
+        let tx = Transaction::new(&[&payer, &co_signer], message, svm.latest_blockhash());
+        svm.send_transaction_result(tx).unwrap().assert_success();
+        assert_eq!(
+            svm.get_balance(&payer.pubkey()).unwrap(),
+            before - 1_000_000 - fee
+        );
+
+        // Precompile signatures are charged too
+        let precompile_ix =
+            Instruction::new_with_bytes(solana_sdk::ed25519_program::ID, &[2, 0], vec![]);
+        let message = Message::new(&[precompile_ix], Some(&payer.pubkey()));
+        assert_eq!(svm.estimate_fee(&message), 3 * LAMPORTS_PER_SIGNATURE);
+    }
+
+    #[test]
+    fn test_transaction_result_assert_error_at() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        let result = svm
+            .send_instructions(
+                &[
+                    system_instruction::transfer(&from.pubkey(), &to, 1_000),
+                    system_instruction::transfer(&from.pubkey(), &to, 10_000_000_000),
+                ],
+                &[&from],
+            )
+            .unwrap();
+
+        assert_eq!(result.failed_instruction_index(), Some(1));
+        result.assert_error_at(1, "Custom(1)");
+    }
+
+    #[test]
+    fn test_transaction_result_assert_error_at_skips_prepended_budget() {
+        let mut svm = LiteSVM::new();
+        set_default_compute_budget(
+            &mut svm,
+            Some(ComputeBudget::new().with_compute_unit_limit(50_000)),
+        );
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        let result = svm
+            .send_instructions(
+                &[
+                    system_instruction::transfer(&from.pubkey(), &to, 1_000),
+                    system_instruction::transfer(&from.pubkey(), &to, 10_000_000_000),
+                ],
+                &[&from],
+            )
+            .unwrap();
+
+        // The runtime numbers the transfers 1 and 2, after the budget instruction
+        assert_eq!(result.prepended_instructions(), 1);
+        assert!(result.error().unwrap().starts_with("InstructionError(2,"));
+        assert_eq!(result.failed_instruction_index(), Some(1));
+        result.assert_error_at(1, "Custom(1)");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected instruction 0 to fail")]
+    fn test_transaction_result_assert_error_at_wrong_index() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        svm.send_instructions(
+            &[
+                system_instruction::transfer(&from.pubkey(), &to, 1_000),
+                system_instruction::transfer(&from.pubkey(), &to, 10_000_000_000),
+            ],
+            &[&from],
+        )
+        .unwrap()
+        .assert_error_at(0, "Custom(1)");
+    }
+
+    #[test]
+    fn test_transaction_result_display() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        let ok = svm
+            .send_instruction(system_instruction::transfer(&from.pubkey(), &to, 1_000), &[&from])
+            .unwrap();
+        assert_eq!(
+            ok.to_string(),
+            format!("transaction: success, {} CU, 0 event(s)", ok.compute_units())
+        );
+
+        let failed = svm
+            .send_instruction(
+                system_instruction::transfer(&from.pubkey(), &to, 10_000_000_000),
+                &[&from],
+            )
+            .unwrap();
+        assert!(failed.to_string().starts_with("transaction: failed (InstructionError(0, Custom(1)))"));
+        assert!(failed.to_pretty_string().contains("Error: InstructionError(0, Custom(1))"));
+    }
+
+    #[test]
+    fn test_transaction_result_error_names() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let ix = system_instruction::transfer(&from.pubkey(), &to, 10_000_000_000);
+        let failed = svm.send_instruction(ix, &[&from]).unwrap();
+        let system = solana_program::system_program::id();
+        assert_eq!(failed.custom_error(), Some((system, 1)));
+        assert_eq!(failed.custom_error_name(), None);
+
+        // Names registered for another program don't apply
+        let failed = failed
+            .with_error_names(Pubkey::new_unique(), [(1, "Unrelated".to_string())])
+            .with_error_names(system, [(1, "ResultWithNegativeLamports".to_string())]);
+        assert_eq!(failed.custom_error_name(), Some("ResultWithNegativeLamports"));
+        assert!(failed
+            .to_pretty_string()
+            .contains("Error: InstructionError(0, ResultWithNegativeLamports (1))"));
+        assert!(failed
+            .to_pretty_string()
+            .contains("failed: ResultWithNegativeLamports (1)"));
+        failed
+            .assert_anchor_error("SystemError::ResultWithNegativeLamports")
+            .assert_error("ResultWithNegativeLamports (1)");
+
+        let panic = std::panic::catch_unwind(|| {
+            failed.assert_success();
+        })
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.contains("InstructionError(0, ResultWithNegativeLamports (1))"));
+    }
+
+    #[test]
+    fn test_transaction_result_assert_log_count() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+
+        let result = svm
+            .send_instructions(
+                &[
+                    system_instruction::transfer(&from.pubkey(), &to, 1_000_000),
+                    system_instruction::transfer(&from.pubkey(), &to, 1_000_000),
+                ],
+                &[&from],
+            )
+            .unwrap();
+
+        result
+            .assert_log_count("invoke [1]", 2)
+            .assert_log_count("Unknown", 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "to appear 3 time(s) in the logs, found 1")]
+    fn test_transaction_result_assert_log_count_fails() {
+        let mut svm = LiteSVM::new();
+        let from = svm.create_funded_account(1_000_000_000).unwrap();
+        let ix = system_instruction::transfer(&from.pubkey(), &Pubkey::new_unique(), 1_000_000);
+
+        svm.send_instruction(ix, &[&from])
+            .unwrap()
+            .assert_log_count("invoke [1]", 3);
+    }
+}
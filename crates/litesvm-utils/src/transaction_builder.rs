@@ -0,0 +1,164 @@
+//! Fluent builder for multi-instruction transactions
+//!
+//! [`TransactionHelpers::transaction`] starts a [`TransactionBuilder`] that collects
+//! instructions from any source (SPL setup, system transfers, the program under
+//! test) and sends them as one atomic transaction.
+//!
+//! ```ignore
+//! let result = svm
+//!     .transaction()
+//!     .add(create_ata_ix)
+//!     .add(mint_to_ix)
+//!     .add(deposit_ix)
+//!     .payer(&sponsor)
+//!     .signers(&[&user, &mint_authority])
+//!     .send()?;
+//! ```
+
+use crate::transaction::{ComputeBudget, TransactionError, TransactionHelpers, TransactionResult};
+use solana_program::instruction::Instruction;
+use solana_sdk::signature::Keypair;
+
+/// Collects instructions and signers for one transaction
+///
+/// Created by [`TransactionHelpers::transaction`]. Without an explicit
+/// [`payer`](Self::payer), the first signer pays, as with
+/// [`send_instructions`](TransactionHelpers::send_instructions).
+#[must_use = "call `send` to execute the transaction"]
+pub struct TransactionBuilder<'a, S: TransactionHelpers + ?Sized> {
+    svm: &'a mut S,
+    instructions: Vec<Instruction>,
+    payer: Option<&'a Keypair>,
+    signers: Vec<&'a Keypair>,
+    budget: Option<ComputeBudget>,
+}
+
+impl<'a, S: TransactionHelpers + ?Sized> TransactionBuilder<'a, S> {
+    /// Start an empty transaction against `svm`
+    pub fn new(svm: &'a mut S) -> Self {
+        Self {
+            svm,
+            instructions: Vec::new(),
+            payer: None,
+            signers: Vec::new(),
+            budget: None,
+        }
+    }
+
+    /// Append an instruction
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Append several instructions, in order
+    pub fn add_all(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Pay the fees from `payer`, which signs too
+    pub fn payer(mut self, payer: &'a Keypair) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Add a signer
+    pub fn signer(mut self, signer: &'a Keypair) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// Add several signers
+    pub fn signers(mut self, signers: &[&'a Keypair]) -> Self {
+        self.signers.extend_from_slice(signers);
+        self
+    }
+
+    /// Prepend `budget`'s ComputeBudget instructions
+    pub fn with_compute_budget(mut self, budget: ComputeBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// The instructions collected so far, without the compute budget
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Sign and send the transaction
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is neither a payer nor a signer. A transaction that executes
+    /// and fails is an `Ok` result; check it with
+    /// [`assert_success`](TransactionResult::assert_success).
+    pub fn send(self) -> Result<TransactionResult, TransactionError> {
+        let budget_len = self.budget.map_or(0, |budget| budget.instructions().len());
+        let instructions = match &self.budget {
+            Some(budget) => budget.prepend_to(&self.instructions),
+            None => self.instructions,
+        };
+        let result = match self.payer {
+            Some(payer) => {
+                self.svm
+                    .send_instructions_with_payer(&instructions, payer, &self.signers)
+            }
+            None => self.svm.send_instructions(&instructions, &self.signers),
+        }?;
+        let prepended = result.prepended_instructions() + budget_len;
+        Ok(result.with_prepended_instructions(prepended))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::TestHelpers;
+    use crate::transaction::{ComputeBudget, TransactionHelpers};
+    use litesvm::LiteSVM;
+    use solana_program::pubkey::Pubkey;
+    use solana_program::system_instruction;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_transaction_builder() {
+        let mut svm = LiteSVM::new();
+        let sponsor = svm.create_funded_account(1_000_000_000).unwrap();
+        let alice = svm.create_funded_account(1_000_000_000).unwrap();
+        let bob = svm.create_funded_account(1_000_000_000).unwrap();
+        let to = Pubkey::new_unique();
+        let from_alice = system_instruction::transfer(&alice.pubkey(), &to, 1_000_000);
+        let from_bob = system_instruction::transfer(&bob.pubkey(), &to, 2_000_000);
+
+        let result = svm
+            .transaction()
+            .add(from_alice.clone())
+            .add_all([from_bob])
+            .payer(&sponsor)
+            .signers(&[&alice, &bob])
+            .with_compute_budget(ComputeBudget::new().with_compute_unit_limit(100_000))
+            .send()
+            .unwrap();
+        result.assert_success();
+        assert_eq!(result.fee_payer(), Some(sponsor.pubkey()));
+        assert_eq!(svm.get_balance(&to), Some(3_000_000));
+        assert_eq!(svm.get_balance(&alice.pubkey()), Some(999_000_000));
+
+        // Without a payer the first signer pays
+        let result = svm
+            .transaction()
+            .add(from_alice)
+            .signer(&alice)
+            .send()
+            .unwrap();
+        assert_eq!(result.fee_payer(), Some(alice.pubkey()));
+    }
+
+    #[test]
+    fn test_transaction_builder_requires_signer() {
+        let mut svm = LiteSVM::new();
+        let ix = system_instruction::transfer(&Keypair::new().pubkey(), &Pubkey::new_unique(), 1);
+        assert!(svm.transaction().add(ix).send().is_err());
+    }
+}
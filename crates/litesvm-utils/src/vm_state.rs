@@ -0,0 +1,265 @@
+//! Helper state kept per VM
+//!
+//! `LiteSVM` has no slot for extension data, so the helpers keep their per-VM state
+//! in the VM itself, in zero-lamport accounts at [`vm_state_addresses`], each owned
+//! by its own address. They are written the first time a helper needs them and live
+//! and die with the VM. Two VMs never see each other's state, whichever thread they
+//! run on, and clones of a VM (including snapshots and forks) carry a copy of the
+//! state as it was when they were cloned.
+//!
+//! The state is split by how often it changes, so sending a transaction doesn't
+//! decode or rewrite everything the helpers remember:
+//!
+//! - the settings ([`VmState`]) are small and only written when changed,
+//! - tracked addresses are appended as raw 32-byte keys, and only when a new one
+//!   shows up,
+//! - surgery records are appended one by one and only read for the report.
+//!
+//! The accounts are helper bookkeeping, not test state: they hold no lamports, no
+//! transaction can load them as a fee payer, and [`TestHelpers::iter_accounts`] and
+//! [`save_fixtures`] skip them. Helper state that fails to decode is a bug and
+//! panics rather than being silently reset.
+//!
+//! [`TestHelpers::iter_accounts`]: crate::TestHelpers::iter_accounts
+//! [`save_fixtures`]: crate::save_fixtures
+
+use crate::surgery::SurgeryRecord;
+use crate::transaction::ComputeBudget;
+use litesvm::LiteSVM;
+use serde::{Deserialize, Serialize};
+use solana_program::hash::hashv;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+use std::panic::Location;
+use std::sync::{Mutex, OnceLock};
+
+/// The settings the helpers remember about one VM
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct VmState {
+    /// Name and address of the faucet paying helper airdrops, see [`crate::faucet`]
+    pub faucet: Option<(String, Pubkey)>,
+    /// Budget prepended to helper-built transactions, see
+    /// [`crate::transaction::set_default_compute_budget`]
+    pub default_compute_budget: Option<ComputeBudget>,
+    /// Whether helper-sent transactions record account diffs, see
+    /// [`crate::account_diff`]
+    pub record_account_diffs: bool,
+    /// Keypair seed and the index of the next keypair, see [`crate::keypairs`]
+    pub keypair_seed: Option<(u64, u64)>,
+}
+
+fn state_address(name: &[u8]) -> Pubkey {
+    Pubkey::new_from_array(hashv(&[b"litesvm-utils", name]).to_bytes())
+}
+
+fn settings_address() -> Pubkey {
+    state_address(b"vm-state")
+}
+
+fn tracked_accounts_address() -> Pubkey {
+    state_address(b"tracked-accounts")
+}
+
+fn surgery_log_address() -> Pubkey {
+    state_address(b"surgery-log")
+}
+
+/// Get the addresses of the accounts holding the helpers' per-VM state
+///
+/// Exclude them when comparing or dumping the full state of a VM.
+pub fn vm_state_addresses() -> [Pubkey; 3] {
+    [settings_address(), tracked_accounts_address(), surgery_log_address()]
+}
+
+/// Check whether `pubkey` holds helper state rather than test state
+pub(crate) fn is_vm_state_address(pubkey: &Pubkey) -> bool {
+    vm_state_addresses().contains(pubkey)
+}
+
+fn read(svm: &LiteSVM, address: Pubkey) -> Vec<u8> {
+    svm.get_account(&address)
+        .map(|account| account.data)
+        .unwrap_or_default()
+}
+
+fn write(svm: &mut LiteSVM, address: Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: 0,
+        data,
+        owner: address,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(address, account)
+        .expect("the state account is a plain data account");
+}
+
+fn load(svm: &LiteSVM) -> VmState {
+    let data = read(svm, settings_address());
+    if data.is_empty() {
+        return VmState::default();
+    }
+    bincode::deserialize(&data).expect("the helpers' per-VM settings are corrupt")
+}
+
+/// Read the VM's helper settings
+///
+/// A VM no helper has written settings for reads as [`VmState::default`].
+pub(crate) fn vm_state<R>(svm: &LiteSVM, f: impl FnOnce(&VmState) -> R) -> R {
+    f(&load(svm))
+}
+
+/// Update the VM's helper settings
+pub(crate) fn vm_state_mut<R>(svm: &mut LiteSVM, f: impl FnOnce(&mut VmState) -> R) -> R {
+    let mut state = load(svm);
+    let result = f(&mut state);
+    let data = bincode::serialize(&state).expect("helper settings serialize");
+    write(svm, settings_address(), data);
+    result
+}
+
+/// Every address tracked on the VM, in ascending order
+pub(crate) fn tracked_accounts(svm: &LiteSVM) -> Vec<Pubkey> {
+    let data = read(svm, tracked_accounts_address());
+    let mut pubkeys: Vec<Pubkey> = data
+        .chunks_exact(32)
+        .map(|key| Pubkey::try_from(key).expect("chunks are 32 bytes"))
+        .collect();
+    pubkeys.sort();
+    pubkeys.dedup();
+    pubkeys
+}
+
+/// Append the addresses the VM doesn't track yet
+///
+/// Nothing is written when every address is already tracked.
+pub(crate) fn track(svm: &mut LiteSVM, pubkeys: impl IntoIterator<Item = Pubkey>) {
+    let mut data = read(svm, tracked_accounts_address());
+    let known = data.len();
+    for pubkey in pubkeys {
+        if !data.chunks_exact(32).any(|key| key == pubkey.as_ref()) {
+            data.extend_from_slice(pubkey.as_ref());
+        }
+    }
+    if data.len() > known {
+        write(svm, tracked_accounts_address(), data);
+    }
+}
+
+/// A surgery record as stored: its call site is an index into [`locations`]
+type StoredRecord = (String, Pubkey, u64, usize);
+
+/// Process-wide table of surgery call sites
+///
+/// A `&'static Location` can't be serialized, so records store their index here.
+/// The table only grows with the number of distinct call sites in the test binary.
+fn locations() -> &'static Mutex<Vec<&'static Location<'static>>> {
+    static LOCATIONS: OnceLock<Mutex<Vec<&'static Location<'static>>>> = OnceLock::new();
+    LOCATIONS.get_or_init(Default::default)
+}
+
+fn intern(location: &'static Location<'static>) -> usize {
+    let mut locations = locations().lock().unwrap();
+    match locations.iter().position(|known| *known == location) {
+        Some(index) => index,
+        None => {
+            locations.push(location);
+            locations.len() - 1
+        }
+    }
+}
+
+/// Every surgery record of the VM, in order
+pub(crate) fn surgery_log(svm: &LiteSVM) -> Vec<SurgeryRecord> {
+    let data = read(svm, surgery_log_address());
+    let locations = locations().lock().unwrap();
+    let mut cursor = data.as_slice();
+    let mut records = Vec::new();
+    while !cursor.is_empty() {
+        let (operation, pubkey, slot, location): StoredRecord =
+            bincode::deserialize_from(&mut cursor).expect("the helpers' surgery log is corrupt");
+        let location = *locations
+            .get(location)
+            .expect("surgery records only refer to call sites of this process");
+        records.push(SurgeryRecord {
+            operation,
+            pubkey,
+            slot,
+            location,
+        });
+    }
+    records
+}
+
+/// Append a record to the VM's surgery log
+pub(crate) fn push_surgery(svm: &mut LiteSVM, record: SurgeryRecord) {
+    let stored: StoredRecord = (
+        record.operation,
+        record.pubkey,
+        record.slot,
+        intern(record.location),
+    );
+    let mut data = read(svm, surgery_log_address());
+    bincode::serialize_into(&mut data, &stored).expect("surgery records serialize");
+    write(svm, surgery_log_address(), data);
+}
+
+/// Forget the VM's surgery log
+pub(crate) fn clear_surgery_log(svm: &mut LiteSVM) {
+    write(svm, surgery_log_address(), Vec::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_is_per_vm() {
+        let mut first = LiteSVM::new();
+        let mut second = LiteSVM::new();
+        let pubkey = Pubkey::new_unique();
+
+        track(&mut first, [pubkey]);
+        vm_state_mut(&mut first, |state| state.record_account_diffs = true);
+        assert_eq!(tracked_accounts(&first), vec![pubkey]);
+        assert!(tracked_accounts(&second).is_empty());
+        assert!(!vm_state(&second, |state| state.record_account_diffs));
+
+        vm_state_mut(&mut second, |state| state.record_account_diffs = false);
+        assert!(vm_state(&first, |state| state.record_account_diffs));
+
+        // Clones carry a copy of the state of the VM they were cloned from
+        let mut clone = first.clone();
+        assert_eq!(tracked_accounts(&clone), vec![pubkey]);
+        track(&mut clone, [Pubkey::new_unique()]);
+        assert_eq!(tracked_accounts(&first), vec![pubkey]);
+
+        // The state accounts hold no lamports and can't pay fees
+        for address in vm_state_addresses().iter().take(2) {
+            let account = first.get_account(address).unwrap();
+            assert_eq!(account.lamports, 0);
+            assert_eq!(account.owner, *address);
+        }
+    }
+
+    #[test]
+    fn test_track_only_writes_new_addresses() {
+        let mut svm = LiteSVM::new();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        track(&mut svm, [a, b, a]);
+        let written = svm.get_account(&tracked_accounts_address()).unwrap();
+        assert_eq!(written.data.len(), 64);
+
+        track(&mut svm, [b, a]);
+        assert_eq!(svm.get_account(&tracked_accounts_address()).unwrap(), written);
+    }
+
+    #[test]
+    #[should_panic(expected = "per-VM settings are corrupt")]
+    fn test_corrupt_state_panics() {
+        let mut svm = LiteSVM::new();
+        write(&mut svm, settings_address(), vec![7]);
+        vm_state(&svm, |state| state.record_account_diffs);
+    }
+}
@@ -1,14 +1,14 @@
-/// Advanced features demonstration for anchor-litesvm
-///
-/// This example showcases more complex testing scenarios including:
-/// - Token operations (mint, transfer, burn)
-/// - PDA (Program Derived Address) calculations
-/// - Batch operations
-/// - Error handling and assertions
-/// - Transaction metadata analysis
-///
-/// Note: These examples demonstrate the actual working API.
-/// For runnable tests, you would need compiled Anchor program bytes.
+//! Advanced features demonstration for anchor-litesvm
+//!
+//! This example showcases more complex testing scenarios including:
+//! - Token operations (mint, transfer, burn)
+//! - PDA (Program Derived Address) calculations
+//! - Batch operations
+//! - Error handling and assertions
+//! - Transaction metadata analysis
+//!
+//! Note: These examples demonstrate the actual working API.
+//! For runnable tests, you would need compiled Anchor program bytes.
 
 use anchor_litesvm::{AnchorLiteSVM, AssertionHelpers, TestHelpers};
 use solana_sdk::signature::Signer;
@@ -1,7 +1,7 @@
-/// Example showing how anchor-litesvm provides production-compatible testing
-///
-/// This example demonstrates the 78% code reduction achieved with anchor-litesvm
-/// compared to raw LiteSVM, while maintaining the exact same syntax as anchor-client.
+//! Example showing how anchor-litesvm provides production-compatible testing
+//!
+//! This example demonstrates the 78% code reduction achieved with anchor-litesvm
+//! compared to raw LiteSVM, while maintaining the exact same syntax as anchor-client.
 
 use anchor_litesvm::{AnchorLiteSVM, AssertionHelpers, TestHelpers};
 use solana_sdk::signature::Signer;